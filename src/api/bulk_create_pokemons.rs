@@ -0,0 +1,148 @@
+use std::io::{BufRead, BufReader};
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::domain::bulk_create_pokemons::{self, ItemError};
+use crate::domain::create_pokemon;
+use crate::repositories::{ability, pokemon::Repository};
+
+use super::create_pokemon::Request;
+use super::fetch_pokemon::Response;
+use super::{ApiError, FieldError, Status};
+
+#[derive(Serialize)]
+struct Item {
+    index: usize,
+    status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pokemon: Option<Response>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<ApiError>,
+}
+
+#[derive(Serialize)]
+struct Summary {
+    created: usize,
+    results: Vec<Item>,
+}
+
+// Accepts a JSON array, or one JSON object per line with `application/x-ndjson`.
+fn read_items(req: &rouille::Request) -> Result<Vec<create_pokemon::Request>, ApiError> {
+    let ndjson = req.header("Content-Type").is_some_and(|content_type| {
+        content_type.starts_with("application/x-ndjson")
+            || content_type.starts_with("application/ndjson")
+    });
+    if !ndjson {
+        return match rouille::input::json_input::<Vec<Request>>(req) {
+            Ok(items) => Ok(items.into_iter().map(Into::into).collect()),
+            Err(e) => Err(ApiError::from(e)),
+        };
+    }
+
+    let body = match req.data() {
+        Some(body) => body,
+        None => return Err(Status::InternalServerError.into()),
+    };
+    let mut items = vec![];
+    for (i, line) in BufReader::new(body).lines().enumerate() {
+        let line = line.map_err(|e| ApiError::new(Status::BadRequest, e))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<Request>(&line) {
+            Ok(item) => items.push(item.into()),
+            Err(e) => {
+                return Err(ApiError::new(
+                    Status::BadRequest,
+                    format!("line {}: {}", i + 1, e),
+                ))
+            }
+        }
+    }
+    Ok(items)
+}
+
+pub fn item_error(error: ItemError) -> ApiError {
+    match error {
+        ItemError::Invalid(errors) => errors.into(),
+        ItemError::UnknownAbility(name) => {
+            ApiError::new(Status::BadRequest, "the request has invalid fields").with_field(
+                FieldError::new("abilities", format!("unknown ability \"{}\"", name)),
+            )
+        }
+        ItemError::Conflict => {
+            ApiError::new(Status::Conflict, "the pokemon or its name already exists")
+        }
+    }
+}
+
+pub fn serve(
+    req: &rouille::Request,
+    repo: Arc<dyn Repository>,
+    ability_repo: Arc<dyn ability::Repository>,
+) -> rouille::Response {
+    let continue_on_error = match req.get_param("continue_on_error").as_deref() {
+        None | Some("false") => false,
+        Some("true") => true,
+        Some(value) => {
+            return ApiError::new(
+                Status::BadRequest,
+                format!(
+                    "query parameter continue_on_error must be true or false, got \"{}\"",
+                    value
+                ),
+            )
+            .into()
+        }
+    };
+    let pokemons = match read_items(req) {
+        Ok(pokemons) => pokemons,
+        Err(e) => return e.into(),
+    };
+
+    let req = bulk_create_pokemons::Request {
+        pokemons,
+        continue_on_error,
+    };
+    match bulk_create_pokemons::execute(repo, ability_repo, req) {
+        Ok(results) => {
+            let results: Vec<Item> = results
+                .into_iter()
+                .enumerate()
+                .map(|(index, res)| match res {
+                    Ok(pokemon) => Item {
+                        index,
+                        status: 201,
+                        pokemon: Some(pokemon.into()),
+                        error: None,
+                    },
+                    Err(e) => {
+                        let error = item_error(e);
+                        Item {
+                            index,
+                            status: error.status.status_code(),
+                            pokemon: None,
+                            error: Some(error),
+                        }
+                    }
+                })
+                .collect();
+            let created = results.iter().filter(|item| item.error.is_none()).count();
+            let status_code = if continue_on_error { 200 } else { 201 };
+            rouille::Response::json(&Summary { created, results }).with_status_code(status_code)
+        }
+        Err(bulk_create_pokemons::Error::Rejected(index, e)) => {
+            let mut error = item_error(e);
+            error.message = format!(
+                "item {} was rejected, nothing was created: {}",
+                index, error.message
+            );
+            for field in error.errors.iter_mut() {
+                field.field = format!("[{}].{}", index, field.field);
+            }
+            error.into()
+        }
+        Err(bulk_create_pokemons::Error::Unknown) => Status::InternalServerError.into(),
+    }
+}
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{domain::calculate_stats, repositories::pokemon::Repository};
+
+use super::{pokemon_key, ApiError, FieldError, Stats, StatsRequest, Status};
+
+#[derive(Deserialize)]
+struct Request {
+    level: u16,
+    #[serde(default)]
+    ivs: StatsRequest,
+    #[serde(default)]
+    evs: StatsRequest,
+    nature: String,
+}
+
+#[derive(Serialize)]
+struct Response {
+    number: u16,
+    name: String,
+    level: u16,
+    nature: String,
+    stats: Stats,
+}
+
+pub fn serve(number: u16, req: &rouille::Request, repo: Arc<dyn Repository>) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => req,
+        Err(e) => return ApiError::from(e).into(),
+    };
+    let key = pokemon_key(number, &None);
+
+    match calculate_stats::execute(
+        repo,
+        calculate_stats::Request {
+            number,
+            level: req.level,
+            ivs: req.ivs.into(),
+            evs: req.evs.into(),
+            nature: req.nature,
+        },
+    ) {
+        Ok(calculate_stats::Response {
+            number,
+            name,
+            level,
+            nature,
+            stats,
+        }) => rouille::Response::json(&Response {
+            number,
+            name,
+            level,
+            nature,
+            stats: stats.into(),
+        }),
+        Err(calculate_stats::Error::InvalidIndividual(e)) => FieldError::new(e.field(), e).into(),
+        Err(calculate_stats::Error::MissingBaseStats) => ApiError::new(
+            Status::Conflict,
+            format!("pokemon {} has no base stats to calculate from", key),
+        )
+        .into(),
+        Err(calculate_stats::Error::NotFound) => {
+            ApiError::new(Status::NotFound, format!("pokemon {} does not exist", key)).into()
+        }
+        Err(calculate_stats::Error::BadRequest) => ApiError::new(
+            Status::BadRequest,
+            format!("{} is not a valid pokemon", key),
+        )
+        .into(),
+        Err(calculate_stats::Error::Unknown) => Status::InternalServerError.into(),
+    }
+}
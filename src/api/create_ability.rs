@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{domain::create_ability, repositories::ability::Repository};
+
+use super::{ApiError, Status};
+
+#[derive(Deserialize)]
+struct Request {
+    name: String,
+    #[serde(default)]
+    description: String,
+    generation: u8,
+}
+
+#[derive(Serialize)]
+struct Response {
+    name: String,
+    description: String,
+    generation: u8,
+}
+
+pub fn serve(req: &rouille::Request, repo: Arc<dyn Repository>) -> rouille::Response {
+    match rouille::input::json_input::<Request>(req) {
+        Ok(req) => match create_ability::execute(
+            repo,
+            create_ability::Request {
+                name: req.name.clone(),
+                description: req.description,
+                generation: req.generation,
+            },
+        ) {
+            Ok(create_ability::Response {
+                name,
+                description,
+                generation,
+            }) => rouille::Response::json(&Response {
+                name,
+                description,
+                generation,
+            }),
+            Err(create_ability::Error::BadRequest(message)) => {
+                ApiError::new(Status::BadRequest, message).into()
+            }
+            Err(create_ability::Error::Conflict) => ApiError::new(
+                Status::Conflict,
+                format!("ability \"{}\" already exists", req.name),
+            )
+            .into(),
+            Err(create_ability::Error::Unknown) => Status::InternalServerError.into(),
+        },
+        Err(e) => ApiError::from(e).into(),
+    }
+}
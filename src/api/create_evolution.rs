@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::create_evolution;
+use crate::repositories::{evolution, pokemon};
+
+use super::{pokemon_key, ApiError, Status};
+
+#[derive(Deserialize)]
+struct Request {
+    to: u16,
+    trigger: String,
+    #[serde(default)]
+    level: Option<u16>,
+    #[serde(default)]
+    item: Option<String>,
+    #[serde(default)]
+    condition: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Response {
+    from: u16,
+    to: u16,
+    trigger: String,
+    level: Option<u16>,
+    item: Option<String>,
+    condition: Option<String>,
+}
+
+pub fn serve(
+    number: u16,
+    req: &rouille::Request,
+    pokemon_repo: Arc<dyn pokemon::Repository>,
+    evolution_repo: Arc<dyn evolution::Repository>,
+) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => req,
+        Err(e) => return ApiError::from(e).into(),
+    };
+
+    let (from, to) = (pokemon_key(number, &None), pokemon_key(req.to, &None));
+    match create_evolution::execute(
+        pokemon_repo,
+        evolution_repo,
+        create_evolution::Request {
+            from: number,
+            to: req.to,
+            trigger: req.trigger,
+            level: req.level,
+            item: req.item,
+            condition: req.condition,
+        },
+    ) {
+        Ok(create_evolution::Response {
+            from,
+            to,
+            trigger,
+            level,
+            item,
+            condition,
+        }) => rouille::Response::json(&Response {
+            from,
+            to,
+            trigger,
+            level,
+            item,
+            condition,
+        }),
+        Err(create_evolution::Error::BadRequest(message)) => {
+            ApiError::new(Status::BadRequest, message).into()
+        }
+        Err(create_evolution::Error::NotFound) => ApiError::new(
+            Status::NotFound,
+            format!("pokemon {} or {} does not exist", from, to),
+        )
+        .into(),
+        Err(create_evolution::Error::Conflict) => ApiError::new(
+            Status::Conflict,
+            format!("pokemon {} already evolves from another pokemon", to),
+        )
+        .into(),
+        Err(create_evolution::Error::Cycle) => ApiError::new(
+            Status::Conflict,
+            format!("pokemon {} already evolves into {}", to, from),
+        )
+        .into(),
+        Err(create_evolution::Error::Unknown) => Status::InternalServerError.into(),
+    }
+}
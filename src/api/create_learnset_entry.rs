@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::create_learnset_entry;
+use crate::repositories::{learnset, moves, pokemon};
+
+use super::{pokemon_key, ApiError, Status};
+
+#[derive(Deserialize)]
+struct Request {
+    #[serde(rename = "move")]
+    move_name: String,
+    method: String,
+    #[serde(default)]
+    level: Option<u16>,
+}
+
+#[derive(Serialize)]
+struct Response {
+    number: u16,
+    #[serde(rename = "move")]
+    move_name: String,
+    method: String,
+    level: Option<u16>,
+}
+
+pub fn serve(
+    number: u16,
+    req: &rouille::Request,
+    pokemon_repo: Arc<dyn pokemon::Repository>,
+    move_repo: Arc<dyn moves::Repository>,
+    learnset_repo: Arc<dyn learnset::Repository>,
+) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => req,
+        Err(e) => return ApiError::from(e).into(),
+    };
+
+    let key = pokemon_key(number, &None);
+    let move_name = req.move_name.clone();
+    match create_learnset_entry::execute(
+        pokemon_repo,
+        move_repo,
+        learnset_repo,
+        create_learnset_entry::Request {
+            number,
+            move_name: req.move_name,
+            method: req.method,
+            level: req.level,
+        },
+    ) {
+        Ok(create_learnset_entry::Response {
+            number,
+            move_name,
+            method,
+            level,
+        }) => rouille::Response::json(&Response {
+            number,
+            move_name,
+            method,
+            level,
+        }),
+        Err(create_learnset_entry::Error::BadRequest(message)) => {
+            ApiError::new(Status::BadRequest, message).into()
+        }
+        Err(create_learnset_entry::Error::NotFound) => ApiError::new(
+            Status::NotFound,
+            format!("pokemon {} or move \"{}\" does not exist", key, move_name),
+        )
+        .into(),
+        Err(create_learnset_entry::Error::Conflict) => ApiError::new(
+            Status::Conflict,
+            format!("pokemon {} already learns \"{}\" that way", key, move_name),
+        )
+        .into(),
+        Err(create_learnset_entry::Error::Unknown) => Status::InternalServerError.into(),
+    }
+}
@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use serde::Deserialize;
+
+use crate::{domain::create_move, repositories::moves::Repository};
+
+use super::{ApiError, Move, Status};
+
+#[derive(Deserialize)]
+struct Request {
+    name: String,
+    #[serde(rename = "type")]
+    move_type: String,
+    category: String,
+    #[serde(default)]
+    power: Option<u8>,
+    #[serde(default)]
+    accuracy: Option<u8>,
+    pp: u8,
+    #[serde(default)]
+    priority: i8,
+}
+
+pub fn serve(req: &rouille::Request, repo: Arc<dyn Repository>) -> rouille::Response {
+    match rouille::input::json_input::<Request>(req) {
+        Ok(req) => match create_move::execute(
+            repo,
+            create_move::Request {
+                name: req.name.clone(),
+                move_type: req.move_type,
+                category: req.category,
+                power: req.power,
+                accuracy: req.accuracy,
+                pp: req.pp,
+                priority: req.priority,
+            },
+        ) {
+            Ok(m) => rouille::Response::json(&Move::from(m)),
+            Err(create_move::Error::BadRequest(message)) => {
+                ApiError::new(Status::BadRequest, message).into()
+            }
+            Err(create_move::Error::Conflict) => ApiError::new(
+                Status::Conflict,
+                format!("move \"{}\" already exists", req.name),
+            )
+            .into(),
+            Err(create_move::Error::Unknown) => Status::InternalServerError.into(),
+        },
+        Err(e) => ApiError::from(e).into(),
+    }
+}
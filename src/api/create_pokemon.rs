@@ -1,25 +1,39 @@
-use crate::{domain::create_pokemon, repositories::pokemon::Repository};
+use crate::domain::{create_pokemon, fetch_pokemon};
+use crate::repositories::{ability, pokemon::Repository};
 use core::fmt;
 use std::fmt::Display;
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 
-use super::Status;
+use super::fetch_pokemon::Response;
+use super::{api_version, etag, pokemon_key, ApiError, FieldError, StatsRequest, Status};
 
 #[derive(Deserialize, Serialize)]
-struct Request {
+pub struct Request {
     number: u16,
+    #[serde(default)]
+    form: Option<String>,
     name: String,
     types: Vec<String>,
+    #[serde(default)]
+    stats: Option<StatsRequest>,
+    #[serde(default)]
+    abilities: Vec<String>,
+    #[serde(default)]
+    hidden_ability: Option<String>,
 }
 
 impl From<Request> for create_pokemon::Request {
     fn from(req: Request) -> Self {
         Self {
             number: req.number,
+            form: req.form,
             name: req.name,
             types: req.types,
+            stats: req.stats.map(<[u16; 6]>::from),
+            abilities: req.abilities,
+            hidden_ability: req.hidden_ability,
         }
     }
 }
@@ -28,29 +42,169 @@ impl Display for Request {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "pokemon = name: {}, number: {}, types: {:?}",
-            self.number, self.name, self.types
+            "pokemon = name: {}, number: {}, types: {:?}, stats: {:?}",
+            self.number, self.name, self.types, self.stats
         )
     }
 }
 
+// The version 1 body: the created pokemon as a JSON string inside `message`.
 #[derive(Serialize)]
-struct Response {
+struct LegacyResponse {
     message: String,
 }
 
-pub fn serve(req: &rouille::Request, repo: Arc<dyn Repository>) -> rouille::Response {
-    match rouille::input::json_input::<Request>(req) {
-        Ok(req) => match create_pokemon::execute(repo, req.into()) {
-            Ok(pokemon) => rouille::Response::json(&Response {
-                message: serde_json::to_string(&pokemon).expect("expect pokemon response"),
-            }),
-            Err(create_pokemon::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
-            Err(create_pokemon::Error::Conflict) => rouille::Response::from(Status::Conflict),
-            Err(create_pokemon::Error::Unknown) => {
-                rouille::Response::from(Status::InternalServerError)
-            }
-        },
-        _ => rouille::Response::from(Status::BadRequest),
+#[derive(Serialize)]
+struct LegacyPokemon {
+    number: u16,
+    form: Option<String>,
+    name: String,
+    types: Vec<String>,
+    stats: Option<[u16; 6]>,
+    abilities: Vec<String>,
+    hidden_ability: Option<String>,
+}
+
+impl From<&fetch_pokemon::Response> for LegacyPokemon {
+    fn from(res: &fetch_pokemon::Response) -> Self {
+        Self {
+            number: res.number,
+            form: res.form.clone(),
+            name: res.name.clone(),
+            types: res.types.clone(),
+            stats: res.stats,
+            abilities: res.abilities.clone(),
+            hidden_ability: res.hidden_ability.clone(),
+        }
+    }
+}
+
+pub fn serve(
+    req: &rouille::Request,
+    repo: Arc<dyn Repository>,
+    ability_repo: Arc<dyn ability::Repository>,
+) -> rouille::Response {
+    let version = match api_version(req) {
+        Ok(version) => version,
+        Err(e) => return e.into(),
+    };
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => req,
+        Err(e) => return ApiError::from(e).into(),
+    };
+    let key = pokemon_key(req.number, &req.form);
+
+    match create_pokemon::execute(repo, ability_repo, req.into()) {
+        Ok(pokemon) if version == 1 => rouille::Response::json(&LegacyResponse {
+            message: serde_json::to_string(&LegacyPokemon::from(&pokemon))
+                .expect("expect pokemon response"),
+        }),
+        Ok(pokemon) => {
+            let location = match &pokemon.form {
+                Some(form) => format!("/{}/forms/{}", pokemon.number, form),
+                None => format!("/{}", pokemon.number),
+            };
+            let tag = etag(pokemon.version);
+            rouille::Response::json(&Response::from(pokemon))
+                .with_status_code(201)
+                .with_additional_header("Location", location)
+                .with_additional_header("ETag", tag)
+        }
+        Err(create_pokemon::Error::Invalid(errors)) => ApiError::from(errors).into(),
+        Err(create_pokemon::Error::UnknownAbility(name)) => rouille::Response::from(
+            FieldError::new("abilities", format!("unknown ability \"{}\"", name)),
+        ),
+        Err(create_pokemon::Error::Conflict) => ApiError::new(
+            Status::Conflict,
+            format!("pokemon {} or its name already exists", key),
+        )
+        .into(),
+        Err(create_pokemon::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::pokemon::InMemoryRepository;
+    use serde_json::Value;
+    use std::io::Read;
+
+    const PIKACHU: &str = r#"{"number":25,"name":"Pikachu","types":["Electric"]}"#;
+
+    fn post(api_version: Option<&str>, body: &str) -> rouille::Response {
+        let mut headers = vec![(
+            String::from("Content-Type"),
+            String::from("application/json"),
+        )];
+        if let Some(version) = api_version {
+            headers.push((String::from("X-Api-Version"), version.to_string()));
+        }
+        let req = rouille::Request::fake_http("POST", "/", headers, body.as_bytes().to_vec());
+        serve(
+            &req,
+            Arc::new(InMemoryRepository::new()),
+            Arc::new(ability::InMemoryRepository::new()),
+        )
+    }
+
+    fn header<'a>(res: &'a rouille::Response, name: &str) -> Option<&'a str> {
+        res.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_ref())
+    }
+
+    fn body(res: rouille::Response) -> Value {
+        let mut body = String::new();
+        res.data
+            .into_reader_and_size()
+            .0
+            .read_to_string(&mut body)
+            .unwrap();
+        serde_json::from_str(&body).unwrap()
+    }
+
+    #[test]
+    fn it_should_answer_with_the_version_1_body_without_a_version_header() {
+        let res = post(None, PIKACHU);
+
+        assert_eq!(res.status_code, 200);
+        assert_eq!(header(&res, "Location"), None);
+        let message = body(res)["message"].as_str().unwrap().to_string();
+        let pokemon: Value = serde_json::from_str(&message).unwrap();
+        assert_eq!(pokemon["name"], "Pikachu");
+    }
+
+    #[test]
+    fn it_should_answer_201_with_a_location_from_version_2() {
+        let res = post(Some("2"), PIKACHU);
+
+        assert_eq!(res.status_code, 201);
+        assert_eq!(header(&res, "Location"), Some("/25"));
+        assert_eq!(header(&res, "ETag"), Some("\"1\""));
+        let body = body(res);
+        assert_eq!(body["name"], "Pikachu");
+        assert_eq!(body["generation"], 1);
+    }
+
+    #[test]
+    fn it_should_point_the_location_at_the_form() {
+        let res = post(
+            Some("2"),
+            r#"{"number":25,"form":"alola","name":"Pikachu","types":["Electric"]}"#,
+        );
+
+        assert_eq!(header(&res, "Location"), Some("/25/forms/alola"));
+    }
+
+    #[test]
+    fn it_should_reject_an_unknown_version() {
+        for version in ["3", "latest"] {
+            let res = post(Some(version), PIKACHU);
+
+            assert_eq!(res.status_code, 400);
+            assert_eq!(body(res)["code"], "bad_request");
+        }
     }
 }
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::domain::delete_ability;
+use crate::repositories::{ability, pokemon};
+
+use super::{ApiError, Status};
+
+#[derive(Serialize)]
+struct Response {
+    name: String,
+    description: String,
+    generation: u8,
+}
+
+pub fn serve(
+    name: String,
+    ability_repo: Arc<dyn ability::Repository>,
+    pokemon_repo: Arc<dyn pokemon::Repository>,
+) -> rouille::Response {
+    match delete_ability::execute(name.clone(), ability_repo, pokemon_repo) {
+        Ok(delete_ability::Response {
+            name,
+            description,
+            generation,
+        }) => rouille::Response::json(&Response {
+            name,
+            description,
+            generation,
+        }),
+        Err(delete_ability::Error::BadRequest) => ApiError::new(
+            Status::BadRequest,
+            format!("\"{}\" is not a valid ability name", name),
+        )
+        .into(),
+        Err(delete_ability::Error::NotFound) => ApiError::new(
+            Status::NotFound,
+            format!("ability \"{}\" does not exist", name),
+        )
+        .into(),
+        Err(delete_ability::Error::Conflict) => ApiError::new(
+            Status::Conflict,
+            format!("ability \"{}\" is still used by a pokemon", name),
+        )
+        .into(),
+        Err(delete_ability::Error::Unknown) => Status::InternalServerError.into(),
+    }
+}
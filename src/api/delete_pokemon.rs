@@ -2,30 +2,66 @@ use std::sync::Arc;
 
 use serde::Serialize;
 
-use crate::{domain::delete_pokemon, repositories::pokemon::Repository};
+use crate::{
+    domain::delete_pokemon,
+    repositories::pokemon::{self, Repository},
+};
 
-use super::Status;
+use super::{if_match, pokemon_key, ApiError, Stats, Status};
 
 #[derive(Serialize)]
 pub struct Response {
     number: u16,
+    form: Option<String>,
     name: String,
     types: Vec<String>,
+    stats: Option<Stats>,
+    abilities: Vec<String>,
+    hidden_ability: Option<String>,
 }
 
-pub fn serve(req: u16, repo: Arc<dyn Repository>) -> rouille::Response {
-    match delete_pokemon::execute(req, repo) {
+pub fn serve(
+    number: u16,
+    form: Option<String>,
+    req: &rouille::Request,
+    repo: Arc<dyn Repository>,
+) -> rouille::Response {
+    let key = pokemon_key(number, &form);
+    let version = match if_match(req) {
+        Ok(version) => version,
+        Err(e) => return e.into(),
+    };
+    match delete_pokemon::execute(number, form, version, pokemon::now(), repo) {
         Ok(delete_pokemon::Response {
             number,
+            form,
             name,
             types,
+            stats,
+            abilities,
+            hidden_ability,
         }) => rouille::Response::json(&Response {
             number,
+            form,
             name,
             types,
+            stats: stats.map(Stats::from),
+            abilities,
+            hidden_ability,
         }),
-        Err(delete_pokemon::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(delete_pokemon::Error::BadRequest) => ApiError::new(
+            Status::BadRequest,
+            format!("{} is not a valid pokemon", key),
+        )
+        .into(),
         Err(delete_pokemon::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
-        Err(delete_pokemon::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(delete_pokemon::Error::NotFound) => {
+            ApiError::new(Status::NotFound, format!("pokemon {} does not exist", key)).into()
+        }
+        Err(delete_pokemon::Error::PreconditionFailed) => ApiError::new(
+            Status::PreconditionFailed,
+            format!("pokemon {} has changed since it was fetched", key),
+        )
+        .into(),
     }
 }
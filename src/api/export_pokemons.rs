@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use crate::api::{ApiError, Status};
+use crate::domain::export_pokemons::{self, Format};
+use crate::repositories::pokemon::Repository;
+
+pub fn serve(req: &rouille::Request, repo: Arc<dyn Repository>) -> rouille::Response {
+    let format = req
+        .get_param("format")
+        .unwrap_or_else(|| String::from("json"));
+    let (content_type, extension) = match Format::try_from(format.as_str()) {
+        Ok(Format::Csv) => ("text/csv; charset=utf-8", "csv"),
+        Ok(Format::Ndjson) => ("application/x-ndjson", "ndjson"),
+        Ok(Format::Json) => ("application/json", "json"),
+        Err(e) => return ApiError::new(Status::BadRequest, e).into(),
+    };
+
+    match export_pokemons::execute(
+        repo,
+        export_pokemons::Request {
+            format: format.clone(),
+        },
+    ) {
+        Ok(data) => rouille::Response::from_data(content_type, data).with_additional_header(
+            "Content-Disposition",
+            format!("attachment; filename=\"pokedex.{}\"", extension),
+        ),
+        Err(export_pokemons::Error::BadRequest) => {
+            ApiError::new(Status::BadRequest, format!("unknown format \"{}\"", format)).into()
+        }
+        Err(export_pokemons::Error::Unknown) => Status::InternalServerError.into(),
+    }
+}
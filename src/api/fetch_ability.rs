@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::{domain::fetch_ability, repositories::ability::Repository};
+
+use super::{ApiError, Status};
+
+#[derive(Serialize)]
+struct Response {
+    name: String,
+    description: String,
+    generation: u8,
+}
+
+pub fn serve(name: String, repo: Arc<dyn Repository>) -> rouille::Response {
+    match fetch_ability::execute(repo, fetch_ability::Request { name: name.clone() }) {
+        Ok(fetch_ability::Response {
+            name,
+            description,
+            generation,
+        }) => rouille::Response::json(&Response {
+            name,
+            description,
+            generation,
+        }),
+        Err(fetch_ability::Error::NotFound) => ApiError::new(
+            Status::NotFound,
+            format!("ability \"{}\" does not exist", name),
+        )
+        .into(),
+        Err(fetch_ability::Error::BadRequest) => ApiError::new(
+            Status::BadRequest,
+            format!("\"{}\" is not a valid ability name", name),
+        )
+        .into(),
+        Err(fetch_ability::Error::Unknown) => Status::InternalServerError.into(),
+    }
+}
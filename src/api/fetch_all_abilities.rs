@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::{domain::fetch_all_abilities, repositories::ability::Repository};
+
+use super::Status;
+
+#[derive(Serialize)]
+struct Response {
+    name: String,
+    description: String,
+    generation: u8,
+}
+
+pub fn serve(repo: Arc<dyn Repository>) -> rouille::Response {
+    match fetch_all_abilities::execute(repo) {
+        Ok(res) => rouille::Response::json(
+            &res.into_iter()
+                .map(|a| Response {
+                    name: a.name,
+                    description: a.description,
+                    generation: a.generation,
+                })
+                .collect::<Vec<Response>>(),
+        ),
+        Err(fetch_all_abilities::Error::Unknown) => Status::InternalServerError.into(),
+    }
+}
@@ -0,0 +1,12 @@
+use std::sync::Arc;
+
+use crate::{domain::fetch_all_moves, repositories::moves::Repository};
+
+use super::{Move, Status};
+
+pub fn serve(repo: Arc<dyn Repository>) -> rouille::Response {
+    match fetch_all_moves::execute(repo) {
+        Ok(res) => rouille::Response::json(&res.into_iter().map(Move::from).collect::<Vec<Move>>()),
+        Err(fetch_all_moves::Error::Unknown) => Status::InternalServerError.into(),
+    }
+}
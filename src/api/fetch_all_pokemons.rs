@@ -1,30 +1,121 @@
-use crate::api::Status;
+use crate::api::{ApiError, Stats, Status};
 use crate::domain::fetch_all_pokemons;
 use crate::repositories::pokemon::Repository;
 
 use serde::Serialize;
+use std::str::FromStr;
 use std::sync::Arc;
 
 #[derive(Serialize)]
 pub struct Response {
     pub number: u16,
+    pub form: Option<String>,
     pub name: String,
     pub types: Vec<String>,
+    pub stats: Option<Stats>,
+    pub abilities: Vec<String>,
+    pub hidden_ability: Option<String>,
+    pub generation: Option<u8>,
+    pub region: Option<String>,
 }
 
-pub fn serve(repo: Arc<dyn Repository>) -> rouille::Response {
-    match fetch_all_pokemons::execute(repo) {
-        Ok(res) => rouille::Response::json(
-            &res.into_iter()
+fn param<T: FromStr>(req: &rouille::Request, name: &str) -> Result<Option<T>, ApiError> {
+    match req.get_param(name) {
+        Some(value) => value.parse().map(Some).map_err(|_| {
+            ApiError::new(
+                Status::BadRequest,
+                format!(
+                    "query parameter {} must be a number, got \"{}\"",
+                    name, value
+                ),
+            )
+        }),
+        None => Ok(None),
+    }
+}
+
+fn request(req: &rouille::Request) -> Result<fetch_all_pokemons::Request, ApiError> {
+    Ok(fetch_all_pokemons::Request {
+        generation: param(req, "generation")?,
+        pokemon_type: req.get_param("type"),
+        name: req.get_param("name"),
+        name_prefix: req.get_param("name_prefix"),
+        min_number: param(req, "min_number")?,
+        max_number: param(req, "max_number")?,
+        sort: req.get_param("sort"),
+        order: req.get_param("order"),
+        offset: param(req, "offset")?,
+        limit: param(req, "limit")?,
+    })
+}
+
+pub fn serve(req: &rouille::Request, repo: Arc<dyn Repository>) -> rouille::Response {
+    let req = match request(req) {
+        Ok(req) => req,
+        Err(e) => return e.into(),
+    };
+
+    match fetch_all_pokemons::execute(repo, req) {
+        Ok(page) => rouille::Response::json(
+            &page
+                .pokemons
+                .into_iter()
                 .map(|p| Response {
                     number: p.number,
+                    form: p.form,
                     name: p.name,
                     types: p.types,
+                    stats: p.stats.map(Stats::from),
+                    abilities: p.abilities,
+                    hidden_ability: p.hidden_ability,
+                    generation: p.generation,
+                    region: p.region,
                 })
                 .collect::<Vec<Response>>(),
-        ),
+        )
+        .with_additional_header("X-Total-Count", page.total.to_string()),
+        Err(fetch_all_pokemons::Error::BadRequest(message)) => {
+            ApiError::new(Status::BadRequest, message).into()
+        }
         Err(fetch_all_pokemons::Error::Unknown) => {
             rouille::Response::from(Status::InternalServerError)
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn get(url: &str) -> (u16, String) {
+        let req = rouille::Request::fake_http("GET", url, vec![], vec![]);
+        let res = serve(&req, Arc::new(InMemoryRepository::new()));
+        let mut body = String::new();
+        res.data
+            .into_reader_and_size()
+            .0
+            .read_to_string(&mut body)
+            .unwrap();
+        (res.status_code, body)
+    }
+
+    #[test]
+    fn it_should_name_the_query_parameter_that_is_not_a_number() {
+        let (status, body) = get("/?offset=ten");
+
+        assert_eq!(status, 400);
+        assert!(body.contains("offset"));
+        assert!(body.contains("ten"));
+    }
+
+    #[test]
+    fn it_should_explain_an_invalid_filter() {
+        let (status, body) = get("/?sort=weight");
+
+        assert_eq!(status, 400);
+        assert!(body.contains("weight"));
+    }
+}
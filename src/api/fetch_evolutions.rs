@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::domain::fetch_evolutions;
+use crate::repositories::{evolution, pokemon};
+
+use super::{pokemon_key, ApiError, Status};
+
+#[derive(Serialize)]
+struct Trigger {
+    trigger: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    level: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    item: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    condition: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Response {
+    number: u16,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    evolution: Option<Trigger>,
+    evolves_to: Vec<Response>,
+}
+
+impl From<fetch_evolutions::Response> for Response {
+    fn from(res: fetch_evolutions::Response) -> Self {
+        Self {
+            number: res.number,
+            name: res.name,
+            evolution: res.evolution.map(|t| Trigger {
+                trigger: t.trigger,
+                level: t.level,
+                item: t.item,
+                condition: t.condition,
+            }),
+            evolves_to: res.evolves_to.into_iter().map(Response::from).collect(),
+        }
+    }
+}
+
+pub fn serve(
+    number: u16,
+    pokemon_repo: Arc<dyn pokemon::Repository>,
+    evolution_repo: Arc<dyn evolution::Repository>,
+) -> rouille::Response {
+    match fetch_evolutions::execute(
+        pokemon_repo,
+        evolution_repo,
+        fetch_evolutions::Request { number },
+    ) {
+        Ok(res) => rouille::Response::json(&Response::from(res)),
+        Err(fetch_evolutions::Error::NotFound) => ApiError::new(
+            Status::NotFound,
+            format!("pokemon {} does not exist", pokemon_key(number, &None)),
+        )
+        .into(),
+        Err(fetch_evolutions::Error::BadRequest) => ApiError::new(
+            Status::BadRequest,
+            format!("{} is not a valid pokemon", pokemon_key(number, &None)),
+        )
+        .into(),
+        Err(fetch_evolutions::Error::Unknown) => Status::InternalServerError.into(),
+    }
+}
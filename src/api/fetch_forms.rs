@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use crate::api::fetch_pokemon::Response;
+use crate::api::{pokemon_key, ApiError, Status};
+use crate::domain::fetch_forms;
+use crate::repositories::pokemon::Repository;
+
+pub fn serve(number: u16, repo: Arc<dyn Repository>) -> rouille::Response {
+    match fetch_forms::execute(repo, fetch_forms::Request { number }) {
+        Ok(res) => rouille::Response::json(
+            &res.into_iter()
+                .map(Response::from)
+                .collect::<Vec<Response>>(),
+        ),
+        Err(fetch_forms::Error::NotFound) => ApiError::new(
+            Status::NotFound,
+            format!("pokemon {} does not exist", pokemon_key(number, &None)),
+        )
+        .into(),
+        Err(fetch_forms::Error::BadRequest) => ApiError::new(
+            Status::BadRequest,
+            format!("{} is not a valid pokemon", pokemon_key(number, &None)),
+        )
+        .into(),
+        Err(fetch_forms::Error::Unknown) => Status::InternalServerError.into(),
+    }
+}
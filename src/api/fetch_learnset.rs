@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::domain::fetch_learnset;
+use crate::repositories::{learnset, moves, pokemon};
+
+use super::{pokemon_key, ApiError, Status};
+
+#[derive(Serialize)]
+struct Response {
+    name: String,
+    #[serde(rename = "type")]
+    move_type: String,
+    category: String,
+    power: Option<u8>,
+    accuracy: Option<u8>,
+    pp: u8,
+    priority: i8,
+    method: String,
+    level: Option<u16>,
+}
+
+pub fn serve(
+    number: u16,
+    req: &rouille::Request,
+    pokemon_repo: Arc<dyn pokemon::Repository>,
+    move_repo: Arc<dyn moves::Repository>,
+    learnset_repo: Arc<dyn learnset::Repository>,
+) -> rouille::Response {
+    match fetch_learnset::execute(
+        pokemon_repo,
+        move_repo,
+        learnset_repo,
+        fetch_learnset::Request {
+            number,
+            method: req.get_param("method"),
+        },
+    ) {
+        Ok(res) => rouille::Response::json(
+            &res.into_iter()
+                .map(|m| Response {
+                    name: m.name,
+                    move_type: m.move_type,
+                    category: m.category,
+                    power: m.power,
+                    accuracy: m.accuracy,
+                    pp: m.pp,
+                    priority: m.priority,
+                    method: m.method,
+                    level: m.level,
+                })
+                .collect::<Vec<Response>>(),
+        ),
+        Err(fetch_learnset::Error::NotFound) => ApiError::new(
+            Status::NotFound,
+            format!("pokemon {} does not exist", pokemon_key(number, &None)),
+        )
+        .into(),
+        Err(fetch_learnset::Error::BadRequest(message)) => {
+            ApiError::new(Status::BadRequest, message).into()
+        }
+        Err(fetch_learnset::Error::Unknown) => Status::InternalServerError.into(),
+    }
+}
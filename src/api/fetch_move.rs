@@ -0,0 +1,22 @@
+use std::sync::Arc;
+
+use crate::{domain::fetch_move, repositories::moves::Repository};
+
+use super::{ApiError, Move, Status};
+
+pub fn serve(name: String, repo: Arc<dyn Repository>) -> rouille::Response {
+    match fetch_move::execute(repo, fetch_move::Request { name: name.clone() }) {
+        Ok(m) => rouille::Response::json(&Move::from(m)),
+        Err(fetch_move::Error::NotFound) => ApiError::new(
+            Status::NotFound,
+            format!("move \"{}\" does not exist", name),
+        )
+        .into(),
+        Err(fetch_move::Error::BadRequest) => ApiError::new(
+            Status::BadRequest,
+            format!("\"{}\" is not a valid move name", name),
+        )
+        .into(),
+        Err(fetch_move::Error::Unknown) => Status::InternalServerError.into(),
+    }
+}
@@ -1,31 +1,144 @@
 use serde::Serialize;
 
-use crate::api::Status;
+use crate::api::{etag, not_modified, pokemon_key, ApiError, Stats, Status};
 use std::sync::Arc;
 
-use crate::domain::fetch_pokemon;
+use crate::domain::{fetch_pokemon, fetch_pokemon_by_name};
 use crate::repositories::pokemon::Repository;
 
 #[derive(Serialize)]
 pub struct Response {
     number: u16,
+    form: Option<String>,
     name: String,
     types: Vec<String>,
+    stats: Option<Stats>,
+    abilities: Vec<String>,
+    hidden_ability: Option<String>,
+    generation: Option<u8>,
+    region: Option<String>,
 }
 
-pub fn serve(number: u16, repo: Arc<dyn Repository>) -> rouille::Response {
-    match fetch_pokemon::execute(repo, fetch_pokemon::Request { number }) {
-        Ok(fetch_pokemon::Response {
+impl From<fetch_pokemon::Response> for Response {
+    fn from(res: fetch_pokemon::Response) -> Self {
+        let fetch_pokemon::Response {
             number,
+            form,
             name,
             types,
-        }) => rouille::Response::json(&Response {
+            stats,
+            abilities,
+            hidden_ability,
+            generation,
+            region,
+            // Sent as the ETag header rather than in the body.
+            version: _,
+        } = res;
+        Self {
             number,
+            form,
             name,
             types,
-        }),
-        Err(fetch_pokemon::Error::NotFound) => Status::NotFound.into(),
-        Err(fetch_pokemon::Error::BadRequest) => Status::BadRequest.into(),
+            stats: stats.map(Stats::from),
+            abilities,
+            hidden_ability,
+            generation,
+            region,
+        }
+    }
+}
+
+pub fn serve(
+    number: u16,
+    form: Option<String>,
+    req: &rouille::Request,
+    repo: Arc<dyn Repository>,
+) -> rouille::Response {
+    let key = pokemon_key(number, &form);
+    match fetch_pokemon::execute(repo, fetch_pokemon::Request { number, form }) {
+        Ok(res) if not_modified(req, res.version) => rouille::Response::empty_204()
+            .with_status_code(304)
+            .with_additional_header("ETag", etag(res.version)),
+        Ok(res) => {
+            let tag = etag(res.version);
+            rouille::Response::json(&Response::from(res)).with_additional_header("ETag", tag)
+        }
+        Err(fetch_pokemon::Error::NotFound) => {
+            ApiError::new(Status::NotFound, format!("pokemon {} does not exist", key)).into()
+        }
+        Err(fetch_pokemon::Error::BadRequest) => ApiError::new(
+            Status::BadRequest,
+            format!("{} is not a valid pokemon", key),
+        )
+        .into(),
+        Err(fetch_pokemon::Error::Unknown) => Status::InternalServerError.into(),
+    }
+}
+
+pub fn serve_by_name(
+    name: String,
+    req: &rouille::Request,
+    repo: Arc<dyn Repository>,
+) -> rouille::Response {
+    let query = fetch_pokemon_by_name::Request { name: name.clone() };
+    match fetch_pokemon_by_name::execute(repo, query) {
+        Ok(res) if not_modified(req, res.version) => rouille::Response::empty_204()
+            .with_status_code(304)
+            .with_additional_header("ETag", etag(res.version)),
+        Ok(res) => {
+            let tag = etag(res.version);
+            rouille::Response::json(&Response::from(res)).with_additional_header("ETag", tag)
+        }
+        Err(fetch_pokemon::Error::NotFound) => ApiError::new(
+            Status::NotFound,
+            format!("no pokemon is named \"{}\"", name),
+        )
+        .into(),
+        Err(fetch_pokemon::Error::BadRequest) => {
+            ApiError::new(Status::BadRequest, "a pokemon name cannot be empty").into()
+        }
         Err(fetch_pokemon::Error::Unknown) => Status::InternalServerError.into(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        BaseStats, PokemonAbilities, PokemonForm, PokemonName, PokemonNumber, PokemonTypes,
+    };
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn get_by_name(if_none_match: &str) -> rouille::Response {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            Some(BaseStats::pikachu()),
+            PokemonAbilities::pikachu(),
+        )
+        .ok()
+        .expect("pokemon to be inserted");
+        let headers = vec![(String::from("If-None-Match"), String::from(if_none_match))];
+        let req = rouille::Request::fake_http("GET", "/name/pikachu", headers, vec![]);
+        serve_by_name(String::from("pikachu"), &req, repo)
+    }
+
+    #[test]
+    fn it_should_answer_304_by_name_when_the_etag_matches() {
+        let res = get_by_name("\"1\"");
+
+        assert_eq!(res.status_code, 304);
+        assert!(res
+            .headers
+            .iter()
+            .any(|(name, value)| name == "ETag" && value == "\"1\""));
+    }
+
+    #[test]
+    fn it_should_answer_200_by_name_when_the_etag_is_stale() {
+        assert_eq!(get_by_name("\"2\"").status_code, 200);
+    }
+}
@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::{domain::fetch_trash, repositories::pokemon::Repository};
+
+use super::Status;
+
+#[derive(Serialize)]
+struct Response {
+    number: u16,
+    form: Option<String>,
+    name: String,
+    types: Vec<String>,
+    deleted_at: u64,
+}
+
+pub fn serve(repo: Arc<dyn Repository>) -> rouille::Response {
+    match fetch_trash::execute(repo) {
+        Ok(res) => rouille::Response::json(
+            &res.into_iter()
+                .map(|t| Response {
+                    number: t.number,
+                    form: t.form,
+                    name: t.name,
+                    types: t.types,
+                    deleted_at: t.deleted_at,
+                })
+                .collect::<Vec<Response>>(),
+        ),
+        Err(fetch_trash::Error::Unknown) => Status::InternalServerError.into(),
+    }
+}
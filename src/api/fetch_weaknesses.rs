@@ -0,0 +1,64 @@
+use serde::Serialize;
+
+use crate::api::{pokemon_key, ApiError, Status};
+use std::sync::Arc;
+
+use crate::domain::fetch_weaknesses;
+use crate::repositories::pokemon::Repository;
+
+#[derive(Serialize)]
+struct Matchup {
+    #[serde(rename = "type")]
+    attacking_type: String,
+    multiplier: f32,
+}
+
+impl From<fetch_weaknesses::Matchup> for Matchup {
+    fn from(m: fetch_weaknesses::Matchup) -> Self {
+        Self {
+            attacking_type: m.attacking_type,
+            multiplier: m.multiplier,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Response {
+    number: u16,
+    name: String,
+    types: Vec<String>,
+    weaknesses: Vec<Matchup>,
+    resistances: Vec<Matchup>,
+    immunities: Vec<Matchup>,
+}
+
+pub fn serve(number: u16, repo: Arc<dyn Repository>) -> rouille::Response {
+    match fetch_weaknesses::execute(repo, fetch_weaknesses::Request { number }) {
+        Ok(fetch_weaknesses::Response {
+            number,
+            name,
+            types,
+            weaknesses,
+            resistances,
+            immunities,
+        }) => rouille::Response::json(&Response {
+            number,
+            name,
+            types,
+            weaknesses: weaknesses.into_iter().map(Matchup::from).collect(),
+            resistances: resistances.into_iter().map(Matchup::from).collect(),
+            immunities: immunities.into_iter().map(Matchup::from).collect(),
+        }),
+        Err(fetch_weaknesses::Error::NotFound) => ApiError::new(
+            Status::NotFound,
+            format!("pokemon {} does not exist", pokemon_key(number, &None)),
+        )
+        .into(),
+        Err(fetch_weaknesses::Error::BadRequest) => ApiError::new(
+            Status::BadRequest,
+            format!("{} is not a valid pokemon", pokemon_key(number, &None)),
+        )
+        .into(),
+        Err(fetch_weaknesses::Error::Unknown) => Status::InternalServerError.into(),
+    }
+}
@@ -0,0 +1,99 @@
+use std::io::Read;
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::domain::import_pokemons;
+use crate::repositories::{ability, pokemon::Repository};
+
+use super::bulk_create_pokemons::item_error;
+use super::{ApiError, Status};
+
+#[derive(Serialize)]
+struct Rejection {
+    line: usize,
+    error: ApiError,
+}
+
+#[derive(Serialize)]
+struct Response {
+    imported: usize,
+    rejected: Vec<Rejection>,
+}
+
+// An explicit `format` wins over the format implied by the Content-Type.
+fn format(req: &rouille::Request) -> String {
+    if let Some(format) = req.get_param("format") {
+        return format;
+    }
+    match req.header("Content-Type") {
+        Some(t) if t.starts_with("text/csv") => String::from("csv"),
+        Some(t) if t.starts_with("application/x-ndjson") || t.starts_with("application/ndjson") => {
+            String::from("ndjson")
+        }
+        _ => String::from("json"),
+    }
+}
+
+pub fn serve(
+    req: &rouille::Request,
+    repo: Arc<dyn Repository>,
+    ability_repo: Arc<dyn ability::Repository>,
+) -> rouille::Response {
+    let continue_on_error = match req.get_param("continue_on_error").as_deref() {
+        None | Some("false") => false,
+        Some("true") => true,
+        Some(value) => {
+            return ApiError::new(
+                Status::BadRequest,
+                format!(
+                    "query parameter continue_on_error must be true or false, got \"{}\"",
+                    value
+                ),
+            )
+            .into()
+        }
+    };
+    let format = format(req);
+    let mut data = String::new();
+    match req.data().map(|mut body| body.read_to_string(&mut data)) {
+        Some(Ok(_)) => {}
+        Some(Err(e)) => return ApiError::new(Status::BadRequest, e).into(),
+        None => return Status::InternalServerError.into(),
+    }
+
+    let req = import_pokemons::Request {
+        format,
+        data,
+        continue_on_error,
+    };
+    match import_pokemons::execute(repo, ability_repo, req) {
+        Ok(res) => rouille::Response::json(&Response {
+            imported: res.imported,
+            rejected: res
+                .rejected
+                .into_iter()
+                .map(|rejection| Rejection {
+                    line: rejection.line,
+                    error: item_error(rejection.error),
+                })
+                .collect(),
+        })
+        .with_status_code(if continue_on_error { 200 } else { 201 }),
+        Err(import_pokemons::Error::BadRequest) => {
+            ApiError::new(Status::BadRequest, "format must be csv, ndjson or json").into()
+        }
+        Err(import_pokemons::Error::Parse(line, message)) => {
+            ApiError::new(Status::BadRequest, format!("line {}: {}", line, message)).into()
+        }
+        Err(import_pokemons::Error::Rejected(line, e)) => {
+            let mut error = item_error(e);
+            error.message = format!(
+                "line {} was rejected, nothing was imported: {}",
+                line, error.message
+            );
+            error.into()
+        }
+        Err(import_pokemons::Error::Unknown) => Status::InternalServerError.into(),
+    }
+}
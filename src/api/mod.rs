@@ -1,47 +1,479 @@
-use crate::repositories::pokemon::Repository;
-use std::sync::Arc;
+use crate::config::{Config, LogLevel};
+use crate::domain::entities::ValidationError;
+use crate::repositories::{ability, evolution, learnset, moves, pokemon::Repository};
+use rouille::input::json::JsonError;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
+mod bulk_create_pokemons;
+mod calculate_stats;
+mod create_ability;
+mod create_evolution;
+mod create_learnset_entry;
+mod create_move;
 mod create_pokemon;
+mod delete_ability;
 mod delete_pokemon;
+mod export_pokemons;
+mod fetch_ability;
+mod fetch_all_abilities;
+mod fetch_all_moves;
 mod fetch_all_pokemons;
+mod fetch_evolutions;
+mod fetch_forms;
+mod fetch_learnset;
+mod fetch_move;
 mod fetch_pokemon;
+mod fetch_trash;
+mod fetch_weaknesses;
 mod health;
+mod import_pokemons;
+mod restore_pokemon;
+mod search_pokemons;
+mod update_ability;
+mod update_pokemon;
 
+#[derive(Clone, Copy)]
 enum Status {
     BadRequest,
     NotFound,
     Conflict,
+    PreconditionFailed,
     InternalServerError,
 }
 
-impl From<Status> for rouille::Response {
-    fn from(status: Status) -> Self {
-        let status_code = match status {
+impl Status {
+    fn status_code(self) -> u16 {
+        match self {
             Status::BadRequest => 400,
             Status::NotFound => 404,
             Status::Conflict => 409,
+            Status::PreconditionFailed => 412,
             Status::InternalServerError => 500,
-        };
+        }
+    }
+
+    fn code(self) -> &'static str {
+        match self {
+            Status::BadRequest => "bad_request",
+            Status::NotFound => "not_found",
+            Status::Conflict => "conflict",
+            Status::PreconditionFailed => "precondition_failed",
+            Status::InternalServerError => "internal_server_error",
+        }
+    }
+
+    fn message(self) -> &'static str {
+        match self {
+            Status::BadRequest => "the request is invalid",
+            Status::NotFound => "the resource does not exist",
+            Status::Conflict => "the resource conflicts with an existing one",
+            Status::PreconditionFailed => "the resource has changed since it was fetched",
+            Status::InternalServerError => "an unexpected error happened",
+        }
+    }
+}
+
+impl From<Status> for rouille::Response {
+    fn from(status: Status) -> Self {
+        ApiError::from(status).into()
+    }
+}
+
+// Newer response shapes are opt-in through `X-Api-Version`, so clients that send no
+// header keep getting version 1.
+const API_VERSIONS: std::ops::RangeInclusive<u8> = 1..=2;
+
+fn api_version(req: &rouille::Request) -> Result<u8, ApiError> {
+    let header = match req.header("X-Api-Version") {
+        Some(header) => header,
+        None => return Ok(*API_VERSIONS.start()),
+    };
+    match header.trim().parse() {
+        Ok(version) if API_VERSIONS.contains(&version) => Ok(version),
+        _ => Err(ApiError::new(
+            Status::BadRequest,
+            format!(
+                "X-Api-Version {} is not supported, expected {} to {}",
+                header,
+                API_VERSIONS.start(),
+                API_VERSIONS.end()
+            ),
+        )),
+    }
+}
+
+// How messages refer to a pokemon, e.g. "#25" or "#25 (alola)", here and in the admin tool.
+pub fn pokemon_key(number: u16, form: &Option<String>) -> String {
+    match form {
+        Some(form) => format!("#{} ({})", number, form),
+        None => format!("#{}", number),
+    }
+}
+
+// A Pokémon's ETag is its version, which the repository bumps on every change.
+fn etag(version: u64) -> String {
+    format!("\"{}\"", version)
+}
 
+// `If-None-Match` uses the weak comparison, so `W/"3"` matches version 3 too.
+fn not_modified(req: &rouille::Request, version: u64) -> bool {
+    req.header("If-None-Match").is_some_and(|tags| {
+        tags.split(',')
+            .map(str::trim)
+            .any(|tag| tag == "*" || tag.strip_prefix("W/").unwrap_or(tag) == etag(version))
+    })
+}
+
+// The version an `If-Match` header requires, or `None` when any version will do. Only a
+// single strong ETag can name a version; anything else can never match.
+fn if_match(req: &rouille::Request) -> Result<Option<u64>, ApiError> {
+    let tag = match req.header("If-Match").map(str::trim) {
+        None | Some("*") => return Ok(None),
+        Some(tag) => tag,
+    };
+    match tag
+        .strip_prefix('"')
+        .and_then(|tag| tag.strip_suffix('"'))
+        .and_then(|version| version.parse().ok())
+    {
+        Some(version) => Ok(Some(version)),
+        None => Err(ApiError::new(
+            Status::PreconditionFailed,
+            format!("If-Match {} does not name a version of this pokemon", tag),
+        )),
+    }
+}
+
+// The body of every error response, whatever the status.
+#[derive(Serialize)]
+struct ApiError {
+    #[serde(skip)]
+    status: Status,
+    code: &'static str,
+    message: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    errors: Vec<FieldError>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
+}
+
+impl ApiError {
+    fn new(status: Status, message: impl ToString) -> Self {
         Self {
-            status_code,
-            headers: vec![],
-            data: rouille::ResponseBody::empty(),
-            upgrade: None,
+            status,
+            code: status.code(),
+            message: message.to_string(),
+            errors: vec![],
+            request_id: None,
+        }
+    }
+
+    fn with_field(mut self, error: FieldError) -> Self {
+        self.errors.push(error);
+        self
+    }
+}
+
+impl From<Vec<ValidationError>> for ApiError {
+    fn from(errors: Vec<ValidationError>) -> Self {
+        errors.into_iter().fold(
+            Self::new(Status::BadRequest, "the request has invalid fields"),
+            |error, e| error.with_field(FieldError::new(e.field(), e)),
+        )
+    }
+}
+
+// rouille's own message for a parse failure leaves out what serde said was wrong.
+impl From<JsonError> for ApiError {
+    fn from(error: JsonError) -> Self {
+        match error {
+            JsonError::ParseError(e) => Self::new(
+                Status::BadRequest,
+                format!("the JSON body is invalid: {}", e),
+            ),
+            e => Self::new(Status::BadRequest, e),
         }
     }
 }
 
-pub fn serve(url: &str, repo: Arc<dyn Repository>) {
-    rouille::start_server(url, move |req| {
-        router!(
+impl From<Status> for ApiError {
+    fn from(status: Status) -> Self {
+        Self::new(status, status.message())
+    }
+}
+
+impl From<ApiError> for rouille::Response {
+    fn from(mut error: ApiError) -> Self {
+        error.request_id = REQUEST_ID.with(|id| id.borrow().clone());
+        rouille::Response::json(&error).with_status_code(error.status.status_code())
+    }
+}
+
+thread_local! {
+    // Rouille handles a request from start to finish on one thread, so error
+    // responses built deep inside a handler can still report its id.
+    static REQUEST_ID: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+// Reuses the caller's `X-Request-Id` when it is sensible, otherwise makes one up.
+fn request_id(req: &rouille::Request) -> String {
+    static NEXT: AtomicU64 = AtomicU64::new(1);
+    static STARTED: OnceLock<u64> = OnceLock::new();
+
+    match req.header("X-Request-Id") {
+        Some(id) if (1..=128).contains(&id.len()) && id.bytes().all(|b| b.is_ascii_graphic()) => {
+            id.to_string()
+        }
+        _ => {
+            let started = STARTED.get_or_init(|| {
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |d| d.as_secs())
+            });
+            format!("{:x}-{:06x}", started, NEXT.fetch_add(1, Ordering::Relaxed))
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct StatsRequest {
+    hp: u16,
+    attack: u16,
+    defense: u16,
+    special_attack: u16,
+    special_defense: u16,
+    speed: u16,
+}
+
+impl From<StatsRequest> for [u16; 6] {
+    fn from(req: StatsRequest) -> Self {
+        [
+            req.hp,
+            req.attack,
+            req.defense,
+            req.special_attack,
+            req.special_defense,
+            req.speed,
+        ]
+    }
+}
+
+impl From<[u16; 6]> for StatsRequest {
+    fn from(stats: [u16; 6]) -> Self {
+        let [hp, attack, defense, special_attack, special_defense, speed] = stats;
+        Self {
+            hp,
+            attack,
+            defense,
+            special_attack,
+            special_defense,
+            speed,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Stats {
+    hp: u16,
+    attack: u16,
+    defense: u16,
+    special_attack: u16,
+    special_defense: u16,
+    speed: u16,
+    total: u16,
+}
+
+impl From<[u16; 6]> for Stats {
+    fn from(stats: [u16; 6]) -> Self {
+        let [hp, attack, defense, special_attack, special_defense, speed] = stats;
+        Self {
+            hp,
+            attack,
+            defense,
+            special_attack,
+            special_defense,
+            speed,
+            total: stats.iter().sum(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Move {
+    name: String,
+    #[serde(rename = "type")]
+    move_type: String,
+    category: String,
+    power: Option<u8>,
+    accuracy: Option<u8>,
+    pp: u8,
+    priority: i8,
+}
+
+impl From<crate::domain::create_move::Response> for Move {
+    fn from(m: crate::domain::create_move::Response) -> Self {
+        Self {
+            name: m.name,
+            move_type: m.move_type,
+            category: m.category,
+            power: m.power,
+            accuracy: m.accuracy,
+            pp: m.pp,
+            priority: m.priority,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct FieldError {
+    field: String,
+    message: String,
+}
+
+impl FieldError {
+    fn new(field: &str, message: impl ToString) -> Self {
+        Self {
+            field: field.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl From<FieldError> for rouille::Response {
+    fn from(error: FieldError) -> Self {
+        ApiError::new(Status::BadRequest, "the request has invalid fields")
+            .with_field(error)
+            .into()
+    }
+}
+
+// Successful requests are logged at info, client errors at warn and server errors at error.
+fn log(
+    log_level: LogLevel,
+    req: &rouille::Request,
+    res: &rouille::Response,
+    start: Instant,
+    id: &str,
+) {
+    let level = match res.status_code {
+        500.. => LogLevel::Error,
+        400.. => LogLevel::Warn,
+        _ => LogLevel::Info,
+    };
+    if level <= log_level {
+        eprintln!(
+            "{} {} {} {} {:.1}ms {}",
+            level,
+            req.method(),
+            req.raw_url(),
+            res.status_code,
+            start.elapsed().as_secs_f64() * 1000.0,
+            id
+        );
+    }
+}
+
+pub fn serve(
+    config: &Config,
+    repo: Arc<dyn Repository>,
+    evolution_repo: Arc<dyn evolution::Repository>,
+    ability_repo: Arc<dyn ability::Repository>,
+    move_repo: Arc<dyn moves::Repository>,
+    learnset_repo: Arc<dyn learnset::Repository>,
+) -> Result<(), String> {
+    let log_level = config.log_level;
+    let server = rouille::Server::new(config.bind.as_str(), move |req| {
+        let start = Instant::now();
+        let request_id = request_id(req);
+        REQUEST_ID.with(|id| *id.borrow_mut() = Some(request_id.clone()));
+
+        let response = router!(
         req,
         (GET)(/health) => {health::serve()},
-        (GET)(/) => {fetch_all_pokemons::serve(repo.clone())},
-        (GET)(/{number:u16}) => {fetch_pokemon::serve(number, repo.clone())},
-        (DELETE)(/{number:u16}) => {delete_pokemon::serve(number, repo.clone())},
-        (POST)(/) => {create_pokemon::serve(req, repo.clone())},
+        (GET)(/) => {fetch_all_pokemons::serve(req, repo.clone())},
+        (GET)(/{number:u16}) => {fetch_pokemon::serve(number, None, req, repo.clone())},
+        (GET)(/{number:u16}/forms) => {fetch_forms::serve(number, repo.clone())},
+        (GET)(/{number:u16}/forms/{form:String}) => {
+            fetch_pokemon::serve(number, Some(form), req, repo.clone())
+        },
+        (PUT)(/{number:u16}/forms/{form:String}) => {
+            update_pokemon::serve(number, Some(form), req, repo.clone(), ability_repo.clone())
+        },
+        (PATCH)(/{number:u16}/forms/{form:String}) => {
+            update_pokemon::serve_patch(number, Some(form), req, repo.clone(), ability_repo.clone())
+        },
+        (DELETE)(/{number:u16}/forms/{form:String}) => {
+            delete_pokemon::serve(number, Some(form), req, repo.clone())
+        },
+        (GET)(/name/{name:String}) => {fetch_pokemon::serve_by_name(name, req, repo.clone())},
+        (GET)(/search) => {search_pokemons::serve(req, repo.clone())},
+        (POST)(/bulk) => {bulk_create_pokemons::serve(req, repo.clone(), ability_repo.clone())},
+        (GET)(/export) => {export_pokemons::serve(req, repo.clone())},
+        (POST)(/import) => {import_pokemons::serve(req, repo.clone(), ability_repo.clone())},
+        (GET)(/{number:u16}/weaknesses) => {fetch_weaknesses::serve(number, repo.clone())},
+        (GET)(/{number:u16}/evolutions) => {
+            fetch_evolutions::serve(number, repo.clone(), evolution_repo.clone())
+        },
+        (POST)(/{number:u16}/evolutions) => {
+            create_evolution::serve(number, req, repo.clone(), evolution_repo.clone())
+        },
+        (GET)(/{number:u16}/moves) => {
+            fetch_learnset::serve(number, req, repo.clone(), move_repo.clone(), learnset_repo.clone())
+        },
+        (POST)(/{number:u16}/moves) => {
+            create_learnset_entry::serve(
+                number,
+                req,
+                repo.clone(),
+                move_repo.clone(),
+                learnset_repo.clone(),
+            )
+        },
+        (POST)(/{number:u16}/stats) => {calculate_stats::serve(number, req, repo.clone())},
+        (PUT)(/{number:u16}) => {
+            update_pokemon::serve(number, None, req, repo.clone(), ability_repo.clone())
+        },
+        (PATCH)(/{number:u16}) => {
+            update_pokemon::serve_patch(number, None, req, repo.clone(), ability_repo.clone())
+        },
+        (DELETE)(/{number:u16}) => {delete_pokemon::serve(number, None, req, repo.clone())},
+        (GET)(/trash) => {fetch_trash::serve(repo.clone())},
+        (POST)(/trash/{number:u16}/restore) => {
+            restore_pokemon::serve(number, None, repo.clone(), ability_repo.clone())
+        },
+        (POST)(/trash/{number:u16}/forms/{form:String}/restore) => {
+            restore_pokemon::serve(number, Some(form), repo.clone(), ability_repo.clone())
+        },
+        (POST)(/) => {create_pokemon::serve(req, repo.clone(), ability_repo.clone())},
+        (GET)(/abilities) => {fetch_all_abilities::serve(ability_repo.clone())},
+        (POST)(/abilities) => {create_ability::serve(req, ability_repo.clone())},
+        (GET)(/abilities/{name:String}) => {fetch_ability::serve(name, ability_repo.clone())},
+        (PUT)(/abilities/{name:String}) => {
+            update_ability::serve(name, req, ability_repo.clone())
+        },
+        (DELETE)(/abilities/{name:String}) => {
+            delete_ability::serve(name, ability_repo.clone(), repo.clone())
+        },
+        (GET)(/moves) => {fetch_all_moves::serve(move_repo.clone())},
+        (POST)(/moves) => {create_move::serve(req, move_repo.clone())},
+        (GET)(/moves/{name:String}) => {fetch_move::serve(name, move_repo.clone())},
         _ => rouille::Response::from(Status::NotFound)
-        )
-    });
+        );
+        log(log_level, req, &response, start, &request_id);
+        response.with_additional_header("X-Request-Id", request_id)
+    })
+    .map_err(|e| format!("cannot listen on {}: {}", config.bind, e))?;
+    if log_level >= LogLevel::Info {
+        eprintln!(
+            "info listening on {} with {} workers",
+            server.server_addr(),
+            config.workers
+        );
+    }
+    server.pool_size(config.workers).run();
+    Ok(())
 }
@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use crate::domain::restore_pokemon;
+use crate::repositories::{ability, pokemon::Repository};
+
+use super::{etag, fetch_pokemon::Response, pokemon_key, ApiError, Status};
+
+pub fn serve(
+    number: u16,
+    form: Option<String>,
+    repo: Arc<dyn Repository>,
+    ability_repo: Arc<dyn ability::Repository>,
+) -> rouille::Response {
+    let key = pokemon_key(number, &form);
+    match restore_pokemon::execute(
+        repo,
+        ability_repo,
+        restore_pokemon::Request { number, form },
+    ) {
+        Ok(res) => {
+            let tag = etag(res.version);
+            rouille::Response::json(&Response::from(res)).with_additional_header("ETag", tag)
+        }
+        Err(restore_pokemon::Error::BadRequest) => ApiError::new(
+            Status::BadRequest,
+            format!("{} is not a valid pokemon", key),
+        )
+        .into(),
+        Err(restore_pokemon::Error::NotFound) => ApiError::new(
+            Status::NotFound,
+            format!("pokemon {} is not in the trash", key),
+        )
+        .into(),
+        Err(restore_pokemon::Error::Conflict) => ApiError::new(
+            Status::Conflict,
+            format!(
+                "pokemon {} or its name has been taken since it was deleted",
+                key
+            ),
+        )
+        .into(),
+        Err(restore_pokemon::Error::Unknown) => Status::InternalServerError.into(),
+    }
+}
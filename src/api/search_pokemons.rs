@@ -0,0 +1,56 @@
+use crate::api::{ApiError, Status};
+use crate::domain::search_pokemons;
+use crate::repositories::pokemon::Repository;
+
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Serialize)]
+struct Response {
+    number: u16,
+    form: Option<String>,
+    name: String,
+    types: Vec<String>,
+    score: f32,
+}
+
+pub fn serve(req: &rouille::Request, repo: Arc<dyn Repository>) -> rouille::Response {
+    let max_results = match req.get_param("max_results") {
+        Some(n) => match n.parse() {
+            Ok(n) => Some(n),
+            Err(_) => {
+                return ApiError::new(
+                    Status::BadRequest,
+                    format!(
+                        "query parameter max_results must be a number, got \"{}\"",
+                        n
+                    ),
+                )
+                .into()
+            }
+        },
+        None => None,
+    };
+    let req = search_pokemons::Request {
+        q: req.get_param("q").unwrap_or_default(),
+        max_results,
+    };
+
+    match search_pokemons::execute(repo, req) {
+        Ok(res) => rouille::Response::json(
+            &res.into_iter()
+                .map(|p| Response {
+                    number: p.number,
+                    form: p.form,
+                    name: p.name,
+                    types: p.types,
+                    score: p.score,
+                })
+                .collect::<Vec<Response>>(),
+        ),
+        Err(search_pokemons::Error::BadRequest(message)) => {
+            ApiError::new(Status::BadRequest, message).into()
+        }
+        Err(search_pokemons::Error::Unknown) => Status::InternalServerError.into(),
+    }
+}
@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{domain::update_ability, repositories::ability::Repository};
+
+use super::{ApiError, Status};
+
+#[derive(Deserialize)]
+struct Request {
+    #[serde(default)]
+    description: String,
+    generation: u8,
+}
+
+#[derive(Serialize)]
+struct Response {
+    name: String,
+    description: String,
+    generation: u8,
+}
+
+pub fn serve(name: String, req: &rouille::Request, repo: Arc<dyn Repository>) -> rouille::Response {
+    match rouille::input::json_input::<Request>(req) {
+        Ok(req) => match update_ability::execute(
+            repo,
+            update_ability::Request {
+                name: name.clone(),
+                description: req.description,
+                generation: req.generation,
+            },
+        ) {
+            Ok(update_ability::Response {
+                name,
+                description,
+                generation,
+            }) => rouille::Response::json(&Response {
+                name,
+                description,
+                generation,
+            }),
+            Err(update_ability::Error::BadRequest(message)) => {
+                ApiError::new(Status::BadRequest, message).into()
+            }
+            Err(update_ability::Error::NotFound) => ApiError::new(
+                Status::NotFound,
+                format!("ability \"{}\" does not exist", name),
+            )
+            .into(),
+            Err(update_ability::Error::Unknown) => Status::InternalServerError.into(),
+        },
+        Err(e) => ApiError::from(e).into(),
+    }
+}
@@ -0,0 +1,447 @@
+use std::io::Read;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::domain::{fetch_pokemon, update_pokemon};
+use crate::repositories::{ability, pokemon::Repository};
+
+use super::fetch_pokemon::Response;
+use super::{etag, if_match, pokemon_key, ApiError, FieldError, StatsRequest, Status};
+
+#[derive(Deserialize, Serialize)]
+struct Request {
+    name: String,
+    types: Vec<String>,
+    #[serde(default)]
+    stats: Option<StatsRequest>,
+    #[serde(default)]
+    abilities: Vec<String>,
+    #[serde(default)]
+    hidden_ability: Option<String>,
+}
+
+impl From<fetch_pokemon::Response> for Request {
+    fn from(res: fetch_pokemon::Response) -> Self {
+        Self {
+            name: res.name,
+            types: res.types,
+            stats: res.stats.map(StatsRequest::from),
+            abilities: res.abilities,
+            hidden_ability: res.hidden_ability,
+        }
+    }
+}
+
+pub fn serve(
+    number: u16,
+    form: Option<String>,
+    req: &rouille::Request,
+    repo: Arc<dyn Repository>,
+    ability_repo: Arc<dyn ability::Repository>,
+) -> rouille::Response {
+    let version = match if_match(req) {
+        Ok(version) => version,
+        Err(e) => return e.into(),
+    };
+    match rouille::input::json_input::<Request>(req) {
+        Ok(req) => update(number, form, version, req, repo, ability_repo),
+        Err(e) => ApiError::from(e).into(),
+    }
+}
+
+// Applies an RFC 7396 merge patch to the stored Pokémon and saves the result as a full update.
+pub fn serve_patch(
+    number: u16,
+    form: Option<String>,
+    req: &rouille::Request,
+    repo: Arc<dyn Repository>,
+    ability_repo: Arc<dyn ability::Repository>,
+) -> rouille::Response {
+    let version = match if_match(req) {
+        Ok(version) => version,
+        Err(e) => return e.into(),
+    };
+    let patch = match read_merge_patch(req) {
+        Ok(patch) => patch,
+        Err(e) => return e.into(),
+    };
+
+    let (current, fetched) = match fetch_pokemon::execute(
+        repo.clone(),
+        fetch_pokemon::Request {
+            number,
+            form: form.clone(),
+        },
+    ) {
+        Ok(res) => {
+            let fetched = res.version;
+            (Request::from(res), fetched)
+        }
+        Err(fetch_pokemon::Error::NotFound) => {
+            return ApiError::new(
+                Status::NotFound,
+                format!("pokemon {} does not exist", pokemon_key(number, &form)),
+            )
+            .into()
+        }
+        Err(fetch_pokemon::Error::BadRequest) => {
+            return ApiError::new(
+                Status::BadRequest,
+                format!("{} is not a valid pokemon", pokemon_key(number, &form)),
+            )
+            .into()
+        }
+        Err(fetch_pokemon::Error::Unknown) => return Status::InternalServerError.into(),
+    };
+
+    // The patch was merged into what was fetched, so a write since then must not be lost.
+    let version = version.or(Some(fetched));
+    let mut target = serde_json::to_value(current).expect("pokemon to serialize");
+    merge_patch(&mut target, patch);
+    match serde_json::from_value::<Request>(target) {
+        Ok(req) => update(number, form, version, req, repo, ability_repo),
+        Err(e) => ApiError::new(
+            Status::BadRequest,
+            format!("the patched pokemon is invalid: {}", e),
+        )
+        .into(),
+    }
+}
+
+fn read_merge_patch(req: &rouille::Request) -> Result<Value, ApiError> {
+    match req.header("Content-Type") {
+        Some(content_type)
+            if content_type.starts_with("application/merge-patch+json")
+                || content_type.starts_with("application/json") => {}
+        _ => {
+            return Err(ApiError::new(
+                Status::BadRequest,
+                "a patch needs Content-Type application/merge-patch+json or application/json",
+            ))
+        }
+    }
+    let mut body = String::new();
+    match req.data().map(|mut data| data.read_to_string(&mut body)) {
+        Some(Ok(_)) => {}
+        _ => return Err(ApiError::new(Status::BadRequest, "the body cannot be read")),
+    }
+    serde_json::from_str(&body).map_err(|e| {
+        ApiError::new(
+            Status::BadRequest,
+            format!("the body is not a JSON merge patch: {}", e),
+        )
+    })
+}
+
+fn merge_patch(target: &mut Value, patch: Value) {
+    match patch {
+        Value::Object(patch) => {
+            if !target.is_object() {
+                *target = Value::Object(Map::new());
+            }
+            if let Value::Object(target) = target {
+                for (key, value) in patch {
+                    if value.is_null() {
+                        target.remove(&key);
+                    } else {
+                        merge_patch(target.entry(key).or_insert(Value::Null), value);
+                    }
+                }
+            }
+        }
+        patch => *target = patch,
+    }
+}
+
+fn update(
+    number: u16,
+    form: Option<String>,
+    version: Option<u64>,
+    req: Request,
+    repo: Arc<dyn Repository>,
+    ability_repo: Arc<dyn ability::Repository>,
+) -> rouille::Response {
+    let key = pokemon_key(number, &form);
+    match update_pokemon::execute(
+        repo,
+        ability_repo,
+        update_pokemon::Request {
+            number,
+            form,
+            name: req.name,
+            types: req.types,
+            stats: req.stats.map(<[u16; 6]>::from),
+            abilities: req.abilities,
+            hidden_ability: req.hidden_ability,
+            version,
+        },
+    ) {
+        Ok(res) => {
+            let tag = etag(res.version);
+            rouille::Response::json(&Response::from(res)).with_additional_header("ETag", tag)
+        }
+        Err(update_pokemon::Error::Invalid(errors)) => ApiError::from(errors).into(),
+        Err(update_pokemon::Error::UnknownAbility(name)) => {
+            FieldError::new("abilities", format!("unknown ability \"{}\"", name)).into()
+        }
+        Err(update_pokemon::Error::NotFound) => {
+            ApiError::new(Status::NotFound, format!("pokemon {} does not exist", key)).into()
+        }
+        Err(update_pokemon::Error::Conflict) => ApiError::new(
+            Status::Conflict,
+            "the name already belongs to another pokemon",
+        )
+        .into(),
+        Err(update_pokemon::Error::PreconditionFailed) => ApiError::new(
+            Status::PreconditionFailed,
+            format!("pokemon {} has changed since it was fetched", key),
+        )
+        .into(),
+        Err(update_pokemon::Error::Unknown) => Status::InternalServerError.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::Pokemon;
+    use crate::domain::entities::{
+        Ability, AbilityName, BaseStats, Generation, PokemonAbilities, PokemonForm, PokemonName,
+        PokemonNumber, PokemonTypes,
+    };
+    use crate::repositories::ability::Repository as _;
+    use crate::repositories::pokemon::{
+        BulkMode, DeleteError, FetchAllError, FetchError, InMemoryRepository, InsertError,
+        InsertManyError, PurgeError, RestoreError, Tombstone, UpdateError,
+    };
+    use std::sync::Once;
+
+    // Another client renames the Pokémon right after the first fetch.
+    struct Racing(InMemoryRepository, Once);
+
+    impl Repository for Racing {
+        fn insert(
+            &self,
+            number: PokemonNumber,
+            form: PokemonForm,
+            name: PokemonName,
+            types: PokemonTypes,
+            stats: Option<BaseStats>,
+            abilities: PokemonAbilities,
+        ) -> Result<Pokemon, InsertError> {
+            self.0.insert(number, form, name, types, stats, abilities)
+        }
+
+        fn insert_many(
+            &self,
+            pokemons: Vec<Pokemon>,
+            mode: BulkMode,
+        ) -> Result<Vec<Result<Pokemon, InsertError>>, InsertManyError> {
+            self.0.insert_many(pokemons, mode)
+        }
+
+        fn fetch_all(&self) -> Result<Vec<Pokemon>, FetchAllError> {
+            self.0.fetch_all()
+        }
+
+        fn fetch(&self, number: PokemonNumber, form: &PokemonForm) -> Result<Pokemon, FetchError> {
+            let pokemon = self.0.fetch(number, form)?;
+            self.1.call_once(|| {
+                let renamed = Pokemon {
+                    name: PokemonName::try_from(String::from("Sparky")).unwrap(),
+                    ..pokemon.clone()
+                };
+                self.0
+                    .update(renamed, None)
+                    .ok()
+                    .expect("pokemon to be updated");
+            });
+            Ok(pokemon)
+        }
+
+        fn fetch_by_name(&self, name: &PokemonName) -> Result<Pokemon, FetchError> {
+            self.0.fetch_by_name(name)
+        }
+
+        fn fetch_by_trigrams(&self, name: &PokemonName) -> Result<Vec<Pokemon>, FetchAllError> {
+            self.0.fetch_by_trigrams(name)
+        }
+
+        fn fetch_forms(&self, number: PokemonNumber) -> Result<Vec<Pokemon>, FetchError> {
+            self.0.fetch_forms(number)
+        }
+
+        fn update(&self, pokemon: Pokemon, version: Option<u64>) -> Result<Pokemon, UpdateError> {
+            self.0.update(pokemon, version)
+        }
+
+        fn delete(
+            &self,
+            number: PokemonNumber,
+            form: &PokemonForm,
+            version: Option<u64>,
+            deleted_at: u64,
+        ) -> Result<Pokemon, DeleteError> {
+            self.0.delete(number, form, version, deleted_at)
+        }
+
+        fn fetch_trash(&self) -> Result<Vec<Tombstone>, FetchAllError> {
+            self.0.fetch_trash()
+        }
+
+        fn restore(
+            &self,
+            number: PokemonNumber,
+            form: &PokemonForm,
+        ) -> Result<Pokemon, RestoreError> {
+            self.0.restore(number, form)
+        }
+
+        fn purge_trash(&self, deleted_before: u64) -> Result<usize, PurgeError> {
+            self.0.purge_trash(deleted_before)
+        }
+    }
+
+    fn repos() -> (Arc<InMemoryRepository>, Arc<ability::InMemoryRepository>) {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            Some(BaseStats::pikachu()),
+            PokemonAbilities::pikachu(),
+        )
+        .ok()
+        .expect("pokemon to be inserted");
+        let ability_repo = Arc::new(ability::InMemoryRepository::new());
+        for name in ["Static", "Lightning Rod", "Surge Surfer"] {
+            ability_repo
+                .insert(Ability::new(
+                    AbilityName::try_from(String::from(name)).unwrap(),
+                    String::new(),
+                    Generation::try_from(3).unwrap(),
+                ))
+                .ok()
+                .expect("ability to be inserted");
+        }
+        (repo, ability_repo)
+    }
+
+    fn patch(content_type: Option<&str>, body: &str) -> (u16, Value) {
+        let (repo, ability_repo) = repos();
+        let headers = content_type
+            .map(|content_type| vec![(String::from("Content-Type"), content_type.to_string())])
+            .unwrap_or_default();
+        let req = rouille::Request::fake_http("PATCH", "/25", headers, body.as_bytes().to_vec());
+        read(serve_patch(25, None, &req, repo, ability_repo))
+    }
+
+    fn put(body: &str) -> (u16, Value) {
+        let (repo, ability_repo) = repos();
+        let headers = vec![(
+            String::from("Content-Type"),
+            String::from("application/json"),
+        )];
+        let req = rouille::Request::fake_http("PUT", "/25", headers, body.as_bytes().to_vec());
+        read(serve(25, None, &req, repo, ability_repo))
+    }
+
+    fn read(res: rouille::Response) -> (u16, Value) {
+        let mut body = String::new();
+        res.data
+            .into_reader_and_size()
+            .0
+            .read_to_string(&mut body)
+            .unwrap();
+        (res.status_code, serde_json::from_str(&body).unwrap())
+    }
+
+    #[test]
+    fn it_should_explain_why_a_put_body_could_not_be_parsed() {
+        let (status, body) = put(r#"{"name":"Raichu"}"#);
+
+        assert_eq!(status, 400);
+        assert_eq!(body["code"], "bad_request");
+        assert!(body["message"].as_str().unwrap().contains("types"));
+    }
+
+    #[test]
+    fn it_should_explain_a_missing_json_content_type() {
+        let (status, body) = patch(None, r#"{"name":"Raichu"}"#);
+
+        assert_eq!(status, 400);
+        assert_eq!(body["code"], "bad_request");
+        assert!(body["message"]
+            .as_str()
+            .unwrap()
+            .contains("application/merge-patch+json"));
+    }
+
+    #[test]
+    fn it_should_keep_the_fields_a_patch_leaves_out() {
+        let (status, body) = patch(
+            Some("application/merge-patch+json"),
+            r#"{"name":"Pikachu Libre"}"#,
+        );
+
+        assert_eq!(status, 200);
+        assert_eq!(body["name"], "Pikachu Libre");
+        assert_eq!(body["types"], serde_json::json!(["Electric"]));
+        assert_eq!(body["stats"]["hp"], 35);
+        assert_eq!(body["hidden_ability"], "Lightning Rod");
+    }
+
+    #[test]
+    fn it_should_remove_null_fields_and_merge_nested_objects() {
+        let (status, body) = patch(
+            Some("application/json"),
+            r#"{"hidden_ability":null,"stats":{"speed":110},"abilities":["Surge Surfer"]}"#,
+        );
+
+        assert_eq!(status, 200);
+        assert_eq!(body["hidden_ability"], Value::Null);
+        assert_eq!(body["stats"]["speed"], 110);
+        assert_eq!(body["stats"]["hp"], 35);
+        assert_eq!(body["abilities"], serde_json::json!(["Surge Surfer"]));
+    }
+
+    #[test]
+    fn it_should_refuse_a_patch_when_the_pokemon_changed_after_it_was_read() {
+        let (repo, ability_repo) = repos();
+        let repo = Arc::try_unwrap(repo)
+            .ok()
+            .expect("repository not to be shared");
+        let racing = Arc::new(Racing(repo, Once::new()));
+        let headers = vec![(
+            String::from("Content-Type"),
+            String::from("application/merge-patch+json"),
+        )];
+        let body = br#"{"types":["Electric","Steel"]}"#.to_vec();
+        let req = rouille::Request::fake_http("PATCH", "/25", headers, body);
+
+        let res = serve_patch(25, None, &req, racing.clone(), ability_repo);
+
+        assert_eq!(res.status_code, 412);
+        match racing
+            .0
+            .fetch(PokemonNumber::pikachu(), &PokemonForm::default())
+        {
+            Ok(pokemon) => {
+                assert_eq!(String::from(pokemon.name), "Sparky");
+                assert_eq!(pokemon.version, 2);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_reject_a_patch_that_leaves_a_required_field_out() {
+        let (status, body) = patch(Some("application/json"), r#"{"name":null}"#);
+
+        assert_eq!(status, 400);
+        assert!(body["message"].as_str().unwrap().contains("name"));
+    }
+}
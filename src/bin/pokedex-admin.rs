@@ -0,0 +1,669 @@
+use clap::{Parser, Subcommand};
+use pokedex::api::pokemon_key;
+use pokedex::config::{self, Backend, Config};
+use pokedex::domain::bulk_create_pokemons::ItemError;
+use pokedex::domain::{
+    create_pokemon, delete_pokemon, export_pokemons, fetch_all_pokemons, fetch_pokemon,
+    import_pokemons,
+};
+use pokedex::repositories::{ability, pokemon};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+
+#[derive(Parser)]
+#[command(
+    name = "pokedex-admin",
+    about = "Manage the Pokédex repository without going through the HTTP API"
+)]
+struct Cli {
+    /// TOML file to read, defaults to POKEDEX_CONFIG or ./pokedex.toml when it exists
+    #[arg(long, global = true)]
+    config: Option<String>,
+    /// sqlite or file
+    #[arg(long, global = true)]
+    backend: Option<String>,
+    /// SQLite database file or data directory of the file backend
+    #[arg(long, global = true)]
+    path: Option<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List Pokémon, one per line
+    List {
+        #[arg(long = "type")]
+        pokemon_type: Option<String>,
+        #[arg(long)]
+        name: Option<String>,
+        #[arg(long)]
+        generation: Option<u8>,
+        #[arg(long)]
+        limit: Option<usize>,
+    },
+    /// Show a Pokémon
+    Get {
+        number: u16,
+        #[arg(long)]
+        form: Option<String>,
+    },
+    /// Create a Pokémon
+    Add {
+        #[arg(long)]
+        number: u16,
+        #[arg(long)]
+        form: Option<String>,
+        #[arg(long)]
+        name: String,
+        #[arg(long = "type", required = true)]
+        types: Vec<String>,
+        /// hp,attack,defense,special_attack,special_defense,speed
+        #[arg(long, value_delimiter = ',')]
+        stats: Option<Vec<u16>>,
+        /// Must be in the ability catalogue
+        #[arg(long = "ability")]
+        abilities: Vec<String>,
+        /// Must be in the ability catalogue
+        #[arg(long)]
+        hidden_ability: Option<String>,
+    },
+    /// Move a Pokémon to the trash
+    Delete {
+        number: u16,
+        #[arg(long)]
+        form: Option<String>,
+        /// Only delete if the Pokémon is still at this version
+        #[arg(long)]
+        if_version: Option<u64>,
+    },
+    /// Import Pokémon from a CSV, NDJSON or JSON file
+    Import {
+        file: String,
+        /// Defaults to the file extension
+        #[arg(long)]
+        format: Option<String>,
+        #[arg(long)]
+        continue_on_error: bool,
+    },
+    /// Export every Pokémon to stdout or a file
+    Export {
+        #[arg(long, default_value = "json")]
+        format: String,
+        #[arg(long)]
+        output: Option<String>,
+    },
+    /// Count Pokémon by type and generation
+    Stats,
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let args = config::Args {
+        config: cli.config,
+        backend: cli.backend,
+        path: cli.path,
+        ..Default::default()
+    };
+    let config = match Config::load(args) {
+        Ok(config) => config,
+        Err(errors) => {
+            for e in errors {
+                eprintln!("error: {}", e);
+            }
+            std::process::exit(1);
+        }
+    };
+    if let Err(e) = run(config, cli.command) {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run(config: Config, command: Command) -> Result<(), String> {
+    // An in-memory repository would be gone as soon as the command returns.
+    if config.backend == Backend::Memory {
+        return Err(String::from(
+            "pass --backend sqlite or --backend file, with --path, to the repository to manage",
+        ));
+    }
+    config.configure_dex_range().map_err(|e| e.to_string())?;
+    let repos = config.open_repositories()?;
+    let repo = repos.pokemon;
+
+    match command {
+        Command::List {
+            pokemon_type,
+            name,
+            generation,
+            limit,
+        } => list(repo, pokemon_type, name, generation, limit),
+        Command::Get { number, form } => get(repo, number, form),
+        Command::Add {
+            number,
+            form,
+            name,
+            types,
+            stats,
+            abilities,
+            hidden_ability,
+        } => {
+            let stats = match stats {
+                Some(stats) => {
+                    Some(<[u16; 6]>::try_from(stats).map_err(|_| "stats needs exactly 6 values")?)
+                }
+                None => None,
+            };
+            let req = create_pokemon::Request {
+                number,
+                form,
+                name,
+                types,
+                stats,
+                abilities,
+                hidden_ability,
+            };
+            add(repo, repos.ability, req)
+        }
+        Command::Delete {
+            number,
+            form,
+            if_version,
+        } => delete(repo, number, form, if_version),
+        Command::Import {
+            file,
+            format,
+            continue_on_error,
+        } => import(repo, repos.ability, file, format, continue_on_error),
+        Command::Export { format, output } => export(repo, format, output),
+        Command::Stats => stats(repo),
+    }
+}
+
+fn describe(error: ItemError) -> String {
+    match error {
+        ItemError::Invalid(errors) => errors
+            .iter()
+            .map(|e| format!("{}: {}", e.field(), e))
+            .collect::<Vec<_>>()
+            .join("; "),
+        ItemError::UnknownAbility(name) => {
+            format!(
+                "unknown ability \"{}\", add it to the catalogue first",
+                name
+            )
+        }
+        ItemError::Conflict => String::from("the pokemon or its name already exists"),
+    }
+}
+
+fn list(
+    repo: Arc<dyn pokemon::Repository>,
+    pokemon_type: Option<String>,
+    name: Option<String>,
+    generation: Option<u8>,
+    limit: Option<usize>,
+) -> Result<(), String> {
+    let req = fetch_all_pokemons::Request {
+        pokemon_type,
+        name,
+        generation,
+        limit,
+        ..Default::default()
+    };
+    match fetch_all_pokemons::execute(repo, req) {
+        Ok(page) => {
+            for pokemon in page.pokemons {
+                println!(
+                    "{:<16} {:<16} {}",
+                    pokemon_key(pokemon.number, &pokemon.form),
+                    pokemon.name,
+                    pokemon.types.join("/")
+                );
+            }
+            if let Some(limit) = limit.filter(|&limit| limit < page.total) {
+                eprintln!("showing {} of {}", limit, page.total);
+            }
+            Ok(())
+        }
+        Err(fetch_all_pokemons::Error::BadRequest(message)) => Err(message),
+        Err(fetch_all_pokemons::Error::Unknown) => Err(String::from("cannot read the repository")),
+    }
+}
+
+fn get(
+    repo: Arc<dyn pokemon::Repository>,
+    number: u16,
+    form: Option<String>,
+) -> Result<(), String> {
+    let req = fetch_pokemon::Request {
+        number,
+        form: form.clone(),
+    };
+    match fetch_pokemon::execute(repo, req) {
+        Ok(res) => {
+            println!("{} {}", pokemon_key(res.number, &res.form), res.name);
+            println!("version: {}", res.version);
+            println!("types: {}", res.types.join("/"));
+            if let Some(stats) = res.stats {
+                let stats: Vec<String> = stats.iter().map(u16::to_string).collect();
+                println!("stats: {}", stats.join(","));
+            }
+            if !res.abilities.is_empty() {
+                println!("abilities: {}", res.abilities.join(", "));
+            }
+            if let Some(hidden_ability) = res.hidden_ability {
+                println!("hidden ability: {}", hidden_ability);
+            }
+            if let (Some(generation), Some(region)) = (res.generation, res.region) {
+                println!("generation: {} ({})", generation, region);
+            }
+            Ok(())
+        }
+        Err(fetch_pokemon::Error::BadRequest) => Err(String::from("invalid number or form")),
+        Err(fetch_pokemon::Error::NotFound) => {
+            Err(format!("{} does not exist", pokemon_key(number, &form)))
+        }
+        Err(fetch_pokemon::Error::Unknown) => Err(String::from("cannot read the repository")),
+    }
+}
+
+fn add(
+    repo: Arc<dyn pokemon::Repository>,
+    ability_repo: Arc<dyn ability::Repository>,
+    req: create_pokemon::Request,
+) -> Result<(), String> {
+    match create_pokemon::execute(repo, ability_repo, req) {
+        Ok(res) => {
+            println!(
+                "created {} {}",
+                pokemon_key(res.number, &res.form),
+                res.name
+            );
+            Ok(())
+        }
+        Err(create_pokemon::Error::Invalid(errors)) => Err(describe(ItemError::Invalid(errors))),
+        Err(create_pokemon::Error::UnknownAbility(name)) => {
+            Err(describe(ItemError::UnknownAbility(name)))
+        }
+        Err(create_pokemon::Error::Conflict) => Err(describe(ItemError::Conflict)),
+        Err(create_pokemon::Error::Unknown) => Err(String::from("cannot write the repository")),
+    }
+}
+
+fn delete(
+    repo: Arc<dyn pokemon::Repository>,
+    number: u16,
+    form: Option<String>,
+    version: Option<u64>,
+) -> Result<(), String> {
+    match delete_pokemon::execute(number, form.clone(), version, pokemon::now(), repo) {
+        Ok(res) => {
+            println!(
+                "moved {} {} to the trash",
+                pokemon_key(res.number, &res.form),
+                res.name
+            );
+            Ok(())
+        }
+        Err(delete_pokemon::Error::BadRequest) => Err(String::from("invalid number or form")),
+        Err(delete_pokemon::Error::NotFound) => {
+            Err(format!("{} does not exist", pokemon_key(number, &form)))
+        }
+        Err(delete_pokemon::Error::PreconditionFailed) => Err(format!(
+            "{} is no longer at version {}",
+            pokemon_key(number, &form),
+            version.unwrap_or_default()
+        )),
+        Err(delete_pokemon::Error::Unknown) => Err(String::from("cannot write the repository")),
+    }
+}
+
+fn import(
+    repo: Arc<dyn pokemon::Repository>,
+    ability_repo: Arc<dyn ability::Repository>,
+    file: String,
+    format: Option<String>,
+    continue_on_error: bool,
+) -> Result<(), String> {
+    let format = match format {
+        Some(format) => format,
+        None => match Path::new(&file).extension().and_then(|ext| ext.to_str()) {
+            Some(ext) => String::from(ext),
+            None => return Err(String::from("cannot guess the format, pass --format")),
+        },
+    };
+    let data =
+        std::fs::read_to_string(&file).map_err(|e| format!("cannot read {}: {}", file, e))?;
+
+    let req = import_pokemons::Request {
+        format,
+        data,
+        continue_on_error,
+    };
+    match import_pokemons::execute(repo, ability_repo, req) {
+        Ok(res) => {
+            let rejected = res.rejected.len();
+            for rejection in res.rejected {
+                eprintln!("{}:{}: {}", file, rejection.line, describe(rejection.error));
+            }
+            println!("imported {}, rejected {}", res.imported, rejected);
+            Ok(())
+        }
+        Err(import_pokemons::Error::BadRequest) => {
+            Err(String::from("unknown format, expected csv, ndjson or json"))
+        }
+        Err(import_pokemons::Error::Parse(line, message)) => {
+            Err(format!("{}:{}: {}", file, line, message))
+        }
+        Err(import_pokemons::Error::Rejected(line, error)) => Err(format!(
+            "{}:{}: {}, nothing was imported",
+            file,
+            line,
+            describe(error)
+        )),
+        Err(import_pokemons::Error::Unknown) => Err(String::from("cannot write the repository")),
+    }
+}
+
+fn export(
+    repo: Arc<dyn pokemon::Repository>,
+    format: String,
+    output: Option<String>,
+) -> Result<(), String> {
+    match export_pokemons::execute(repo, export_pokemons::Request { format }) {
+        Ok(data) => match output {
+            Some(path) => {
+                std::fs::write(&path, data).map_err(|e| format!("cannot write {}: {}", path, e))
+            }
+            None => {
+                print!("{}", data);
+                Ok(())
+            }
+        },
+        Err(export_pokemons::Error::BadRequest) => {
+            Err(String::from("unknown format, expected csv, ndjson or json"))
+        }
+        Err(export_pokemons::Error::Unknown) => Err(String::from("cannot read the repository")),
+    }
+}
+
+fn stats(repo: Arc<dyn pokemon::Repository>) -> Result<(), String> {
+    let page = match fetch_all_pokemons::execute(repo, fetch_all_pokemons::Request::default()) {
+        Ok(page) => page,
+        Err(_) => return Err(String::from("cannot read the repository")),
+    };
+
+    let mut by_type = BTreeMap::new();
+    let mut by_generation = BTreeMap::new();
+    for pokemon in &page.pokemons {
+        for pokemon_type in &pokemon.types {
+            *by_type.entry(pokemon_type.clone()).or_insert(0) += 1;
+        }
+        *by_generation.entry(pokemon.generation).or_insert(0) += 1;
+    }
+
+    println!("total: {}", page.total);
+    println!("by type:");
+    for (pokemon_type, count) in by_type {
+        println!("  {:<10} {}", pokemon_type, count);
+    }
+    println!("by generation:");
+    for (generation, count) in by_generation {
+        match generation {
+            Some(generation) => println!("  {:<10} {}", generation, count),
+            None => println!("  {:<10} {}", "unknown", count),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pokedex::config::{Layer, RepositoryLayer};
+    use pokedex::domain::{create_ability, fetch_pokemon};
+    use std::fs;
+    use std::path::PathBuf;
+
+    #[test]
+    fn it_should_accept_the_global_flags_after_the_subcommand() {
+        let cli = Cli::try_parse_from([
+            "pokedex-admin",
+            "delete",
+            "25",
+            "--form",
+            "alola",
+            "--backend",
+            "file",
+            "--path",
+            "data",
+        ])
+        .expect("the arguments should parse");
+
+        assert_eq!(cli.backend.as_deref(), Some("file"));
+        assert_eq!(cli.path.as_deref(), Some("data"));
+        match cli.command {
+            Command::Delete {
+                number,
+                form,
+                if_version,
+            } => {
+                assert_eq!(number, 25);
+                assert_eq!(form.as_deref(), Some("alola"));
+                assert_eq!(if_version, None);
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_parse_add_with_repeated_types_and_abilities() {
+        let cli = Cli::try_parse_from([
+            "pokedex-admin",
+            "add",
+            "--number",
+            "6",
+            "--name",
+            "Charizard",
+            "--type",
+            "Fire",
+            "--type",
+            "Flying",
+            "--stats",
+            "78,84,78,109,85,100",
+            "--ability",
+            "Blaze",
+        ])
+        .expect("the arguments should parse");
+
+        match cli.command {
+            Command::Add {
+                types,
+                stats,
+                abilities,
+                ..
+            } => {
+                assert_eq!(types, vec!["Fire", "Flying"]);
+                assert_eq!(stats, Some(vec![78, 84, 78, 109, 85, 100]));
+                assert_eq!(abilities, vec!["Blaze"]);
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_reject_add_without_a_type() {
+        let cli = Cli::try_parse_from([
+            "pokedex-admin",
+            "add",
+            "--number",
+            "25",
+            "--name",
+            "Pikachu",
+        ]);
+
+        assert!(cli.is_err());
+    }
+
+    #[test]
+    fn it_should_refuse_the_memory_backend() {
+        let config = Config::resolve(Layer::default()).expect("the defaults should resolve");
+
+        assert!(run(config, Command::Stats).is_err());
+    }
+
+    #[test]
+    fn it_should_add_and_delete_a_pokemon_in_the_file_backend() {
+        let dir = TempDir::new("add-delete");
+        let config = || repository_config("file", &dir.0);
+
+        assert!(run(config(), add_pikachu(vec![])).is_ok());
+        assert_eq!(fetch_pikachu(&config()).map(|p| p.version), Some(1));
+
+        let res = run(
+            config(),
+            Command::Delete {
+                number: 25,
+                form: None,
+                if_version: Some(2),
+            },
+        );
+        assert!(res.is_err());
+
+        let res = run(
+            config(),
+            Command::Delete {
+                number: 25,
+                form: None,
+                if_version: Some(1),
+            },
+        );
+        assert!(res.is_ok());
+        assert!(fetch_pikachu(&config()).is_none());
+    }
+
+    #[test]
+    fn it_should_only_store_abilities_from_the_catalogue() {
+        let dir = TempDir::new("abilities");
+        let config = || repository_config("file", &dir.0);
+
+        let res = run(config(), add_pikachu(vec![String::from("Static")]));
+        match res {
+            Err(message) => assert!(message.contains("unknown ability \"Static\"")),
+            _ => unreachable!(),
+        };
+        assert!(fetch_pikachu(&config()).is_none());
+
+        add_ability(&config(), "Static");
+        assert!(run(config(), add_pikachu(vec![String::from("static")])).is_ok());
+        assert_eq!(
+            fetch_pikachu(&config()).map(|p| p.abilities),
+            Some(vec![String::from("Static")])
+        );
+    }
+
+    #[test]
+    fn it_should_round_trip_an_export_into_an_empty_sqlite_repository() {
+        let dir = TempDir::new("round-trip");
+        fs::create_dir_all(&dir.0).unwrap();
+        let source = || repository_config("file", &dir.0.join("data"));
+        let target = || repository_config("sqlite", &dir.0.join("pokedex.db"));
+        let file = dir.0.join("export.json").to_string_lossy().into_owned();
+
+        add_ability(&source(), "Static");
+        assert!(run(source(), add_pikachu(vec![String::from("Static")])).is_ok());
+        let res = run(
+            source(),
+            Command::Export {
+                format: String::from("json"),
+                output: Some(file.clone()),
+            },
+        );
+        assert!(res.is_ok());
+
+        assert!(run(target(), import_command(&file)).is_err());
+        assert!(fetch_pikachu(&target()).is_none());
+
+        add_ability(&target(), "Static");
+        assert!(run(target(), import_command(&file)).is_ok());
+        let pikachu = fetch_pikachu(&target()).expect("pikachu should be imported");
+        assert_eq!(pikachu.name, "Pikachu");
+        assert_eq!(pikachu.types, vec!["Electric"]);
+        assert_eq!(pikachu.abilities, vec!["Static"]);
+    }
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let dir =
+                std::env::temp_dir().join(format!("pokedex-admin-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            Self(dir)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn repository_config(backend: &str, path: &Path) -> Config {
+        Config::resolve(Layer {
+            repository: RepositoryLayer {
+                backend: Some(String::from(backend)),
+                path: Some(path.to_string_lossy().into_owned()),
+            },
+            ..Default::default()
+        })
+        .expect("the repository settings should resolve")
+    }
+
+    fn add_pikachu(abilities: Vec<String>) -> Command {
+        Command::Add {
+            number: 25,
+            form: None,
+            name: String::from("Pikachu"),
+            types: vec![String::from("Electric")],
+            stats: Some(vec![35, 55, 40, 50, 50, 90]),
+            abilities,
+            hidden_ability: None,
+        }
+    }
+
+    fn import_command(file: &str) -> Command {
+        Command::Import {
+            file: String::from(file),
+            format: None,
+            continue_on_error: false,
+        }
+    }
+
+    fn add_ability(config: &Config, name: &str) {
+        let repo = config
+            .open_repositories()
+            .expect("the repository should open")
+            .ability;
+        let req = create_ability::Request {
+            name: String::from(name),
+            description: String::new(),
+            generation: 3,
+        };
+        assert!(create_ability::execute(repo, req).is_ok());
+    }
+
+    fn fetch_pikachu(config: &Config) -> Option<fetch_pokemon::Response> {
+        let repo = config
+            .open_repositories()
+            .expect("the repository should open")
+            .pokemon;
+        let req = fetch_pokemon::Request {
+            number: 25,
+            form: None,
+        };
+        fetch_pokemon::execute(repo, req).ok()
+    }
+}
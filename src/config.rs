@@ -0,0 +1,647 @@
+use crate::domain::entities::PokemonNumber;
+use crate::repositories::ability;
+use crate::repositories::pokemon::{
+    FileRepository, InMemoryRepository, Repository, SqliteRepository,
+};
+use serde::Deserialize;
+use std::fmt::{self, Display};
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+const DEFAULT_BIND: &str = "localhost:8111";
+const DEFAULT_CONFIG_FILE: &str = "pokedex.toml";
+const DEFAULT_TRASH_RETENTION_DAYS: u64 = 30;
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl TryFrom<&str> for LogLevel {
+    type Error = ConfigError;
+
+    fn try_from(level: &str) -> Result<Self, ConfigError> {
+        match level.trim().to_ascii_lowercase().as_str() {
+            "off" => Ok(Self::Off),
+            "error" => Ok(Self::Error),
+            "warn" => Ok(Self::Warn),
+            "info" => Ok(Self::Info),
+            "debug" => Ok(Self::Debug),
+            _ => Err(ConfigError::LogLevel(String::from(level))),
+        }
+    }
+}
+
+impl Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let level = match self {
+            Self::Off => "off",
+            Self::Error => "error",
+            Self::Warn => "warn",
+            Self::Info => "info",
+            Self::Debug => "debug",
+        };
+        write!(f, "{}", level)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Backend {
+    Memory,
+    Sqlite(String),
+    File(String),
+}
+
+impl Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Memory => write!(f, "memory"),
+            Self::Sqlite(path) => write!(f, "sqlite ({})", path),
+            Self::File(dir) => write!(f, "file ({})", dir),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ConfigError {
+    File(String, String),
+    Env(&'static str, String),
+    Bind(String),
+    Workers,
+    Backend(String),
+    MissingPath(String),
+    UnexpectedPath,
+    LogLevel(String),
+    DexRange(u16, u16),
+    LegacyEnv(&'static str),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::File(path, message) => write!(f, "cannot load {}: {}", path, message),
+            Self::Env(var, value) => write!(f, "invalid {}: \"{}\"", var, value),
+            Self::Bind(bind) => write!(f, "bind address \"{}\" is not host:port", bind),
+            Self::Workers => write!(f, "workers must be at least 1"),
+            Self::Backend(backend) => write!(
+                f,
+                "unknown backend \"{}\", expected memory, sqlite or file",
+                backend
+            ),
+            Self::MissingPath(backend) => write!(f, "the {} backend needs a path", backend),
+            Self::UnexpectedPath => write!(f, "the memory backend does not take a path"),
+            Self::LogLevel(level) => write!(
+                f,
+                "unknown log level \"{}\", expected off, error, warn, info or debug",
+                level
+            ),
+            Self::DexRange(min, max) => write!(
+                f,
+                "dex range {}..={} must be non-empty and start at 1 or more",
+                min, max
+            ),
+            Self::LegacyEnv(var) => write!(
+                f,
+                "{} cannot be combined with POKEDEX_BACKEND or POKEDEX_PATH, set only those",
+                var
+            ),
+        }
+    }
+}
+
+// One source of settings; every field is optional so that sources can be stacked.
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Layer {
+    pub bind: Option<String>,
+    pub workers: Option<usize>,
+    pub log_level: Option<String>,
+    #[serde(default)]
+    pub repository: RepositoryLayer,
+    #[serde(default)]
+    pub dex: DexLayer,
+    #[serde(default)]
+    pub trash: TrashLayer,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RepositoryLayer {
+    pub backend: Option<String>,
+    pub path: Option<String>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DexLayer {
+    pub min_number: Option<u16>,
+    pub max_number: Option<u16>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TrashLayer {
+    pub retention_days: Option<u64>,
+}
+
+impl Layer {
+    pub fn from_toml(path: &str, toml: &str) -> Result<Self, ConfigError> {
+        toml::from_str(toml).map_err(|e| ConfigError::File(String::from(path), e.message().into()))
+    }
+
+    pub fn from_env(var: impl Fn(&str) -> Option<String>) -> Result<Self, Vec<ConfigError>> {
+        let mut errors = vec![];
+        let workers = parse_var(&var, "POKEDEX_WORKERS", &mut errors);
+        let min_number = parse_var(&var, "POKEDEX_MIN_NUMBER", &mut errors);
+        let max_number = parse_var(&var, "POKEDEX_MAX_NUMBER", &mut errors);
+        let retention_days = parse_var(&var, "POKEDEX_TRASH_RETENTION_DAYS", &mut errors);
+        let repository = legacy_repository(&var, &mut errors);
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(Self {
+            bind: var("POKEDEX_BIND"),
+            workers,
+            log_level: var("POKEDEX_LOG_LEVEL"),
+            repository,
+            dex: DexLayer {
+                min_number,
+                max_number,
+            },
+            trash: TrashLayer { retention_days },
+        })
+    }
+
+    // Fills whatever this layer leaves unset from a lower-precedence one.
+    pub fn or(self, lower: Layer) -> Layer {
+        Layer {
+            bind: self.bind.or(lower.bind),
+            workers: self.workers.or(lower.workers),
+            log_level: self.log_level.or(lower.log_level),
+            repository: RepositoryLayer {
+                backend: self.repository.backend.or(lower.repository.backend),
+                path: self.repository.path.or(lower.repository.path),
+            },
+            dex: DexLayer {
+                min_number: self.dex.min_number.or(lower.dex.min_number),
+                max_number: self.dex.max_number.or(lower.dex.max_number),
+            },
+            trash: TrashLayer {
+                retention_days: self.trash.retention_days.or(lower.trash.retention_days),
+            },
+        }
+    }
+}
+
+// Before the config file existed, POKEDEX_DATABASE chose a SQLite file and, failing
+// that, POKEDEX_DATA_DIR a file backend directory. They still work on their own.
+fn legacy_repository(
+    var: &impl Fn(&str) -> Option<String>,
+    errors: &mut Vec<ConfigError>,
+) -> RepositoryLayer {
+    let backend = var("POKEDEX_BACKEND");
+    let path = var("POKEDEX_PATH");
+    let legacy = [("POKEDEX_DATABASE", "sqlite"), ("POKEDEX_DATA_DIR", "file")]
+        .into_iter()
+        .filter_map(|(name, backend)| Some((name, backend, var(name)?)))
+        .collect::<Vec<_>>();
+
+    if backend.is_some() || path.is_some() {
+        errors.extend(
+            legacy
+                .iter()
+                .map(|(name, _, _)| ConfigError::LegacyEnv(name)),
+        );
+        return RepositoryLayer { backend, path };
+    }
+    match legacy.into_iter().next() {
+        Some((_, backend, path)) => RepositoryLayer {
+            backend: Some(String::from(backend)),
+            path: Some(path),
+        },
+        None => RepositoryLayer::default(),
+    }
+}
+
+fn parse_var<T: FromStr>(
+    var: &impl Fn(&str) -> Option<String>,
+    name: &'static str,
+    errors: &mut Vec<ConfigError>,
+) -> Option<T> {
+    let value = var(name)?;
+    match value.trim().parse() {
+        Ok(value) => Some(value),
+        Err(_) => {
+            errors.push(ConfigError::Env(name, value));
+            None
+        }
+    }
+}
+
+// Command-line flags, shared by the binaries that open the repository.
+#[derive(clap::Args, Default)]
+pub struct Args {
+    /// TOML file to read, defaults to POKEDEX_CONFIG or ./pokedex.toml when it exists
+    #[arg(long)]
+    pub config: Option<String>,
+    /// Address to listen on, as host:port
+    #[arg(long)]
+    pub bind: Option<String>,
+    /// Number of threads serving requests
+    #[arg(long)]
+    pub workers: Option<usize>,
+    /// off, error, warn, info or debug
+    #[arg(long)]
+    pub log_level: Option<String>,
+    /// memory, sqlite or file
+    #[arg(long)]
+    pub backend: Option<String>,
+    /// SQLite database file or data directory of the file backend
+    #[arg(long)]
+    pub path: Option<String>,
+    /// Lowest valid national dex number
+    #[arg(long)]
+    pub min_number: Option<u16>,
+    /// Highest valid national dex number
+    #[arg(long)]
+    pub max_number: Option<u16>,
+    /// Days a deleted Pokémon stays in the trash before it is purged
+    #[arg(long)]
+    pub trash_retention_days: Option<u64>,
+}
+
+impl From<Args> for Layer {
+    fn from(args: Args) -> Self {
+        Self {
+            bind: args.bind,
+            workers: args.workers,
+            log_level: args.log_level,
+            repository: RepositoryLayer {
+                backend: args.backend,
+                path: args.path,
+            },
+            dex: DexLayer {
+                min_number: args.min_number,
+                max_number: args.max_number,
+            },
+            trash: TrashLayer {
+                retention_days: args.trash_retention_days,
+            },
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Config {
+    pub bind: String,
+    pub workers: usize,
+    pub log_level: LogLevel,
+    pub backend: Backend,
+    pub dex_range: RangeInclusive<u16>,
+    pub trash_retention: Duration,
+}
+
+impl Config {
+    // The config file comes first, then `POKEDEX_*` variables, then the flags.
+    pub fn load(args: Args) -> Result<Self, Vec<ConfigError>> {
+        let mut errors = vec![];
+        let file = match args
+            .config
+            .clone()
+            .or_else(|| std::env::var("POKEDEX_CONFIG").ok())
+        {
+            Some(path) => Some(path),
+            None if Path::new(DEFAULT_CONFIG_FILE).exists() => {
+                Some(String::from(DEFAULT_CONFIG_FILE))
+            }
+            None => None,
+        };
+        let file = match file {
+            Some(path) => match std::fs::read_to_string(&path) {
+                Ok(toml) => Layer::from_toml(&path, &toml).unwrap_or_else(|e| {
+                    errors.push(e);
+                    Layer::default()
+                }),
+                Err(e) => {
+                    errors.push(ConfigError::File(path, e.to_string()));
+                    Layer::default()
+                }
+            },
+            None => Layer::default(),
+        };
+        let env = Layer::from_env(|name| std::env::var(name).ok()).unwrap_or_else(|e| {
+            errors.extend(e);
+            Layer::default()
+        });
+
+        match Self::resolve(Layer::from(args).or(env).or(file)) {
+            Ok(config) if errors.is_empty() => Ok(config),
+            Ok(_) => Err(errors),
+            Err(e) => {
+                errors.extend(e);
+                Err(errors)
+            }
+        }
+    }
+
+    // Applies the defaults and reports every invalid setting at once.
+    pub fn resolve(layer: Layer) -> Result<Self, Vec<ConfigError>> {
+        let mut errors = vec![];
+
+        let bind = layer.bind.unwrap_or_else(|| String::from(DEFAULT_BIND));
+        match bind.rsplit_once(':') {
+            Some((host, port)) if !host.is_empty() && port.parse::<u16>().is_ok() => {}
+            _ => errors.push(ConfigError::Bind(bind.clone())),
+        }
+
+        let workers = layer
+            .workers
+            .unwrap_or_else(|| 8 * std::thread::available_parallelism().map_or(1, |n| n.get()));
+        if workers == 0 {
+            errors.push(ConfigError::Workers);
+        }
+
+        let log_level = match layer.log_level {
+            Some(level) => LogLevel::try_from(level.as_str()).unwrap_or_else(|e| {
+                errors.push(e);
+                LogLevel::Info
+            }),
+            None => LogLevel::Info,
+        };
+
+        let backend = match (layer.repository.backend.as_deref(), layer.repository.path) {
+            (None | Some("memory"), None) => Backend::Memory,
+            (None | Some("memory"), Some(_)) => {
+                errors.push(ConfigError::UnexpectedPath);
+                Backend::Memory
+            }
+            (Some("sqlite"), Some(path)) => Backend::Sqlite(path),
+            (Some("file"), Some(dir)) => Backend::File(dir),
+            (Some(backend @ ("sqlite" | "file")), None) => {
+                errors.push(ConfigError::MissingPath(String::from(backend)));
+                Backend::Memory
+            }
+            (Some(backend), _) => {
+                errors.push(ConfigError::Backend(String::from(backend)));
+                Backend::Memory
+            }
+        };
+
+        let min = layer.dex.min_number.unwrap_or(1);
+        let max = layer.dex.max_number.unwrap_or(PokemonNumber::DEFAULT_MAX);
+        if min == 0 || min > max {
+            errors.push(ConfigError::DexRange(min, max));
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(Self {
+            bind,
+            workers,
+            log_level,
+            backend,
+            dex_range: min..=max,
+            trash_retention: Duration::from_secs(
+                layer
+                    .trash
+                    .retention_days
+                    .unwrap_or(DEFAULT_TRASH_RETENTION_DAYS)
+                    .saturating_mul(SECS_PER_DAY),
+            ),
+        })
+    }
+
+    // A `Config` built by hand can still hold a range `resolve` would have refused.
+    pub fn configure_dex_range(&self) -> Result<(), ConfigError> {
+        PokemonNumber::configure_range(self.dex_range.clone())
+            .map_err(|e| ConfigError::DexRange(e.0, e.1))
+    }
+
+    pub fn open_repositories(&self) -> Result<Repositories, String> {
+        match &self.backend {
+            Backend::Memory => Ok(Repositories {
+                pokemon: Arc::new(InMemoryRepository::new()),
+                ability: Arc::new(ability::InMemoryRepository::new()),
+            }),
+            Backend::Sqlite(path) => match SqliteRepository::open(path) {
+                Ok(repo) => Ok(Repositories {
+                    ability: Arc::new(repo.abilities()),
+                    pokemon: Arc::new(repo),
+                }),
+                Err(e) => Err(format!("cannot open database {}: {}", path, e)),
+            },
+            Backend::File(dir) => match FileRepository::open(dir)
+                .and_then(|repo| repo.abilities().map(|abilities| (repo, abilities)))
+            {
+                Ok((repo, abilities)) => Ok(Repositories {
+                    pokemon: Arc::new(repo),
+                    ability: Arc::new(abilities),
+                }),
+                Err(e) => Err(format!("cannot open data directory {}: {}", dir, e)),
+            },
+        }
+    }
+}
+
+// The repositories the configured backend keeps; the other catalogues still live in memory.
+pub struct Repositories {
+    pub pokemon: Arc<dyn Repository>,
+    pub ability: Arc<dyn ability::Repository>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env(vars: &[(&str, &str)]) -> Result<Layer, Vec<ConfigError>> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        Layer::from_env(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn it_should_use_the_defaults_when_nothing_is_set() {
+        match Config::resolve(Layer::default()) {
+            Ok(config) => {
+                assert_eq!(config.bind, "localhost:8111");
+                assert!(config.workers > 0);
+                assert_eq!(config.log_level, LogLevel::Info);
+                assert_eq!(config.backend, Backend::Memory);
+                assert_eq!(config.dex_range, 1..=PokemonNumber::DEFAULT_MAX);
+                assert_eq!(
+                    config.trash_retention,
+                    Duration::from_secs(30 * SECS_PER_DAY)
+                );
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_prefer_flags_over_env_vars_over_the_file() {
+        let file = Layer::from_toml(
+            "pokedex.toml",
+            "bind = \"0.0.0.0:80\"\nworkers = 2\nlog_level = \"debug\"\n\n[repository]\nbackend = \"sqlite\"\npath = \"pokedex.db\"\n\n[dex]\nmax_number = 151\n\n[trash]\nretention_days = 7\n",
+        )
+        .expect("file to be parsed");
+        let env = env(&[
+            ("POKEDEX_WORKERS", "4"),
+            ("POKEDEX_LOG_LEVEL", "warn"),
+            ("POKEDEX_TRASH_RETENTION_DAYS", "14"),
+        ])
+        .expect("env to be parsed");
+        let flags = Layer::from(Args {
+            log_level: Some(String::from("error")),
+            ..Default::default()
+        });
+
+        match Config::resolve(flags.or(env).or(file)) {
+            Ok(config) => {
+                assert_eq!(config.bind, "0.0.0.0:80");
+                assert_eq!(config.workers, 4);
+                assert_eq!(config.log_level, LogLevel::Error);
+                assert_eq!(config.backend, Backend::Sqlite(String::from("pokedex.db")));
+                assert_eq!(config.dex_range, 1..=151);
+                assert_eq!(
+                    config.trash_retention,
+                    Duration::from_secs(14 * SECS_PER_DAY)
+                );
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_report_every_invalid_setting() {
+        let layer = Layer {
+            bind: Some(String::from("localhost")),
+            workers: Some(0),
+            log_level: Some(String::from("verbose")),
+            repository: RepositoryLayer {
+                backend: Some(String::from("sqlite")),
+                path: None,
+            },
+            dex: DexLayer {
+                min_number: Some(200),
+                max_number: Some(151),
+            },
+            ..Default::default()
+        };
+
+        match Config::resolve(layer) {
+            Err(errors) => assert_eq!(
+                errors,
+                vec![
+                    ConfigError::Bind(String::from("localhost")),
+                    ConfigError::Workers,
+                    ConfigError::LogLevel(String::from("verbose")),
+                    ConfigError::MissingPath(String::from("sqlite")),
+                    ConfigError::DexRange(200, 151),
+                ]
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_refuse_to_configure_an_invalid_dex_range() {
+        let mut config = Config::resolve(Layer::default()).expect("the defaults should resolve");
+        #[allow(clippy::reversed_empty_ranges)]
+        let empty = 151..=1;
+        config.dex_range = empty;
+
+        assert_eq!(
+            config.configure_dex_range(),
+            Err(ConfigError::DexRange(151, 1))
+        );
+    }
+
+    #[test]
+    fn it_should_reject_an_unknown_backend() {
+        let layer = Layer {
+            repository: RepositoryLayer {
+                backend: Some(String::from("postgres")),
+                path: Some(String::from("db")),
+            },
+            ..Default::default()
+        };
+
+        match Config::resolve(layer) {
+            Err(errors) => assert_eq!(errors, vec![ConfigError::Backend(String::from("postgres"))]),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_report_env_vars_that_are_not_numbers() {
+        match env(&[("POKEDEX_WORKERS", "many"), ("POKEDEX_MAX_NUMBER", "-1")]) {
+            Err(errors) => assert_eq!(
+                errors,
+                vec![
+                    ConfigError::Env("POKEDEX_WORKERS", String::from("many")),
+                    ConfigError::Env("POKEDEX_MAX_NUMBER", String::from("-1")),
+                ]
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_map_the_legacy_repository_env_vars() {
+        let repository = |vars: &[(&str, &str)]| {
+            let layer = env(vars).expect("env to be parsed");
+            (layer.repository.backend, layer.repository.path)
+        };
+
+        assert_eq!(
+            repository(&[("POKEDEX_DATABASE", "pokedex.db")]),
+            (
+                Some(String::from("sqlite")),
+                Some(String::from("pokedex.db"))
+            )
+        );
+        assert_eq!(
+            repository(&[("POKEDEX_DATA_DIR", "data")]),
+            (Some(String::from("file")), Some(String::from("data")))
+        );
+        assert_eq!(
+            repository(&[
+                ("POKEDEX_DATABASE", "pokedex.db"),
+                ("POKEDEX_DATA_DIR", "data")
+            ]),
+            (
+                Some(String::from("sqlite")),
+                Some(String::from("pokedex.db"))
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_reject_legacy_env_vars_next_to_their_replacement() {
+        match env(&[("POKEDEX_BACKEND", "file"), ("POKEDEX_DATA_DIR", "data")]) {
+            Err(errors) => assert_eq!(errors, vec![ConfigError::LegacyEnv("POKEDEX_DATA_DIR")]),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_reject_unknown_keys_in_the_file() {
+        match Layer::from_toml("pokedex.toml", "port = 8111\n") {
+            Err(ConfigError::File(path, _)) => assert_eq!(path, "pokedex.toml"),
+            _ => unreachable!(),
+        }
+    }
+}
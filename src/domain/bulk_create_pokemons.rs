@@ -0,0 +1,235 @@
+use std::sync::Arc;
+
+use super::create_pokemon::{self, resolve_abilities, validate};
+use super::entities::{Pokemon, ValidationError};
+use super::fetch_pokemon::Response;
+use crate::repositories::ability;
+use crate::repositories::pokemon::{BulkMode, InsertError, InsertManyError, Repository};
+
+pub struct Request {
+    pub pokemons: Vec<create_pokemon::Request>,
+    pub continue_on_error: bool,
+}
+
+pub enum ItemError {
+    Invalid(Vec<ValidationError>),
+    UnknownAbility(String),
+    Conflict,
+}
+
+pub enum Error {
+    // The item at this index was rejected, so nothing was created.
+    Rejected(usize, ItemError),
+    Unknown,
+}
+
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    ability_repo: Arc<dyn ability::Repository>,
+    req: Request,
+) -> Result<Vec<Result<Response, ItemError>>, Error> {
+    let mode = if req.continue_on_error {
+        BulkMode::ContinueOnError
+    } else {
+        BulkMode::AllOrNothing
+    };
+
+    let _references = ability_repo.reference();
+    // Every item is validated before anything is stored; in all-or-nothing mode the
+    // first invalid one stops the whole batch.
+    let mut checked = Vec::with_capacity(req.pokemons.len());
+    for (i, req) in req.pokemons.into_iter().enumerate() {
+        match check(ability_repo.clone(), req) {
+            Ok(pokemon) => checked.push(Ok(pokemon)),
+            Err(None) => return Err(Error::Unknown),
+            Err(Some(e)) if mode == BulkMode::ContinueOnError => checked.push(Err(e)),
+            Err(Some(e)) => return Err(Error::Rejected(i, e)),
+        }
+    }
+
+    let valid: Vec<Pokemon> = checked
+        .iter()
+        .filter_map(|pokemon| pokemon.as_ref().ok().cloned())
+        .collect();
+    let mut inserted = match repo.insert_many(valid, mode) {
+        Ok(results) => results.into_iter(),
+        // Validation kept every item in all-or-nothing mode, so the indexes still line up.
+        Err(InsertManyError::Rejected(i, InsertError::Conflict)) => {
+            return Err(Error::Rejected(i, ItemError::Conflict))
+        }
+        Err(_) => return Err(Error::Unknown),
+    };
+
+    // Stitches the repository results back in between the items that failed validation.
+    let mut results = Vec::with_capacity(checked.len());
+    for pokemon in checked {
+        results.push(match pokemon {
+            Ok(_) => match inserted.next() {
+                Some(Ok(pokemon)) => Ok(pokemon.into()),
+                Some(Err(InsertError::Conflict)) => Err(ItemError::Conflict),
+                Some(Err(InsertError::Unknown)) | None => return Err(Error::Unknown),
+            },
+            Err(e) => Err(e),
+        });
+    }
+    Ok(results)
+}
+
+// `None` stands for an unexpected error, which aborts the batch in every mode.
+fn check(
+    ability_repo: Arc<dyn ability::Repository>,
+    req: create_pokemon::Request,
+) -> Result<Pokemon, Option<ItemError>> {
+    let pokemon = validate(req).map_err(|errors| Some(ItemError::Invalid(errors)))?;
+    match resolve_abilities(ability_repo, &pokemon.abilities, &Default::default()) {
+        Ok(abilities) => Ok(Pokemon {
+            abilities,
+            ..pokemon
+        }),
+        Err(create_pokemon::Error::Invalid(errors)) => Err(Some(ItemError::Invalid(errors))),
+        Err(create_pokemon::Error::UnknownAbility(name)) => {
+            Err(Some(ItemError::UnknownAbility(name)))
+        }
+        Err(_) => Err(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonNameError, PokemonNumber};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn request(number: u16, name: &str) -> create_pokemon::Request {
+        create_pokemon::Request {
+            number,
+            form: None,
+            name: String::from(name),
+            types: vec![String::from("Electric")],
+            stats: None,
+            abilities: vec![],
+            hidden_ability: None,
+        }
+    }
+
+    fn ability_repo() -> Arc<ability::InMemoryRepository> {
+        Arc::new(ability::InMemoryRepository::new())
+    }
+
+    fn numbers(repo: &InMemoryRepository) -> Vec<u16> {
+        match repo.fetch_all() {
+            Ok(pokemons) => pokemons.into_iter().map(|p| p.number.into()).collect(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_create_every_pokemon_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            pokemons: vec![request(25, "Pikachu"), request(26, "Raichu")],
+            continue_on_error: false,
+        };
+
+        match execute(repo.clone(), ability_repo(), req) {
+            Ok(results) => assert_eq!(results.len(), 2),
+            _ => unreachable!(),
+        }
+        assert_eq!(numbers(&repo), vec![25, 26]);
+    }
+
+    #[test]
+    fn it_should_create_nothing_when_an_item_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            pokemons: vec![request(25, "Pikachu"), request(26, "")],
+            continue_on_error: false,
+        };
+
+        match execute(repo.clone(), ability_repo(), req) {
+            Err(Error::Rejected(1, ItemError::Invalid(errors))) => {
+                assert_eq!(errors, vec![ValidationError::Name(PokemonNameError::Empty)])
+            }
+            _ => unreachable!(),
+        }
+        assert!(numbers(&repo).is_empty());
+    }
+
+    #[test]
+    fn it_should_create_nothing_when_items_conflict_with_each_other() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            pokemons: vec![request(25, "Pikachu"), request(26, "pikachu")],
+            continue_on_error: false,
+        };
+
+        match execute(repo.clone(), ability_repo(), req) {
+            Err(Error::Rejected(1, ItemError::Conflict)) => {}
+            _ => unreachable!(),
+        }
+        assert!(numbers(&repo).is_empty());
+    }
+
+    #[test]
+    fn it_should_report_each_item_when_continuing_on_error() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            pokemons: vec![
+                request(25, "Pikachu"),
+                request(26, ""),
+                request(25, "Pikachu"),
+                request(4, "Charmander"),
+            ],
+            continue_on_error: true,
+        };
+
+        match execute(repo.clone(), ability_repo(), req) {
+            Ok(results) => {
+                assert!(matches!(results[0], Ok(Response { number: 25, .. })));
+                assert!(matches!(results[1], Err(ItemError::Invalid(_))));
+                assert!(matches!(results[2], Err(ItemError::Conflict)));
+                assert!(matches!(results[3], Ok(Response { number: 4, .. })));
+            }
+            _ => unreachable!(),
+        }
+        assert_eq!(
+            numbers(&repo),
+            vec![
+                u16::from(PokemonNumber::charmander()),
+                u16::from(PokemonNumber::pikachu())
+            ]
+        );
+    }
+
+    #[test]
+    fn it_should_report_an_unknown_ability_with_its_index() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let mut item = request(25, "Pikachu");
+        item.abilities = vec![String::from("Static")];
+        let req = Request {
+            pokemons: vec![item],
+            continue_on_error: false,
+        };
+
+        match execute(repo, ability_repo(), req) {
+            Err(Error::Rejected(0, ItemError::UnknownAbility(name))) => {
+                assert_eq!(name, String::from("Static"))
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request {
+            pokemons: vec![request(25, "Pikachu")],
+            continue_on_error: true,
+        };
+
+        match execute(repo, ability_repo(), req) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use std::sync::Arc;
+
+use crate::repositories::pokemon::{FetchError, Repository};
+
+use super::entities::{PokemonForm, PokemonNumber};
+use super::individual::{Individual, IndividualError};
+
+pub enum Error {
+    Unknown,
+    BadRequest,
+    InvalidIndividual(IndividualError),
+    NotFound,
+    MissingBaseStats,
+}
+
+pub struct Request {
+    pub number: u16,
+    pub level: u16,
+    pub ivs: [u16; 6],
+    pub evs: [u16; 6],
+    pub nature: String,
+}
+
+pub struct Response {
+    pub number: u16,
+    pub name: String,
+    pub level: u16,
+    pub nature: String,
+    pub stats: [u16; 6],
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let pokemon_number = match PokemonNumber::try_from(req.number) {
+        Ok(pokemon_number) => pokemon_number,
+        _ => return Err(Error::BadRequest),
+    };
+    let individual = match Individual::new(req.level, req.ivs, req.evs, req.nature) {
+        Ok(individual) => individual,
+        Err(e) => return Err(Error::InvalidIndividual(e)),
+    };
+
+    let pokemon = match repo.fetch(pokemon_number, &PokemonForm::default()) {
+        Ok(pokemon) => pokemon,
+        Err(FetchError::Unknown) => return Err(Error::Unknown),
+        Err(FetchError::NotFound) => return Err(Error::NotFound),
+    };
+    let base_stats = match pokemon.stats {
+        Some(base_stats) => base_stats,
+        None => return Err(Error::MissingBaseStats),
+    };
+
+    Ok(Response {
+        number: pokemon.number.into(),
+        name: pokemon.name.into(),
+        level: individual.level(),
+        nature: individual.nature().into(),
+        stats: individual.stats(&base_stats),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{BaseStats, PokemonAbilities, PokemonName, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn request(number: u16) -> Request {
+        Request {
+            number,
+            level: 50,
+            ivs: [31; 6],
+            evs: [0, 0, 0, 252, 4, 252],
+            nature: String::from("Timid"),
+        }
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        match execute(repo, request(PokemonNumber::pikachu().into())) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, request(PokemonNumber::bad().into())) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_an_invalid_individual_error_when_level_is_out_of_range() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            level: 101,
+            ..request(PokemonNumber::pikachu().into())
+        };
+
+        match execute(repo, req) {
+            Err(Error::InvalidIndividual(IndividualError::Level(101))) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_repo_doesnot_contain_the_pokemon() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, request(PokemonNumber::pikachu().into())) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_missing_base_stats_error_when_the_pokemon_has_none() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            None,
+            PokemonAbilities::default(),
+        )
+        .ok()
+        .expect("pokemon to be inserted");
+
+        match execute(repo, request(PokemonNumber::pikachu().into())) {
+            Err(Error::MissingBaseStats) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_the_computed_stats_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            Some(BaseStats::pikachu()),
+            PokemonAbilities::default(),
+        )
+        .ok()
+        .expect("pokemon to be inserted");
+
+        match execute(repo, request(PokemonNumber::pikachu().into())) {
+            Ok(res) => {
+                assert_eq!(res.level, 50);
+                assert_eq!(res.nature, String::from("Timid"));
+                assert_eq!(res.stats, [110, 67, 60, 102, 71, 156]);
+            }
+            _ => unreachable!(),
+        }
+    }
+}
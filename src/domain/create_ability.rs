@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use crate::repositories::ability::{InsertError, Repository};
+
+use super::entities::{Ability, AbilityName, Generation};
+
+pub struct Request {
+    pub name: String,
+    pub description: String,
+    pub generation: u8,
+}
+
+pub enum Error {
+    BadRequest(String),
+    Conflict,
+    Unknown,
+}
+
+pub struct Response {
+    pub name: String,
+    pub description: String,
+    pub generation: u8,
+}
+
+impl From<Ability> for Response {
+    fn from(ability: Ability) -> Self {
+        Self {
+            name: ability.name.into(),
+            description: ability.description,
+            generation: ability.generation.into(),
+        }
+    }
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let name = AbilityName::try_from(req.name).map_err(|e| Error::BadRequest(e.to_string()))?;
+    let generation =
+        Generation::try_from(req.generation).map_err(|e| Error::BadRequest(e.to_string()))?;
+
+    match repo.insert(Ability::new(name, req.description, generation)) {
+        Ok(ability) => Ok(ability.into()),
+        Err(InsertError::Conflict) => Err(Error::Conflict),
+        Err(InsertError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::ability::InMemoryRepository;
+
+    fn request(name: &str, generation: u8) -> Request {
+        Request {
+            name: String::from(name),
+            description: String::from("May paralyze on contact."),
+            generation,
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo.clone(), request("", 3)) {
+            Err(Error::BadRequest(_)) => {}
+            _ => unreachable!(),
+        }
+        match execute(repo, request("Static", 10)) {
+            Err(Error::BadRequest(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_if_the_name_already_exists() {
+        let repo = Arc::new(InMemoryRepository::new());
+        execute(repo.clone(), request("Static", 3))
+            .ok()
+            .expect("ability to be inserted");
+
+        match execute(repo, request("STATIC", 3)) {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        match execute(repo, request("Static", 3)) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_the_ability_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, request(" Static ", 3)) {
+            Ok(res) => {
+                assert_eq!(res.name, String::from("Static"));
+                assert_eq!(res.generation, 3);
+            }
+            _ => unreachable!(),
+        }
+    }
+}
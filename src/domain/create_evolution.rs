@@ -0,0 +1,197 @@
+use std::sync::Arc;
+
+use crate::repositories::evolution::{self, InsertError};
+use crate::repositories::pokemon::{self, FetchError};
+
+use super::entities::{Evolution, EvolutionTrigger, PokemonForm, PokemonNumber};
+
+pub struct Request {
+    pub from: u16,
+    pub to: u16,
+    pub trigger: String,
+    pub level: Option<u16>,
+    pub item: Option<String>,
+    pub condition: Option<String>,
+}
+
+pub enum Error {
+    BadRequest(String),
+    NotFound,
+    Conflict,
+    Cycle,
+    Unknown,
+}
+
+pub struct Response {
+    pub from: u16,
+    pub to: u16,
+    pub trigger: String,
+    pub level: Option<u16>,
+    pub item: Option<String>,
+    pub condition: Option<String>,
+}
+
+impl From<Evolution> for Response {
+    fn from(evolution: Evolution) -> Self {
+        Self {
+            from: evolution.from.into(),
+            to: evolution.to.into(),
+            trigger: evolution.trigger.kind().to_string(),
+            level: evolution.trigger.level(),
+            item: evolution.trigger.item(),
+            condition: evolution.condition,
+        }
+    }
+}
+
+pub fn execute(
+    pokemon_repo: Arc<dyn pokemon::Repository>,
+    evolution_repo: Arc<dyn evolution::Repository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let from = PokemonNumber::try_from(req.from).map_err(|e| Error::BadRequest(e.to_string()))?;
+    let to = PokemonNumber::try_from(req.to).map_err(|e| Error::BadRequest(e.to_string()))?;
+    let trigger = EvolutionTrigger::new(req.trigger, req.level, req.item)
+        .map_err(|e| Error::BadRequest(e.to_string()))?;
+    if from == to {
+        return Err(Error::BadRequest(String::from(
+            "a pokemon cannot evolve into itself",
+        )));
+    }
+
+    for number in [&from, &to] {
+        match pokemon_repo.fetch(number.clone(), &PokemonForm::default()) {
+            Ok(_) => {}
+            Err(FetchError::NotFound) => return Err(Error::NotFound),
+            Err(FetchError::Unknown) => return Err(Error::Unknown),
+        }
+    }
+
+    // A species evolves from at most one other species, so chains are trees.
+    match evolution_repo.insert(Evolution::new(from, to, trigger, req.condition)) {
+        Ok(evolution) => Ok(evolution.into()),
+        Err(InsertError::Conflict) => Err(Error::Conflict),
+        Err(InsertError::Cycle) => Err(Error::Cycle),
+        Err(InsertError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonAbilities, PokemonName, PokemonTypes};
+    use crate::repositories::pokemon::Repository;
+
+    fn repos(
+        numbers: &[u16],
+    ) -> (
+        Arc<pokemon::InMemoryRepository>,
+        Arc<evolution::InMemoryRepository>,
+    ) {
+        let pokemon_repo = Arc::new(pokemon::InMemoryRepository::new());
+        for n in numbers {
+            pokemon_repo
+                .insert(
+                    PokemonNumber::try_from(*n).unwrap(),
+                    PokemonForm::default(),
+                    PokemonName::try_from(format!("Pokemon {}", n)).unwrap(),
+                    PokemonTypes::charmander(),
+                    None,
+                    PokemonAbilities::default(),
+                )
+                .ok()
+                .expect("pokemon to be inserted");
+        }
+        (pokemon_repo, Arc::new(evolution::InMemoryRepository::new()))
+    }
+
+    fn level_up(from: u16, to: u16) -> Request {
+        Request {
+            from,
+            to,
+            trigger: String::from("level"),
+            level: Some(16),
+            item: None,
+            condition: None,
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_trigger_is_invalid() {
+        let (pokemon_repo, evolution_repo) = repos(&[4, 5]);
+        let req = Request {
+            level: None,
+            ..level_up(4, 5)
+        };
+
+        match execute(pokemon_repo, evolution_repo, req) {
+            Err(Error::BadRequest(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_an_end_is_missing() {
+        let (pokemon_repo, evolution_repo) = repos(&[4]);
+
+        match execute(pokemon_repo, evolution_repo, level_up(4, 5)) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_when_the_target_already_has_a_pre_evolution() {
+        let (pokemon_repo, evolution_repo) = repos(&[4, 5, 6]);
+        execute(pokemon_repo.clone(), evolution_repo.clone(), level_up(4, 6))
+            .ok()
+            .expect("evolution to be inserted");
+
+        match execute(pokemon_repo, evolution_repo, level_up(5, 6)) {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_cycle_error_when_the_chain_would_loop() {
+        let (pokemon_repo, evolution_repo) = repos(&[4, 5, 6]);
+        execute(pokemon_repo.clone(), evolution_repo.clone(), level_up(4, 5))
+            .ok()
+            .expect("evolution to be inserted");
+        execute(pokemon_repo.clone(), evolution_repo.clone(), level_up(5, 6))
+            .ok()
+            .expect("evolution to be inserted");
+
+        match execute(pokemon_repo, evolution_repo, level_up(6, 4)) {
+            Err(Error::Cycle) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let (pokemon_repo, _) = repos(&[4, 5]);
+        let evolution_repo = Arc::new(evolution::InMemoryRepository::new().with_error());
+
+        match execute(pokemon_repo, evolution_repo, level_up(4, 5)) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_the_evolution_otherwise() {
+        let (pokemon_repo, evolution_repo) = repos(&[4, 5]);
+
+        match execute(pokemon_repo, evolution_repo, level_up(4, 5)) {
+            Ok(res) => {
+                assert_eq!(res.from, 4);
+                assert_eq!(res.to, 5);
+                assert_eq!(res.trigger, String::from("level"));
+                assert_eq!(res.level, Some(16));
+            }
+            _ => unreachable!(),
+        }
+    }
+}
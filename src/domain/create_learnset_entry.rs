@@ -0,0 +1,191 @@
+use std::sync::Arc;
+
+use crate::repositories::learnset::{self, InsertError};
+use crate::repositories::{moves, pokemon};
+
+use super::entities::{LearnMethod, LearnsetEntry, MoveName, PokemonForm, PokemonNumber};
+
+pub struct Request {
+    pub number: u16,
+    pub move_name: String,
+    pub method: String,
+    pub level: Option<u16>,
+}
+
+pub enum Error {
+    BadRequest(String),
+    NotFound,
+    Conflict,
+    Unknown,
+}
+
+pub struct Response {
+    pub number: u16,
+    pub move_name: String,
+    pub method: String,
+    pub level: Option<u16>,
+}
+
+pub fn execute(
+    pokemon_repo: Arc<dyn pokemon::Repository>,
+    move_repo: Arc<dyn moves::Repository>,
+    learnset_repo: Arc<dyn learnset::Repository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let number =
+        PokemonNumber::try_from(req.number).map_err(|e| Error::BadRequest(e.to_string()))?;
+    let move_name =
+        MoveName::try_from(req.move_name).map_err(|e| Error::BadRequest(e.to_string()))?;
+    let method =
+        LearnMethod::new(req.method, req.level).map_err(|e| Error::BadRequest(e.to_string()))?;
+
+    match pokemon_repo.fetch(number.clone(), &PokemonForm::default()) {
+        Ok(_) => {}
+        Err(pokemon::FetchError::NotFound) => return Err(Error::NotFound),
+        Err(pokemon::FetchError::Unknown) => return Err(Error::Unknown),
+    }
+    // Entries use the catalogue spelling of the move.
+    let move_name = match move_repo.fetch(move_name) {
+        Ok(m) => m.name,
+        Err(moves::FetchError::NotFound) => return Err(Error::NotFound),
+        Err(moves::FetchError::Unknown) => return Err(Error::Unknown),
+    };
+
+    match learnset_repo.insert(LearnsetEntry::new(number, move_name, method)) {
+        Ok(entry) => Ok(Response {
+            number: entry.number.into(),
+            move_name: entry.move_name.into(),
+            method: entry.method.kind().to_string(),
+            level: entry.method.level(),
+        }),
+        Err(InsertError::Conflict) => Err(Error::Conflict),
+        Err(InsertError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        Move, MoveCategory, PokemonAbilities, PokemonName, PokemonType, PokemonTypes,
+    };
+    use crate::repositories::moves::Repository as _;
+    use crate::repositories::pokemon::Repository as _;
+
+    type Repos = (
+        Arc<pokemon::InMemoryRepository>,
+        Arc<moves::InMemoryRepository>,
+        Arc<learnset::InMemoryRepository>,
+    );
+
+    fn repos() -> Repos {
+        let pokemon_repo = Arc::new(pokemon::InMemoryRepository::new());
+        pokemon_repo
+            .insert(
+                PokemonNumber::pikachu(),
+                PokemonForm::default(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+                None,
+                PokemonAbilities::default(),
+            )
+            .ok()
+            .expect("pokemon to be inserted");
+        let move_repo = Arc::new(moves::InMemoryRepository::new());
+        move_repo
+            .insert(
+                Move::new(
+                    MoveName::try_from(String::from("Thunder Shock")).unwrap(),
+                    PokemonType::Electric,
+                    MoveCategory::Special,
+                    Some(40),
+                    Some(100),
+                    30,
+                    0,
+                )
+                .unwrap(),
+            )
+            .ok()
+            .expect("move to be inserted");
+        (
+            pokemon_repo,
+            move_repo,
+            Arc::new(learnset::InMemoryRepository::new()),
+        )
+    }
+
+    fn request(number: u16, move_name: &str) -> Request {
+        Request {
+            number,
+            move_name: String::from(move_name),
+            method: String::from("level-up"),
+            level: Some(1),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_method_is_invalid() {
+        let (pokemon_repo, move_repo, learnset_repo) = repos();
+        let req = Request {
+            level: None,
+            ..request(25, "Thunder Shock")
+        };
+
+        match execute(pokemon_repo, move_repo, learnset_repo, req) {
+            Err(Error::BadRequest(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_move_doesnot_exist() {
+        let (pokemon_repo, move_repo, learnset_repo) = repos();
+
+        match execute(pokemon_repo, move_repo, learnset_repo, request(25, "Surf")) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_when_the_entry_already_exists() {
+        let (pokemon_repo, move_repo, learnset_repo) = repos();
+        execute(
+            pokemon_repo.clone(),
+            move_repo.clone(),
+            learnset_repo.clone(),
+            request(25, "Thunder Shock"),
+        )
+        .ok()
+        .expect("entry to be inserted");
+
+        match execute(
+            pokemon_repo,
+            move_repo,
+            learnset_repo,
+            request(25, "thunder shock"),
+        ) {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_the_entry_otherwise() {
+        let (pokemon_repo, move_repo, learnset_repo) = repos();
+
+        match execute(
+            pokemon_repo,
+            move_repo,
+            learnset_repo,
+            request(25, "thunder shock"),
+        ) {
+            Ok(res) => {
+                assert_eq!(res.move_name, String::from("Thunder Shock"));
+                assert_eq!(res.method, String::from("level-up"));
+                assert_eq!(res.level, Some(1));
+            }
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use std::sync::Arc;
+
+use crate::repositories::moves::{InsertError, Repository};
+
+use super::entities::{Move, MoveCategory, MoveName, PokemonType};
+
+pub struct Request {
+    pub name: String,
+    pub move_type: String,
+    pub category: String,
+    pub power: Option<u8>,
+    pub accuracy: Option<u8>,
+    pub pp: u8,
+    pub priority: i8,
+}
+
+pub enum Error {
+    BadRequest(String),
+    Conflict,
+    Unknown,
+}
+
+pub struct Response {
+    pub name: String,
+    pub move_type: String,
+    pub category: String,
+    pub power: Option<u8>,
+    pub accuracy: Option<u8>,
+    pub pp: u8,
+    pub priority: i8,
+}
+
+impl From<Move> for Response {
+    fn from(m: Move) -> Self {
+        Self {
+            name: m.name.into(),
+            move_type: m.move_type.into(),
+            category: m.category.into(),
+            power: m.power,
+            accuracy: m.accuracy,
+            pp: m.pp,
+            priority: m.priority,
+        }
+    }
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let m = Move::new(
+        MoveName::try_from(req.name).map_err(|e| Error::BadRequest(e.to_string()))?,
+        PokemonType::try_from(req.move_type).map_err(|e| Error::BadRequest(e.to_string()))?,
+        MoveCategory::try_from(req.category).map_err(|e| Error::BadRequest(e.to_string()))?,
+        req.power,
+        req.accuracy,
+        req.pp,
+        req.priority,
+    )
+    .map_err(|e| Error::BadRequest(e.to_string()))?;
+
+    match repo.insert(m) {
+        Ok(m) => Ok(m.into()),
+        Err(InsertError::Conflict) => Err(Error::Conflict),
+        Err(InsertError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::moves::InMemoryRepository;
+
+    fn thunderbolt() -> Request {
+        Request {
+            name: String::from("Thunderbolt"),
+            move_type: String::from("electric"),
+            category: String::from("special"),
+            power: Some(90),
+            accuracy: Some(100),
+            pp: 15,
+            priority: 0,
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            move_type: String::from("Sound"),
+            ..thunderbolt()
+        };
+
+        match execute(repo, req) {
+            Err(Error::BadRequest(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_if_the_move_already_exists() {
+        let repo = Arc::new(InMemoryRepository::new());
+        execute(repo.clone(), thunderbolt())
+            .ok()
+            .expect("move to be inserted");
+
+        match execute(repo, thunderbolt()) {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        match execute(repo, thunderbolt()) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_the_move_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, thunderbolt()) {
+            Ok(res) => {
+                assert_eq!(res.move_type, String::from("Electric"));
+                assert_eq!(res.category, String::from("Special"));
+                assert_eq!(res.power, Some(90));
+            }
+            _ => unreachable!(),
+        }
+    }
+}
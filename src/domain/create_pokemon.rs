@@ -1,97 +1,358 @@
 use std::sync::Arc;
 
-use serde::Serialize;
-
-use super::entities::{Pokemon, PokemonName, PokemonNumber, PokemonTypes};
+use super::entities::{
+    AbilityName, BaseStats, Pokemon, PokemonAbilities, PokemonForm, PokemonName, PokemonNumber,
+    PokemonTypes, ValidationError,
+};
+use super::fetch_pokemon::Response;
+use crate::repositories::ability::{self, FetchError};
 use crate::repositories::pokemon::{InsertError, Repository};
 
 pub struct Request {
     pub number: u16,
+    pub form: Option<String>,
     pub name: String,
     pub types: Vec<String>,
+    pub stats: Option<[u16; 6]>,
+    pub abilities: Vec<String>,
+    pub hidden_ability: Option<String>,
 }
 
 pub enum Error {
-    BadRequest,
+    Invalid(Vec<ValidationError>),
+    UnknownAbility(String),
     Conflict,
     Unknown,
 }
 
-#[derive(Serialize)]
-pub struct Response {
-    pub number: u16,
-    pub name: String,
-    pub types: Vec<String>,
-}
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    ability_repo: Arc<dyn ability::Repository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let pokemon = validate(req).map_err(Error::Invalid)?;
+    // Held from the catalogue check until the Pokémon is stored.
+    let _references = ability_repo.reference();
+    let abilities = resolve_abilities(
+        ability_repo.clone(),
+        &pokemon.abilities,
+        &Default::default(),
+    )?;
 
-impl From<Pokemon> for Response {
-    fn from(pokemon: Pokemon) -> Self {
-        Self {
-            number: pokemon.number.try_into().expect("pokemon number"),
-            name: pokemon.name.try_into().expect("pokemon name"),
-            types: Vec::<String>::from(pokemon.types),
-        }
+    match repo.insert(
+        pokemon.number,
+        pokemon.form,
+        pokemon.name,
+        pokemon.types,
+        pokemon.stats,
+        abilities,
+    ) {
+        Ok(pokemon) => Ok(pokemon.into()),
+        Err(InsertError::Conflict) => Err(Error::Conflict),
+        Err(InsertError::Unknown) => Err(Error::Unknown),
     }
 }
 
-pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
-    match (
+// Parses every field before giving up, so that all the mistakes are reported at once.
+pub fn validate(req: Request) -> Result<Pokemon, Vec<ValidationError>> {
+    let mut errors = vec![];
+    let number = check(
         PokemonNumber::try_from(req.number),
+        ValidationError::Number,
+        &mut errors,
+    );
+    let form = check(
+        PokemonForm::try_from(req.form),
+        ValidationError::Form,
+        &mut errors,
+    );
+    let name = check(
         PokemonName::try_from(req.name),
+        ValidationError::Name,
+        &mut errors,
+    );
+    let types = check(
         PokemonTypes::try_from(req.types),
-    ) {
-        (Ok(number), Ok(name), Ok(types)) => match repo.insert(number, name, types) {
-            Ok(pokemon) => Ok(pokemon.into()),
-            Err(InsertError::Conflict) => Err(Error::Conflict),
-            Err(InsertError::Unknown) => Err(Error::Unknown),
-        },
-        _ => Err(Error::BadRequest),
+        ValidationError::Types,
+        &mut errors,
+    );
+    let stats = check(
+        req.stats.map(BaseStats::try_from).transpose(),
+        ValidationError::Stats,
+        &mut errors,
+    );
+    let abilities = check(
+        PokemonAbilities::try_from((req.abilities, req.hidden_ability)),
+        ValidationError::Abilities,
+        &mut errors,
+    );
+
+    match (number, form, name, types, stats, abilities) {
+        (Some(number), Some(form), Some(name), Some(types), Some(stats), Some(abilities)) => {
+            Ok(Pokemon::new(number, form, name, types, stats, abilities))
+        }
+        _ => Err(errors),
     }
 }
 
+fn check<T, E>(
+    res: Result<T, E>,
+    field: fn(E) -> ValidationError,
+    errors: &mut Vec<ValidationError>,
+) -> Option<T> {
+    res.map_err(|e| errors.push(field(e))).ok()
+}
+
+// Checks every ability against the catalogue and stores its catalogue spelling. Abilities in
+// `known` were accepted when they were stored and keep their stored spelling unchecked.
+pub fn resolve_abilities(
+    ability_repo: Arc<dyn ability::Repository>,
+    abilities: &PokemonAbilities,
+    known: &PokemonAbilities,
+) -> Result<PokemonAbilities, Error> {
+    let canonical = |name: &AbilityName| {
+        if let Some(known) = known.iter().find(|known| *known == name) {
+            return Ok(String::from(known.clone()));
+        }
+        match ability_repo.fetch(name.clone()) {
+            Ok(ability) => Ok(String::from(ability.name)),
+            Err(FetchError::NotFound) => Err(Error::UnknownAbility(name.clone().into())),
+            Err(FetchError::Unknown) => Err(Error::Unknown),
+        }
+    };
+    let regular = abilities
+        .regular()
+        .iter()
+        .map(canonical)
+        .collect::<Result<Vec<_>, _>>()?;
+    let hidden = abilities.hidden().map(canonical).transpose()?;
+
+    PokemonAbilities::try_from((regular, hidden))
+        .map_err(|e| Error::Invalid(vec![ValidationError::Abilities(e)]))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::InMemoryRepository;
+    use crate::domain::entities::{
+        Ability, BaseStatsError, Generation, PokemonAbilitiesError, PokemonNameError,
+        PokemonNumberError, PokemonType, PokemonTypesError,
+    };
+    use crate::repositories::ability::Repository as _;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn ability_repo() -> Arc<ability::InMemoryRepository> {
+        let repo = Arc::new(ability::InMemoryRepository::new());
+        for name in ["Static", "Lightning Rod"] {
+            repo.insert(Ability::new(
+                AbilityName::try_from(String::from(name)).unwrap(),
+                String::new(),
+                Generation::try_from(3).unwrap(),
+            ))
+            .ok()
+            .expect("ability to be inserted");
+        }
+        repo
+    }
 
     #[test]
     fn it_should_return_the_pokemon_number_otherwise() {
         let number = 25;
-        let mut repo = Arc::new(InMemoryRepository::new());
+        let repo = Arc::new(InMemoryRepository::new());
         let req = Request {
             number,
+            form: None,
             name: String::from("Pikachu"),
             types: vec![String::from("Electric")],
+            stats: None,
+            abilities: vec![],
+            hidden_ability: None,
         };
-        let res = execute(repo, req);
+        let res = execute(repo, ability_repo(), req);
 
         match res {
             Ok(Response {
                 number,
                 name,
                 types,
+                stats,
+                ..
             }) => {
                 assert_eq!(number, 25);
                 assert_eq!(name, "Pikachu".to_string());
                 assert_eq!(types, vec![String::from("Electric")]);
+                assert_eq!(stats, None);
             }
             _ => unreachable!(),
         }
     }
 
     #[test]
-    fn it_should_return_a_bad_request_err_when_a_request_is_invalid() {
+    fn it_should_accept_any_canonical_type_regardless_of_case() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            number: 1,
+            form: None,
+            name: String::from("Bulbasaur"),
+            types: vec![String::from("grass"), String::from("Poison")],
+            stats: Some([45, 49, 49, 65, 65, 45]),
+            abilities: vec![],
+            hidden_ability: None,
+        };
+        let res = execute(repo, ability_repo(), req);
+
+        match res {
+            Ok(Response { types, stats, .. }) => {
+                assert_eq!(types, vec![String::from("Grass"), String::from("Poison")]);
+                assert_eq!(stats, Some([45, 49, 49, 65, 65, 45]));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_an_invalid_err_when_a_request_is_invalid() {
         let number = 25;
-        let mut repo = Arc::new(InMemoryRepository::new());
+        let repo = Arc::new(InMemoryRepository::new());
         let req = Request {
             number,
+            form: None,
             name: String::from(""),
             types: vec![String::from("Electric")],
+            stats: None,
+            abilities: vec![],
+            hidden_ability: None,
+        };
+        let res = execute(repo, ability_repo(), req);
+
+        match res {
+            Err(Error::Invalid(errors)) => {
+                assert_eq!(errors, vec![ValidationError::Name(PokemonNameError::Empty)])
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_an_invalid_err_when_stats_are_out_of_range() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            number: 25,
+            form: None,
+            name: String::from("Pikachu"),
+            types: vec![String::from("Electric")],
+            stats: Some([35, 55, 40, 50, 50, 0]),
+            abilities: vec![],
+            hidden_ability: None,
+        };
+        let res = execute(repo, ability_repo(), req);
+
+        match res {
+            Err(Error::Invalid(errors)) => assert_eq!(
+                errors,
+                vec![ValidationError::Stats(BaseStatsError::OutOfRange(vec![(
+                    "speed", 0
+                )]))]
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_report_every_invalid_field_at_once() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            number: 0,
+            form: None,
+            name: String::new(),
+            types: vec![String::from("Fyre")],
+            stats: None,
+            abilities: vec![String::from("Static"), String::from("static")],
+            hidden_ability: None,
+        };
+
+        match execute(repo, ability_repo(), req) {
+            Err(Error::Invalid(errors)) => assert_eq!(
+                errors,
+                vec![
+                    ValidationError::Number(PokemonNumberError::OutOfRange(0)),
+                    ValidationError::Name(PokemonNameError::Empty),
+                    ValidationError::Types(PokemonTypesError::UnknownType(String::from("Fyre"))),
+                    ValidationError::Abilities(PokemonAbilitiesError::Duplicate(String::from(
+                        "static"
+                    ))),
+                ]
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_an_invalid_err_when_types_are_duplicated() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            number: 4,
+            form: None,
+            name: String::from("Charmander"),
+            types: vec![String::from("Fire"), String::from("Fire")],
+            stats: None,
+            abilities: vec![],
+            hidden_ability: None,
         };
-        let res = execute(repo, req);
+        let res = execute(repo, ability_repo(), req);
 
         match res {
-            Err(Error::BadRequest) => {}
+            Err(Error::Invalid(errors)) => assert_eq!(
+                errors,
+                vec![ValidationError::Types(PokemonTypesError::Duplicate(
+                    PokemonType::Fire
+                ))]
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_reference_existing_abilities() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            number: 25,
+            form: None,
+            name: String::from("Pikachu"),
+            types: vec![String::from("Electric")],
+            stats: None,
+            abilities: vec![String::from("static")],
+            hidden_ability: Some(String::from("Lightning Rod")),
+        };
+
+        match execute(repo, ability_repo(), req) {
+            Ok(Response {
+                abilities,
+                hidden_ability,
+                ..
+            }) => {
+                assert_eq!(abilities, vec![String::from("Static")]);
+                assert_eq!(hidden_ability, Some(String::from("Lightning Rod")));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_ability_err_when_an_ability_is_not_in_the_catalogue() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            number: 25,
+            form: None,
+            name: String::from("Pikachu"),
+            types: vec![String::from("Electric")],
+            stats: None,
+            abilities: vec![String::from("Static"), String::from("Levitate")],
+            hidden_ability: None,
+        };
+
+        match execute(repo, ability_repo(), req) {
+            Err(Error::UnknownAbility(name)) => assert_eq!(name, String::from("Levitate")),
             _ => unreachable!(),
         }
     }
@@ -102,15 +363,28 @@ mod test {
         let name = PokemonName::try_from(String::from("Pikachu")).unwrap();
         let types = PokemonTypes::try_from(vec![String::from("Electric")]).unwrap();
 
-        let mut repo = Arc::new(InMemoryRepository::new());
-        repo.insert(number, name, types);
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            number,
+            PokemonForm::default(),
+            name,
+            types,
+            None,
+            PokemonAbilities::default(),
+        )
+        .ok()
+        .expect("pokemon to be inserted");
         let req = Request {
             number: 25,
+            form: None,
             name: String::from("test"),
             types: vec![String::from("Fire")],
+            stats: None,
+            abilities: vec![],
+            hidden_ability: None,
         };
 
-        let res = execute(repo, req);
+        let res = execute(repo, ability_repo(), req);
 
         match res {
             Err(Error::Conflict) => {}
@@ -118,17 +392,73 @@ mod test {
         }
     }
 
+    #[test]
+    fn it_should_allow_another_form_of_an_existing_number() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = |form: Option<&str>, types: Vec<&str>| Request {
+            number: 26,
+            form: form.map(String::from),
+            name: String::from("Raichu"),
+            types: types.into_iter().map(String::from).collect(),
+            stats: None,
+            abilities: vec![],
+            hidden_ability: None,
+        };
+        execute(repo.clone(), ability_repo(), req(None, vec!["Electric"]))
+            .ok()
+            .expect("pokemon to be created");
+
+        match execute(
+            repo.clone(),
+            ability_repo(),
+            req(Some("Alola"), vec!["Electric", "Psychic"]),
+        ) {
+            Ok(Response { form, .. }) => assert_eq!(form, Some(String::from("alola"))),
+            _ => unreachable!(),
+        }
+        match execute(repo, ability_repo(), req(Some("alola"), vec!["Electric"])) {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_if_another_number_has_the_same_name() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = |number: u16, name: &str| Request {
+            number,
+            form: None,
+            name: String::from(name),
+            types: vec![String::from("Electric")],
+            stats: None,
+            abilities: vec![],
+            hidden_ability: None,
+        };
+        execute(repo.clone(), ability_repo(), req(25, "Pikachu"))
+            .ok()
+            .expect("pokemon to be created");
+
+        match execute(repo, ability_repo(), req(26, "pIKACHU")) {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        }
+    }
+
     #[test]
     fn it_should_return_an_error_when_an_unexpected_error_happens() {
-        let mut repo = Arc::new(InMemoryRepository::new().with_error());
+        let repo = Arc::new(InMemoryRepository::new().with_error());
 
         let req = Request {
             number: 25,
+            form: None,
             name: String::from("test"),
             types: vec![String::from("Fire")],
+            stats: None,
+            abilities: vec![],
+            hidden_ability: None,
         };
 
-        let res = execute(repo, req);
+        let res = execute(repo, ability_repo(), req);
 
         match res {
             Err(Error::Unknown) => {}
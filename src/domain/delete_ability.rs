@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use crate::repositories::ability::{self, DeleteError};
+use crate::repositories::pokemon::{self, FetchAllError};
+
+use super::entities::AbilityName;
+
+pub enum Error {
+    BadRequest,
+    NotFound,
+    Conflict,
+    Unknown,
+}
+
+pub struct Response {
+    pub name: String,
+    pub description: String,
+    pub generation: u8,
+}
+
+pub fn execute(
+    name: String,
+    ability_repo: Arc<dyn ability::Repository>,
+    pokemon_repo: Arc<dyn pokemon::Repository>,
+) -> Result<Response, Error> {
+    let name = match AbilityName::try_from(name) {
+        Ok(name) => name,
+        _ => return Err(Error::BadRequest),
+    };
+
+    // Trashed Pokémon count too, since restoring one brings its abilities back.
+    let in_use = |name: &AbilityName| match (pokemon_repo.fetch_all(), pokemon_repo.fetch_trash()) {
+        (Ok(pokemons), Ok(trash)) => Ok(pokemons
+            .iter()
+            .chain(trash.iter().map(|tombstone| &tombstone.pokemon))
+            .any(|p| p.abilities.contains(name))),
+        (Err(FetchAllError::Unknown), _) | (_, Err(FetchAllError::Unknown)) => Err(()),
+    };
+
+    match ability_repo.delete(name, &in_use) {
+        Ok(ability) => Ok(Response {
+            name: ability.name.into(),
+            description: ability.description,
+            generation: ability.generation.into(),
+        }),
+        Err(DeleteError::NotFound) => Err(Error::NotFound),
+        Err(DeleteError::InUse) => Err(Error::Conflict),
+        Err(DeleteError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        Ability, Generation, PokemonAbilities, PokemonForm, PokemonName, PokemonNumber,
+        PokemonTypes,
+    };
+    use crate::repositories::ability::Repository as _;
+    use crate::repositories::pokemon::Repository as _;
+
+    fn repos() -> (
+        Arc<ability::InMemoryRepository>,
+        Arc<pokemon::InMemoryRepository>,
+    ) {
+        let ability_repo = Arc::new(ability::InMemoryRepository::new());
+        for name in ["Static", "Lightning Rod", "Blaze"] {
+            ability_repo
+                .insert(Ability::new(
+                    AbilityName::try_from(String::from(name)).unwrap(),
+                    String::new(),
+                    Generation::try_from(3).unwrap(),
+                ))
+                .ok()
+                .expect("ability to be inserted");
+        }
+        let pokemon_repo = Arc::new(pokemon::InMemoryRepository::new());
+        pokemon_repo
+            .insert(
+                PokemonNumber::pikachu(),
+                PokemonForm::default(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+                None,
+                PokemonAbilities::pikachu(),
+            )
+            .ok()
+            .expect("pokemon to be inserted");
+        (ability_repo, pokemon_repo)
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_when_a_pokemon_still_references_the_ability() {
+        let (ability_repo, pokemon_repo) = repos();
+
+        match execute(String::from("lightning rod"), ability_repo, pokemon_repo) {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_when_a_trashed_pokemon_references_the_ability() {
+        let (ability_repo, pokemon_repo) = repos();
+        pokemon_repo
+            .delete(PokemonNumber::pikachu(), &PokemonForm::default(), None, 0)
+            .ok()
+            .expect("pokemon to be deleted");
+
+        match execute(String::from("Static"), ability_repo, pokemon_repo) {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_ability_doesnot_exist() {
+        let (ability_repo, pokemon_repo) = repos();
+
+        match execute(String::from("Levitate"), ability_repo, pokemon_repo) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let (_, pokemon_repo) = repos();
+        let ability_repo = Arc::new(ability::InMemoryRepository::new().with_error());
+
+        match execute(String::from("Blaze"), ability_repo, pokemon_repo) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_the_deleted_ability_otherwise() {
+        let (ability_repo, pokemon_repo) = repos();
+
+        match execute(String::from("Blaze"), ability_repo, pokemon_repo) {
+            Ok(res) => assert_eq!(res.name, String::from("Blaze")),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_not_wait_for_pokemons_stored_against_another_catalogue() {
+        let (ability_repo, pokemon_repo) = repos();
+        let (other, _) = repos();
+        let _references = other.reference();
+
+        match execute(String::from("Blaze"), ability_repo, pokemon_repo) {
+            Ok(res) => assert_eq!(res.name, String::from("Blaze")),
+            _ => unreachable!(),
+        }
+    }
+}
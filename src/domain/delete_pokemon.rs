@@ -1,34 +1,58 @@
 use crate::repositories::pokemon::{DeleteError, Repository};
 use std::sync::Arc;
 
-use super::entities::{Pokemon, PokemonNumber};
+use super::entities::{Pokemon, PokemonForm, PokemonNumber};
 
 pub enum Error {
     Unknown,
     NotFound,
     BadRequest,
+    PreconditionFailed,
 }
 
 pub struct Response {
     pub number: u16,
+    pub form: Option<String>,
     pub name: String,
     pub types: Vec<String>,
+    pub stats: Option<[u16; 6]>,
+    pub abilities: Vec<String>,
+    pub hidden_ability: Option<String>,
 }
 
-pub fn execute(number: u16, repo: Arc<dyn Repository>) -> Result<Response, Error> {
-    match PokemonNumber::try_from(number) {
-        Ok(number) => match repo.delete(number) {
+pub fn execute(
+    number: u16,
+    form: Option<String>,
+    version: Option<u64>,
+    // Seconds since the epoch, stamped on the tombstone.
+    now: u64,
+    repo: Arc<dyn Repository>,
+) -> Result<Response, Error> {
+    match (PokemonNumber::try_from(number), PokemonForm::try_from(form)) {
+        (Ok(number), Ok(form)) => match repo.delete(number, &form, version, now) {
             Ok(Pokemon {
                 number,
+                form,
                 name,
                 types,
-            }) => Ok(Response {
-                number: number.into(),
-                name: name.into(),
-                types: Vec::<String>::from(types),
-            }),
+                stats,
+                abilities,
+                ..
+            }) => {
+                let (abilities, hidden_ability) = abilities.into();
+                Ok(Response {
+                    number: number.into(),
+                    form: form.into(),
+                    name: name.into(),
+                    types: Vec::<String>::from(types),
+                    stats: stats.map(<[u16; 6]>::from),
+                    abilities,
+                    hidden_ability,
+                })
+            }
             Err(DeleteError::Unknown) => Err(Error::Unknown),
             Err(DeleteError::NotFound) => Err(Error::NotFound),
+            Err(DeleteError::VersionMismatch) => Err(Error::PreconditionFailed),
         },
         _ => Err(Error::BadRequest),
     }
@@ -39,7 +63,9 @@ mod test {
 
     use super::*;
     use crate::{
-        domain::entities::{PokemonName, PokemonNumber, PokemonTypes},
+        domain::entities::{
+            BaseStats, PokemonAbilities, PokemonForm, PokemonName, PokemonNumber, PokemonTypes,
+        },
         repositories::pokemon::InMemoryRepository,
     };
 
@@ -48,7 +74,7 @@ mod test {
         let repo = Arc::new(InMemoryRepository::new().with_error());
         let req = u16::from(PokemonNumber::pikachu());
 
-        match execute(req, repo) {
+        match execute(req, None, None, 0, repo) {
             Err(Error::Unknown) => {}
             _ => unreachable!(),
         }
@@ -59,7 +85,7 @@ mod test {
         let repo = Arc::new(InMemoryRepository::new());
         let req = u16::from(PokemonNumber::bad());
 
-        match execute(req, repo) {
+        match execute(req, None, None, 0, repo) {
             Err(Error::BadRequest) => {}
             _ => unreachable!(),
         }
@@ -70,29 +96,64 @@ mod test {
         let repo = Arc::new(InMemoryRepository::new());
         repo.insert(
             PokemonNumber::charmander(),
+            PokemonForm::default(),
             PokemonName::charmander(),
             PokemonTypes::charmander(),
-        );
+            Some(BaseStats::charmander()),
+            PokemonAbilities::default(),
+        )
+        .ok()
+        .expect("pokemon to be inserted");
         let req = u16::from(PokemonNumber::pikachu());
 
-        match execute(req, repo) {
+        match execute(req, None, None, 0, repo) {
             Err(Error::NotFound) => {}
             _ => unreachable!(),
         }
     }
 
+    #[test]
+    fn it_should_return_a_precondition_failed_error_when_the_version_is_stale() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonForm::default(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+            Some(BaseStats::charmander()),
+            PokemonAbilities::default(),
+        )
+        .ok()
+        .expect("pokemon to be inserted");
+        let req = u16::from(PokemonNumber::charmander());
+
+        match execute(req, None, Some(2), 0, repo.clone()) {
+            Err(Error::PreconditionFailed) => {}
+            _ => unreachable!(),
+        }
+        match execute(req, None, Some(1), 0, repo) {
+            Ok(_) => {}
+            _ => unreachable!(),
+        }
+    }
+
     #[test]
     fn it_should_return_a_deleted_pokemon_otherwise() {
         let repo = Arc::new(InMemoryRepository::new());
         repo.insert(
             PokemonNumber::charmander(),
+            PokemonForm::default(),
             PokemonName::charmander(),
             PokemonTypes::charmander(),
-        );
+            Some(BaseStats::charmander()),
+            PokemonAbilities::default(),
+        )
+        .ok()
+        .expect("pokemon to be inserted");
         let req = u16::from(PokemonNumber::charmander());
 
-        match execute(req, repo) {
-            Ok(Response) => {}
+        match execute(req, None, None, 0, repo) {
+            Ok(_) => {}
             _ => unreachable!(),
         }
     }
@@ -1,19 +1,40 @@
 use core::fmt;
+use std::collections::BTreeSet;
 use std::fmt::Display;
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicU16, Ordering};
 
 #[derive(Clone, Debug)]
 pub struct Pokemon {
     pub number: PokemonNumber,
+    pub form: PokemonForm,
     pub name: PokemonName,
     pub types: PokemonTypes,
+    pub stats: Option<BaseStats>,
+    pub abilities: PokemonAbilities,
+    // Bumped by the repository on every update, starting from `Pokemon::FIRST_VERSION`.
+    pub version: u64,
 }
 
 impl Pokemon {
-    pub fn new(number: PokemonNumber, name: PokemonName, types: PokemonTypes) -> Self {
+    pub const FIRST_VERSION: u64 = 1;
+
+    pub fn new(
+        number: PokemonNumber,
+        form: PokemonForm,
+        name: PokemonName,
+        types: PokemonTypes,
+        stats: Option<BaseStats>,
+        abilities: PokemonAbilities,
+    ) -> Self {
         Self {
             number,
+            form,
             name,
             types,
+            stats,
+            abilities,
+            version: Self::FIRST_VERSION,
         }
     }
 }
@@ -22,8 +43,8 @@ impl fmt::Display for Pokemon {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "pokemon number: {}, name: {}, types: {}",
-            self.number, self.name, self.types
+            "pokemon number: {}, form: {}, name: {}, types: {}",
+            self.number, self.form, self.name, self.types
         )
     }
 }
@@ -31,20 +52,117 @@ impl fmt::Display for Pokemon {
 #[derive(PartialEq, Clone, Ord, Eq, PartialOrd, Debug)]
 pub struct PokemonNumber(u16);
 
+// Valid national dex numbers, set once at startup by `PokemonNumber::configure_range`.
+static MIN_NUMBER: AtomicU16 = AtomicU16::new(1);
+static MAX_NUMBER: AtomicU16 = AtomicU16::new(PokemonNumber::DEFAULT_MAX);
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DexRangeError(pub u16, pub u16);
+
+impl Display for DexRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "dex range {}..={} must be non-empty and start at 1 or more",
+            self.0, self.1
+        )
+    }
+}
+
+impl PokemonNumber {
+    pub const DEFAULT_MAX: u16 = 1025;
+
+    // First national dex number of each generation, with its main region.
+    const GENERATIONS: [(u16, Region); 10] = [
+        (1, Region::Kanto),
+        (152, Region::Johto),
+        (252, Region::Hoenn),
+        (387, Region::Sinnoh),
+        (494, Region::Unova),
+        (650, Region::Kalos),
+        (722, Region::Alola),
+        (810, Region::Galar),
+        (899, Region::Hisui),
+        (906, Region::Paldea),
+    ];
+
+    pub fn configure_range(range: RangeInclusive<u16>) -> Result<(), DexRangeError> {
+        if *range.start() == 0 || range.is_empty() {
+            return Err(DexRangeError(*range.start(), *range.end()));
+        }
+        MIN_NUMBER.store(*range.start(), Ordering::Relaxed);
+        MAX_NUMBER.store(*range.end(), Ordering::Relaxed);
+        Ok(())
+    }
+
+    // Numbers read back from storage were checked when they were written; the configured
+    // range may have narrowed since, and that must not make stored data unreadable.
+    pub fn from_stored(n: u16) -> Self {
+        Self(n)
+    }
+
+    pub fn range() -> RangeInclusive<u16> {
+        MIN_NUMBER.load(Ordering::Relaxed)..=MAX_NUMBER.load(Ordering::Relaxed)
+    }
+
+    // Hisui shares generation 8 with Galar. Numbers past the last known generation
+    // are valid when the range allows them, but belong to no generation yet.
+    fn era(&self) -> Option<(Generation, Region)> {
+        if self.0 > Self::DEFAULT_MAX {
+            return None;
+        }
+        let idx = Self::GENERATIONS
+            .iter()
+            .rposition(|(start, _)| *start <= self.0)?;
+        let generation = if idx >= 8 { idx } else { idx + 1 };
+        Some((Generation(generation as u8), Self::GENERATIONS[idx].1))
+    }
+
+    pub fn generation(&self) -> Option<Generation> {
+        self.era().map(|(generation, _)| generation)
+    }
+
+    pub fn region(&self) -> Option<Region> {
+        self.era().map(|(_, region)| region)
+    }
+}
+
 impl Display for PokemonNumber {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "number: {}", self.0)
     }
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub enum PokemonNumberError {
+    OutOfRange(u16),
+}
+
+impl Display for PokemonNumberError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfRange(n) => {
+                let range = PokemonNumber::range();
+                write!(
+                    f,
+                    "number {} is out of range, expected {} to {}",
+                    n,
+                    range.start(),
+                    range.end()
+                )
+            }
+        }
+    }
+}
+
 impl TryFrom<u16> for PokemonNumber {
-    type Error = ();
+    type Error = PokemonNumberError;
 
     fn try_from(n: u16) -> Result<Self, Self::Error> {
-        if n > 0 && n < 899 {
+        if Self::range().contains(&n) {
             Ok(Self(n))
         } else {
-            Err(())
+            Err(PokemonNumberError::OutOfRange(n))
         }
     }
 }
@@ -55,21 +173,142 @@ impl From<PokemonNumber> for u16 {
     }
 }
 
+// Identifies a variant of a species, e.g. "alola" or "mega-x". The default form is empty.
+#[derive(PartialEq, Clone, Ord, Eq, PartialOrd, Debug, Default)]
+pub struct PokemonForm(String);
+
+impl PokemonForm {
+    pub fn is_default(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Display for PokemonForm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_default() {
+            write!(f, "default")
+        } else {
+            write!(f, "{}", self.0)
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PokemonFormError {
+    InvalidCharacters(String),
+}
+
+impl Display for PokemonFormError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCharacters(form) => write!(
+                f,
+                "form \"{}\" may only contain letters, digits and dashes",
+                form
+            ),
+        }
+    }
+}
+
+impl TryFrom<String> for PokemonForm {
+    type Error = PokemonFormError;
+
+    fn try_from(f: String) -> Result<Self, Self::Error> {
+        let normalized = f.trim().to_ascii_lowercase();
+        if normalized.is_empty() || normalized == "default" {
+            Ok(Self::default())
+        } else if normalized
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-')
+        {
+            Ok(Self(normalized))
+        } else {
+            Err(PokemonFormError::InvalidCharacters(f))
+        }
+    }
+}
+
+impl TryFrom<Option<String>> for PokemonForm {
+    type Error = PokemonFormError;
+
+    fn try_from(f: Option<String>) -> Result<Self, Self::Error> {
+        f.map_or(Ok(Self::default()), Self::try_from)
+    }
+}
+
+impl From<PokemonForm> for Option<String> {
+    fn from(f: PokemonForm) -> Self {
+        if f.is_default() {
+            None
+        } else {
+            Some(f.0)
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Region {
+    Kanto,
+    Johto,
+    Hoenn,
+    Sinnoh,
+    Unova,
+    Kalos,
+    Alola,
+    Galar,
+    Hisui,
+    Paldea,
+}
+
+impl From<Region> for String {
+    fn from(region: Region) -> String {
+        format!("{:?}", region)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct PokemonName(String);
 
+impl PokemonName {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    // Lowercased three-character windows, padded so short names and word edges still match.
+    pub fn trigrams(&self) -> BTreeSet<String> {
+        let chars: Vec<char> = format!("  {} ", self.0.to_lowercase()).chars().collect();
+        chars
+            .windows(3)
+            .map(|window| window.iter().collect())
+            .collect()
+    }
+}
+
 impl Display for PokemonName {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "name: {}", self.0)
     }
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub enum PokemonNameError {
+    Empty,
+}
+
+impl Display for PokemonNameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "a pokemon name cannot be empty"),
+        }
+    }
+}
+
 impl TryFrom<String> for PokemonName {
-    type Error = ();
+    type Error = PokemonNameError;
 
     fn try_from(n: String) -> Result<Self, Self::Error> {
         if n.is_empty() {
-            Err(())
+            Err(PokemonNameError::Empty)
         } else {
             Ok(Self(n))
         }
@@ -90,26 +329,76 @@ impl Display for PokemonTypes {
         let s = self
             .0
             .iter()
-            .map(|t| t.clone().into())
-            .collect::<Vec<String>>()
+            .map(|t| t.as_str())
+            .collect::<Vec<&str>>()
             .join(",");
         write!(f, "types: {}", s)
     }
 }
 
+impl PokemonTypes {
+    pub const MAX: usize = 2;
+
+    pub fn iter(&self) -> impl Iterator<Item = &PokemonType> {
+        self.0.iter()
+    }
+
+    pub fn primary(&self) -> PokemonType {
+        self.0[0]
+    }
+
+    pub fn secondary(&self) -> Option<PokemonType> {
+        self.0.get(1).copied()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PokemonTypesError {
+    Empty,
+    TooMany(usize),
+    UnknownType(String),
+    Duplicate(PokemonType),
+}
+
+impl Display for PokemonTypesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "a pokemon must have at least one type"),
+            Self::TooMany(n) => write!(
+                f,
+                "a pokemon has at most {} types, got {}",
+                PokemonTypes::MAX,
+                n
+            ),
+            Self::UnknownType(t) => write!(f, "unknown type \"{}\"", t),
+            Self::Duplicate(t) => write!(f, "type {} is listed more than once", t),
+        }
+    }
+}
+
 impl TryFrom<Vec<String>> for PokemonTypes {
-    type Error = ();
+    type Error = PokemonTypesError;
 
     fn try_from(types: Vec<String>) -> Result<Self, Self::Error> {
         if types.is_empty() {
-            Err(())
-        } else {
-            types
-                .into_iter()
-                .map(PokemonType::try_from)
-                .collect::<Result<Vec<_>, _>>()
-                .map(PokemonTypes)
+            return Err(PokemonTypesError::Empty);
+        }
+        if types.len() > Self::MAX {
+            return Err(PokemonTypesError::TooMany(types.len()));
         }
+
+        let mut parsed: Vec<PokemonType> = Vec::with_capacity(types.len());
+        for t in types.into_iter() {
+            let pt = match PokemonType::try_from(t.clone()) {
+                Ok(pt) => pt,
+                _ => return Err(PokemonTypesError::UnknownType(t)),
+            };
+            if parsed.contains(&pt) {
+                return Err(PokemonTypesError::Duplicate(pt));
+            }
+            parsed.push(pt);
+        }
+        Ok(Self(parsed))
     }
 }
 
@@ -123,30 +412,833 @@ impl From<PokemonTypes> for Vec<String> {
     }
 }
 
-#[derive(Clone, Debug)]
-enum PokemonType {
-    Electric,
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BaseStats {
+    hp: u8,
+    attack: u8,
+    defense: u8,
+    special_attack: u8,
+    special_defense: u8,
+    speed: u8,
+}
+
+impl BaseStats {
+    pub fn hp(&self) -> u8 {
+        self.hp
+    }
+
+    pub fn attack(&self) -> u8 {
+        self.attack
+    }
+
+    pub fn defense(&self) -> u8 {
+        self.defense
+    }
+
+    pub fn special_attack(&self) -> u8 {
+        self.special_attack
+    }
+
+    pub fn special_defense(&self) -> u8 {
+        self.special_defense
+    }
+
+    pub fn speed(&self) -> u8 {
+        self.speed
+    }
+
+    pub fn total(&self) -> u16 {
+        <[u16; 6]>::from(*self).iter().sum()
+    }
+}
+
+impl Display for BaseStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "stats: {}/{}/{}/{}/{}/{}",
+            self.hp,
+            self.attack,
+            self.defense,
+            self.special_attack,
+            self.special_defense,
+            self.speed
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BaseStatsError {
+    // Every stat outside 1..=255, in the usual order.
+    OutOfRange(Vec<(&'static str, u16)>),
+}
+
+impl Display for BaseStatsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfRange(stats) => {
+                for (i, (stat, value)) in stats.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "; ")?;
+                    }
+                    write!(f, "{} must be between 1 and 255, got {}", stat, value)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl BaseStats {
+    pub const NAMES: [&'static str; 6] = [
+        "hp",
+        "attack",
+        "defense",
+        "special_attack",
+        "special_defense",
+        "speed",
+    ];
+}
+
+// Stats are given in the usual order: HP, Attack, Defense, Sp. Atk, Sp. Def, Speed.
+impl TryFrom<[u16; 6]> for BaseStats {
+    type Error = BaseStatsError;
+
+    fn try_from(stats: [u16; 6]) -> Result<Self, Self::Error> {
+        let out_of_range: Vec<(&'static str, u16)> = Self::NAMES
+            .into_iter()
+            .zip(stats)
+            .filter(|(_, s)| !(1..=255).contains(s))
+            .collect();
+        if !out_of_range.is_empty() {
+            return Err(BaseStatsError::OutOfRange(out_of_range));
+        }
+        let [hp, attack, defense, special_attack, special_defense, speed] = stats.map(|s| s as u8);
+        Ok(Self {
+            hp,
+            attack,
+            defense,
+            special_attack,
+            special_defense,
+            speed,
+        })
+    }
+}
+
+impl From<BaseStats> for [u16; 6] {
+    fn from(stats: BaseStats) -> Self {
+        [
+            stats.hp,
+            stats.attack,
+            stats.defense,
+            stats.special_attack,
+            stats.special_defense,
+            stats.speed,
+        ]
+        .map(u16::from)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PokemonType {
+    Normal,
     Fire,
+    Water,
+    Electric,
+    Grass,
+    Ice,
+    Fighting,
+    Poison,
+    Ground,
+    Flying,
+    Psychic,
+    Bug,
+    Rock,
+    Ghost,
+    Dragon,
+    Dark,
+    Steel,
+    Fairy,
+}
+
+impl PokemonType {
+    pub const ALL: [PokemonType; 18] = [
+        Self::Normal,
+        Self::Fire,
+        Self::Water,
+        Self::Electric,
+        Self::Grass,
+        Self::Ice,
+        Self::Fighting,
+        Self::Poison,
+        Self::Ground,
+        Self::Flying,
+        Self::Psychic,
+        Self::Bug,
+        Self::Rock,
+        Self::Ghost,
+        Self::Dragon,
+        Self::Dark,
+        Self::Steel,
+        Self::Fairy,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Normal => "Normal",
+            Self::Fire => "Fire",
+            Self::Water => "Water",
+            Self::Electric => "Electric",
+            Self::Grass => "Grass",
+            Self::Ice => "Ice",
+            Self::Fighting => "Fighting",
+            Self::Poison => "Poison",
+            Self::Ground => "Ground",
+            Self::Flying => "Flying",
+            Self::Psychic => "Psychic",
+            Self::Bug => "Bug",
+            Self::Rock => "Rock",
+            Self::Ghost => "Ghost",
+            Self::Dragon => "Dragon",
+            Self::Dark => "Dark",
+            Self::Steel => "Steel",
+            Self::Fairy => "Fairy",
+        }
+    }
+}
+
+impl Display for PokemonType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PokemonTypeError {
+    Unknown(String),
+}
+
+impl Display for PokemonTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(t) => write!(f, "unknown type \"{}\"", t),
+        }
+    }
 }
 
 impl TryFrom<String> for PokemonType {
-    type Error = ();
+    type Error = PokemonTypeError;
 
     fn try_from(s: String) -> Result<Self, Self::Error> {
-        match s.as_str() {
-            "Electric" => Ok(Self::Electric),
-            "Fire" => Ok(Self::Fire),
-            _ => Err(()),
-        }
+        let trimmed = s.trim();
+        Self::ALL
+            .into_iter()
+            .find(|t| t.as_str().eq_ignore_ascii_case(trimmed))
+            .ok_or(PokemonTypeError::Unknown(s))
     }
 }
 
 impl From<PokemonType> for String {
     fn from(ptype: PokemonType) -> String {
-        match ptype {
-            PokemonType::Electric => "Electric".to_string(),
-            PokemonType::Fire => "Fire".to_string(),
-            _ => unreachable!(),
+        ptype.as_str().to_string()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Generation(u8);
+
+impl Generation {
+    pub const LATEST: u8 = 9;
+
+    // National dex numbers introduced by this generation.
+    pub fn numbers(&self) -> RangeInclusive<u16> {
+        let mut eras = PokemonNumber::GENERATIONS
+            .iter()
+            .map(|(start, _)| PokemonNumber(*start))
+            .skip_while(|number| number.generation().as_ref() != Some(self));
+        let start = eras.next().map_or(1, |number| number.0);
+        let end = eras
+            .find(|number| number.generation().as_ref() != Some(self))
+            .map_or(PokemonNumber::DEFAULT_MAX, |number| number.0 - 1);
+        start..=end
+    }
+}
+
+impl Display for Generation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "generation: {}", self.0)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GenerationError {
+    OutOfRange(u8),
+}
+
+impl Display for GenerationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfRange(g) => write!(
+                f,
+                "generation must be between 1 and {}, got {}",
+                Generation::LATEST,
+                g
+            ),
+        }
+    }
+}
+
+impl TryFrom<u8> for Generation {
+    type Error = GenerationError;
+
+    fn try_from(g: u8) -> Result<Self, Self::Error> {
+        if (1..=Self::LATEST).contains(&g) {
+            Ok(Self(g))
+        } else {
+            Err(GenerationError::OutOfRange(g))
+        }
+    }
+}
+
+impl From<Generation> for u8 {
+    fn from(g: Generation) -> Self {
+        g.0
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AbilityName(String);
+
+impl PartialEq for AbilityName {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_ignore_ascii_case(&other.0)
+    }
+}
+
+impl Display for AbilityName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ability: {}", self.0)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AbilityNameError {
+    Empty,
+}
+
+impl Display for AbilityNameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "an ability name cannot be empty"),
+        }
+    }
+}
+
+impl TryFrom<String> for AbilityName {
+    type Error = AbilityNameError;
+
+    fn try_from(n: String) -> Result<Self, Self::Error> {
+        let n = n.trim();
+        if n.is_empty() {
+            Err(AbilityNameError::Empty)
+        } else {
+            Ok(Self(n.to_string()))
+        }
+    }
+}
+
+impl From<AbilityName> for String {
+    fn from(n: AbilityName) -> String {
+        n.0
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Ability {
+    pub name: AbilityName,
+    pub description: String,
+    pub generation: Generation,
+}
+
+impl Ability {
+    pub fn new(name: AbilityName, description: String, generation: Generation) -> Self {
+        Self {
+            name,
+            description,
+            generation,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct PokemonAbilities {
+    regular: Vec<AbilityName>,
+    hidden: Option<AbilityName>,
+}
+
+impl PokemonAbilities {
+    pub const MAX_REGULAR: usize = 2;
+
+    pub fn regular(&self) -> &[AbilityName] {
+        &self.regular
+    }
+
+    pub fn hidden(&self) -> Option<&AbilityName> {
+        self.hidden.as_ref()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &AbilityName> {
+        self.regular.iter().chain(self.hidden.iter())
+    }
+
+    pub fn contains(&self, name: &AbilityName) -> bool {
+        self.iter().any(|n| n == name)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PokemonAbilitiesError {
+    TooMany(usize),
+    EmptyName,
+    Duplicate(String),
+}
+
+impl Display for PokemonAbilitiesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooMany(n) => write!(
+                f,
+                "a pokemon has at most {} regular abilities, got {}",
+                PokemonAbilities::MAX_REGULAR,
+                n
+            ),
+            Self::EmptyName => write!(f, "an ability name cannot be empty"),
+            Self::Duplicate(name) => write!(f, "ability \"{}\" is listed more than once", name),
+        }
+    }
+}
+
+impl TryFrom<(Vec<String>, Option<String>)> for PokemonAbilities {
+    type Error = PokemonAbilitiesError;
+
+    fn try_from((regular, hidden): (Vec<String>, Option<String>)) -> Result<Self, Self::Error> {
+        if regular.len() > Self::MAX_REGULAR {
+            return Err(PokemonAbilitiesError::TooMany(regular.len()));
+        }
+        let name = |n| AbilityName::try_from(n).map_err(|_| PokemonAbilitiesError::EmptyName);
+        let regular = regular
+            .into_iter()
+            .map(name)
+            .collect::<Result<Vec<_>, _>>()?;
+        let hidden = hidden.map(name).transpose()?;
+
+        let abilities = Self { regular, hidden };
+        let names = abilities.iter().collect::<Vec<_>>();
+        if let Some(name) = names
+            .iter()
+            .enumerate()
+            .find_map(|(i, name)| names[..i].contains(name).then_some(name))
+        {
+            return Err(PokemonAbilitiesError::Duplicate(name.0.clone()));
+        }
+        Ok(abilities)
+    }
+}
+
+// A rejected field of a pokemon, tagged with the field it came from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValidationError {
+    Number(PokemonNumberError),
+    Form(PokemonFormError),
+    Name(PokemonNameError),
+    Types(PokemonTypesError),
+    Stats(BaseStatsError),
+    Abilities(PokemonAbilitiesError),
+}
+
+impl ValidationError {
+    pub fn field(&self) -> &'static str {
+        match self {
+            Self::Number(_) => "number",
+            Self::Form(_) => "form",
+            Self::Name(_) => "name",
+            Self::Types(_) => "types",
+            Self::Stats(_) => "stats",
+            Self::Abilities(_) => "abilities",
+        }
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(e) => e.fmt(f),
+            Self::Form(e) => e.fmt(f),
+            Self::Name(e) => e.fmt(f),
+            Self::Types(e) => e.fmt(f),
+            Self::Stats(e) => e.fmt(f),
+            Self::Abilities(e) => e.fmt(f),
+        }
+    }
+}
+
+impl From<PokemonAbilities> for (Vec<String>, Option<String>) {
+    fn from(abilities: PokemonAbilities) -> Self {
+        (
+            abilities.regular.into_iter().map(String::from).collect(),
+            abilities.hidden.map(String::from),
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MoveName(String);
+
+impl PartialEq for MoveName {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_ignore_ascii_case(&other.0)
+    }
+}
+
+impl Display for MoveName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "move: {}", self.0)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MoveNameError {
+    Empty,
+}
+
+impl Display for MoveNameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "a move name cannot be empty"),
+        }
+    }
+}
+
+impl TryFrom<String> for MoveName {
+    type Error = MoveNameError;
+
+    fn try_from(n: String) -> Result<Self, Self::Error> {
+        let n = n.trim();
+        if n.is_empty() {
+            Err(MoveNameError::Empty)
+        } else {
+            Ok(Self(n.to_string()))
+        }
+    }
+}
+
+impl From<MoveName> for String {
+    fn from(n: MoveName) -> String {
+        n.0
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MoveCategory {
+    Physical,
+    Special,
+    Status,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MoveCategoryError {
+    Unknown(String),
+}
+
+impl Display for MoveCategoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(c) => write!(
+                f,
+                "unknown move category \"{}\", expected physical, special or status",
+                c
+            ),
+        }
+    }
+}
+
+impl TryFrom<String> for MoveCategory {
+    type Error = MoveCategoryError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "physical" => Ok(Self::Physical),
+            "special" => Ok(Self::Special),
+            "status" => Ok(Self::Status),
+            _ => Err(MoveCategoryError::Unknown(s)),
+        }
+    }
+}
+
+impl From<MoveCategory> for String {
+    fn from(category: MoveCategory) -> String {
+        match category {
+            MoveCategory::Physical => "Physical".to_string(),
+            MoveCategory::Special => "Special".to_string(),
+            MoveCategory::Status => "Status".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Move {
+    pub name: MoveName,
+    pub move_type: PokemonType,
+    pub category: MoveCategory,
+    pub power: Option<u8>,
+    pub accuracy: Option<u8>,
+    pub pp: u8,
+    pub priority: i8,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MoveError {
+    StatusPower(u8),
+    Power(u8),
+    Accuracy(u8),
+    Pp(u8),
+    Priority(i8),
+}
+
+impl Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::StatusPower(p) => write!(f, "status moves have no power, got {}", p),
+            Self::Power(p) => write!(f, "power must be at least 1, got {}", p),
+            Self::Accuracy(a) => write!(f, "accuracy must be between 1 and 100, got {}", a),
+            Self::Pp(pp) => write!(f, "PP must be between 1 and 64, got {}", pp),
+            Self::Priority(p) => write!(f, "priority must be between -7 and 5, got {}", p),
+        }
+    }
+}
+
+impl Move {
+    // Status moves deal no direct damage, and a move without accuracy never misses.
+    pub fn new(
+        name: MoveName,
+        move_type: PokemonType,
+        category: MoveCategory,
+        power: Option<u8>,
+        accuracy: Option<u8>,
+        pp: u8,
+        priority: i8,
+    ) -> Result<Self, MoveError> {
+        match (category, power) {
+            (MoveCategory::Status, Some(p)) => return Err(MoveError::StatusPower(p)),
+            (_, Some(0)) => return Err(MoveError::Power(0)),
+            _ => {}
+        }
+        if let Some(a) = accuracy.filter(|a| !(1..=100).contains(a)) {
+            return Err(MoveError::Accuracy(a));
+        }
+        if !(1..=64).contains(&pp) {
+            return Err(MoveError::Pp(pp));
+        }
+        if !(-7..=5).contains(&priority) {
+            return Err(MoveError::Priority(priority));
+        }
+
+        Ok(Self {
+            name,
+            move_type,
+            category,
+            power,
+            accuracy,
+            pp,
+            priority,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LearnMethod {
+    LevelUp(u8),
+    Tm,
+    Egg,
+    Tutor,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum LearnMethodError {
+    Unknown(String),
+    Level(Option<u16>),
+    UnexpectedLevel(String),
+}
+
+impl Display for LearnMethodError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(m) => write!(
+                f,
+                "unknown learn method \"{}\", expected level-up, tm, egg or tutor",
+                m
+            ),
+            Self::Level(Some(l)) => write!(f, "level must be between 1 and 100, got {}", l),
+            Self::Level(None) => write!(f, "level-up needs a level"),
+            Self::UnexpectedLevel(m) => write!(f, "{} takes no level", m),
+        }
+    }
+}
+
+impl LearnMethod {
+    pub fn new(method: String, level: Option<u16>) -> Result<Self, LearnMethodError> {
+        let method = method.trim().to_ascii_lowercase();
+        match (method.as_str(), level) {
+            ("level-up", Some(level)) if (1..=100).contains(&level) => {
+                Ok(Self::LevelUp(level as u8))
+            }
+            ("level-up", level) => Err(LearnMethodError::Level(level)),
+            ("tm" | "egg" | "tutor", Some(_)) => Err(LearnMethodError::UnexpectedLevel(method)),
+            ("tm", None) => Ok(Self::Tm),
+            ("egg", None) => Ok(Self::Egg),
+            ("tutor", None) => Ok(Self::Tutor),
+            _ => Err(LearnMethodError::Unknown(method)),
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::LevelUp(_) => "level-up",
+            Self::Tm => "tm",
+            Self::Egg => "egg",
+            Self::Tutor => "tutor",
+        }
+    }
+
+    pub fn level(&self) -> Option<u16> {
+        match self {
+            Self::LevelUp(level) => Some(u16::from(*level)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LearnsetEntry {
+    pub number: PokemonNumber,
+    pub move_name: MoveName,
+    pub method: LearnMethod,
+}
+
+impl LearnsetEntry {
+    pub fn new(number: PokemonNumber, move_name: MoveName, method: LearnMethod) -> Self {
+        Self {
+            number,
+            move_name,
+            method,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum EvolutionTrigger {
+    Level(u8),
+    Item(String),
+    Trade(Option<String>),
+    Friendship,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum EvolutionTriggerError {
+    Unknown(String),
+    Level(Option<u16>),
+    MissingItem,
+    Unexpected(String, &'static str),
+}
+
+impl Display for EvolutionTriggerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(t) => write!(
+                f,
+                "unknown trigger \"{}\", expected level, item, trade or friendship",
+                t
+            ),
+            Self::Level(Some(l)) => write!(f, "level must be between 1 and 100, got {}", l),
+            Self::Level(None) => write!(f, "a level trigger needs a level"),
+            Self::MissingItem => write!(f, "an item trigger needs an item"),
+            Self::Unexpected(t, field) => write!(f, "a {} trigger takes no {}", t, field),
+        }
+    }
+}
+
+impl EvolutionTrigger {
+    pub fn new(
+        trigger: String,
+        level: Option<u16>,
+        item: Option<String>,
+    ) -> Result<Self, EvolutionTriggerError> {
+        let item = item.filter(|item| !item.is_empty());
+        let trigger = trigger.to_ascii_lowercase();
+        match (trigger.as_str(), level, item) {
+            ("level", Some(level), None) if (1..=100).contains(&level) => {
+                Ok(Self::Level(level as u8))
+            }
+            ("level", level, None) => Err(EvolutionTriggerError::Level(level)),
+            ("item", None, Some(item)) => Ok(Self::Item(item)),
+            ("item", None, None) => Err(EvolutionTriggerError::MissingItem),
+            ("trade", None, item) => Ok(Self::Trade(item)),
+            ("friendship", None, None) => Ok(Self::Friendship),
+            ("level" | "friendship", _, Some(_)) => {
+                Err(EvolutionTriggerError::Unexpected(trigger, "item"))
+            }
+            ("item" | "trade" | "friendship", Some(_), _) => {
+                Err(EvolutionTriggerError::Unexpected(trigger, "level"))
+            }
+            _ => Err(EvolutionTriggerError::Unknown(trigger)),
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Level(_) => "level",
+            Self::Item(_) => "item",
+            Self::Trade(_) => "trade",
+            Self::Friendship => "friendship",
+        }
+    }
+
+    pub fn level(&self) -> Option<u16> {
+        match self {
+            Self::Level(level) => Some(u16::from(*level)),
+            _ => None,
+        }
+    }
+
+    pub fn item(&self) -> Option<String> {
+        match self {
+            Self::Item(item) => Some(item.clone()),
+            Self::Trade(item) => item.clone(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Evolution {
+    pub from: PokemonNumber,
+    pub to: PokemonNumber,
+    pub trigger: EvolutionTrigger,
+    pub condition: Option<String>,
+}
+
+impl Evolution {
+    pub fn new(
+        from: PokemonNumber,
+        to: PokemonNumber,
+        trigger: EvolutionTrigger,
+        condition: Option<String>,
+    ) -> Self {
+        Self {
+            from,
+            to,
+            trigger,
+            condition: condition.filter(|c| !c.is_empty()),
         }
     }
 }
@@ -181,6 +1273,28 @@ impl PokemonName {
     }
 }
 
+#[cfg(test)]
+impl BaseStats {
+    pub fn pikachu() -> Self {
+        Self::try_from([35, 55, 40, 50, 50, 90]).unwrap()
+    }
+
+    pub fn charmander() -> Self {
+        Self::try_from([39, 52, 43, 60, 50, 65]).unwrap()
+    }
+}
+
+#[cfg(test)]
+impl PokemonAbilities {
+    pub fn pikachu() -> Self {
+        Self::try_from((
+            vec![String::from("Static")],
+            Some(String::from("Lightning Rod")),
+        ))
+        .unwrap()
+    }
+}
+
 #[cfg(test)]
 impl PokemonTypes {
     pub fn pikachu() -> Self {
@@ -191,3 +1305,358 @@ impl PokemonTypes {
         Self(vec![PokemonType::Fire])
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_derive_generation_and_region_from_the_number() {
+        let era = |n| {
+            let number = PokemonNumber(n);
+            (number.generation().map(u8::from), number.region())
+        };
+        assert_eq!(era(1), (Some(1), Some(Region::Kanto)));
+        assert_eq!(era(151), (Some(1), Some(Region::Kanto)));
+        assert_eq!(era(152), (Some(2), Some(Region::Johto)));
+        assert_eq!(era(386), (Some(3), Some(Region::Hoenn)));
+        assert_eq!(era(809), (Some(7), Some(Region::Alola)));
+        assert_eq!(era(898), (Some(8), Some(Region::Galar)));
+        assert_eq!(era(899), (Some(8), Some(Region::Hisui)));
+        assert_eq!(era(906), (Some(9), Some(Region::Paldea)));
+        assert_eq!(era(1025), (Some(9), Some(Region::Paldea)));
+        assert_eq!(era(1026), (None, None));
+    }
+
+    #[test]
+    fn it_should_split_names_into_padded_trigrams() {
+        let trigrams = PokemonName(String::from("Mew")).trigrams();
+        assert_eq!(
+            trigrams.into_iter().collect::<Vec<_>>(),
+            vec!["  m", " me", "ew ", "mew"]
+        );
+    }
+
+    #[test]
+    fn it_should_list_the_numbers_of_a_generation() {
+        let numbers = |g| Generation::try_from(g).unwrap().numbers();
+        assert_eq!(numbers(1), 1..=151);
+        assert_eq!(numbers(7), 722..=809);
+        assert_eq!(numbers(8), 810..=905);
+        assert_eq!(numbers(9), 906..=1025);
+    }
+
+    #[test]
+    fn it_should_accept_numbers_within_the_default_range() {
+        assert!(PokemonNumber::try_from(1).is_ok());
+        assert!(PokemonNumber::try_from(1025).is_ok());
+        assert_eq!(
+            PokemonNumber::try_from(0),
+            Err(PokemonNumberError::OutOfRange(0))
+        );
+    }
+
+    #[test]
+    fn it_should_reject_an_invalid_range_configuration() {
+        assert_eq!(
+            PokemonNumber::configure_range(0..=151),
+            Err(DexRangeError(0, 151))
+        );
+        #[allow(clippy::reversed_empty_ranges)]
+        let empty = 151..=1;
+        assert_eq!(
+            PokemonNumber::configure_range(empty),
+            Err(DexRangeError(151, 1))
+        );
+    }
+
+    #[test]
+    fn it_should_normalize_form_identifiers() {
+        let form = |f: &str| PokemonForm::try_from(String::from(f));
+        assert_eq!(form("Alola"), Ok(PokemonForm(String::from("alola"))));
+        assert_eq!(form("mega-x"), Ok(PokemonForm(String::from("mega-x"))));
+        assert!(form("").unwrap().is_default());
+        assert!(form("Default").unwrap().is_default());
+        assert!(PokemonForm::try_from(None).unwrap().is_default());
+        assert_eq!(
+            form("alola form"),
+            Err(PokemonFormError::InvalidCharacters(String::from(
+                "alola form"
+            )))
+        );
+        assert_eq!(Option::<String>::from(PokemonForm::default()), None);
+    }
+
+    #[test]
+    fn it_should_parse_every_canonical_type_name() {
+        for t in PokemonType::ALL {
+            assert_eq!(PokemonType::try_from(String::from(t.as_str())), Ok(t));
+        }
+    }
+
+    #[test]
+    fn it_should_parse_type_names_case_insensitively() {
+        assert_eq!(
+            PokemonType::try_from(String::from("grass")),
+            Ok(PokemonType::Grass)
+        );
+        assert_eq!(
+            PokemonType::try_from(String::from("POISON")),
+            Ok(PokemonType::Poison)
+        );
+        assert_eq!(
+            PokemonType::try_from(String::from(" fAiRy ")),
+            Ok(PokemonType::Fairy)
+        );
+    }
+
+    #[test]
+    fn it_should_reject_an_unknown_type_name() {
+        assert_eq!(
+            PokemonType::try_from(String::from("Fyre")),
+            Err(PokemonTypeError::Unknown(String::from("Fyre")))
+        );
+        assert_eq!(
+            PokemonType::try_from(String::from("")),
+            Err(PokemonTypeError::Unknown(String::new()))
+        );
+    }
+
+    #[test]
+    fn it_should_name_what_is_wrong_with_other_value_objects() {
+        assert_eq!(
+            Generation::try_from(10).map(u8::from),
+            Err(GenerationError::OutOfRange(10))
+        );
+        assert_eq!(
+            AbilityName::try_from(String::from("  ")).map(String::from),
+            Err(AbilityNameError::Empty)
+        );
+        assert_eq!(
+            MoveName::try_from(String::new()).map(String::from),
+            Err(MoveNameError::Empty)
+        );
+        assert_eq!(
+            MoveCategory::try_from(String::from("Special")),
+            Ok(MoveCategory::Special)
+        );
+        assert_eq!(
+            MoveCategory::try_from(String::from("magic")),
+            Err(MoveCategoryError::Unknown(String::from("magic")))
+        );
+    }
+
+    #[test]
+    fn it_should_keep_primary_and_secondary_type_order() {
+        let types =
+            PokemonTypes::try_from(vec![String::from("Poison"), String::from("Grass")]).unwrap();
+        assert_eq!(types.primary(), PokemonType::Poison);
+        assert_eq!(types.secondary(), Some(PokemonType::Grass));
+    }
+
+    #[test]
+    fn it_should_reject_invalid_type_lists() {
+        let types = |ts: &[&str]| {
+            PokemonTypes::try_from(ts.iter().map(|t| t.to_string()).collect::<Vec<_>>())
+        };
+        assert_eq!(types(&[]).unwrap_err(), PokemonTypesError::Empty);
+        assert_eq!(
+            types(&["Fire", "Fire", "Fire"]).unwrap_err(),
+            PokemonTypesError::TooMany(3)
+        );
+        assert_eq!(
+            types(&["Fire", "fire"]).unwrap_err(),
+            PokemonTypesError::Duplicate(PokemonType::Fire)
+        );
+        assert_eq!(
+            types(&["Fyre"]).unwrap_err(),
+            PokemonTypesError::UnknownType(String::from("Fyre"))
+        );
+    }
+
+    #[test]
+    fn it_should_accept_base_stats_between_1_and_255() {
+        let stats = BaseStats::try_from([1, 255, 40, 50, 50, 90]).unwrap();
+        assert_eq!(stats.hp(), 1);
+        assert_eq!(stats.attack(), 255);
+        assert_eq!(stats.total(), 486);
+        assert_eq!(<[u16; 6]>::from(stats), [1, 255, 40, 50, 50, 90]);
+    }
+
+    #[test]
+    fn it_should_reject_base_stats_out_of_range() {
+        assert_eq!(
+            BaseStats::try_from([0, 55, 40, 50, 50, 90]),
+            Err(BaseStatsError::OutOfRange(vec![("hp", 0)]))
+        );
+        assert_eq!(
+            BaseStats::try_from([0, 55, 40, 0, 50, 256]),
+            Err(BaseStatsError::OutOfRange(vec![
+                ("hp", 0),
+                ("special_attack", 0),
+                ("speed", 256)
+            ]))
+        );
+    }
+
+    #[test]
+    fn it_should_build_evolution_triggers() {
+        let trigger = |t: &str, level, item: Option<&str>| {
+            EvolutionTrigger::new(t.to_string(), level, item.map(String::from))
+        };
+        assert_eq!(
+            trigger("level", Some(16), None),
+            Ok(EvolutionTrigger::Level(16))
+        );
+        assert_eq!(
+            trigger("Item", None, Some("Water Stone")),
+            Ok(EvolutionTrigger::Item(String::from("Water Stone")))
+        );
+        assert_eq!(
+            trigger("trade", None, Some("Metal Coat")),
+            Ok(EvolutionTrigger::Trade(Some(String::from("Metal Coat"))))
+        );
+        assert_eq!(
+            trigger("friendship", None, None),
+            Ok(EvolutionTrigger::Friendship)
+        );
+        assert_eq!(
+            trigger("level", None, None),
+            Err(EvolutionTriggerError::Level(None))
+        );
+        assert_eq!(
+            trigger("level", Some(101), None),
+            Err(EvolutionTriggerError::Level(Some(101)))
+        );
+        assert_eq!(
+            trigger("item", None, Some("")),
+            Err(EvolutionTriggerError::MissingItem)
+        );
+        assert_eq!(
+            trigger("friendship", Some(5), None),
+            Err(EvolutionTriggerError::Unexpected(
+                String::from("friendship"),
+                "level"
+            ))
+        );
+        assert_eq!(
+            trigger("moon", None, None),
+            Err(EvolutionTriggerError::Unknown(String::from("moon")))
+        );
+    }
+
+    #[test]
+    fn it_should_accept_up_to_two_regular_abilities_and_a_hidden_one() {
+        let abilities = PokemonAbilities::try_from((
+            vec![String::from("Blaze"), String::from("Solar Power")],
+            Some(String::from("Tough Claws")),
+        ))
+        .unwrap();
+        assert_eq!(abilities.regular().len(), 2);
+        assert!(abilities.contains(&AbilityName::try_from(String::from("tough claws")).unwrap()));
+        assert!(PokemonAbilities::try_from((vec![], None)).is_ok());
+    }
+
+    #[test]
+    fn it_should_name_the_field_of_a_validation_error() {
+        let error = ValidationError::Types(PokemonTypesError::UnknownType(String::from("Fyre")));
+        assert_eq!(error.field(), "types");
+        assert_eq!(error.to_string(), "unknown type \"Fyre\"");
+        let error = ValidationError::Name(PokemonNameError::Empty);
+        assert_eq!(error.field(), "name");
+    }
+
+    #[test]
+    fn it_should_reject_invalid_ability_slots() {
+        let abilities = |regular: &[&str], hidden: Option<&str>| {
+            PokemonAbilities::try_from((
+                regular.iter().map(|a| a.to_string()).collect::<Vec<_>>(),
+                hidden.map(String::from),
+            ))
+        };
+        assert_eq!(
+            abilities(&["Blaze", "Solar Power", "Drought"], None).unwrap_err(),
+            PokemonAbilitiesError::TooMany(3)
+        );
+        assert_eq!(
+            abilities(&["Blaze"], Some("blaze")).unwrap_err(),
+            PokemonAbilitiesError::Duplicate(String::from("blaze"))
+        );
+        assert_eq!(
+            abilities(&[""], None).unwrap_err(),
+            PokemonAbilitiesError::EmptyName
+        );
+    }
+
+    #[test]
+    fn it_should_validate_moves() {
+        let new = |category, power, accuracy, pp, priority| {
+            Move::new(
+                MoveName::try_from(String::from("Thunderbolt")).unwrap(),
+                PokemonType::Electric,
+                category,
+                power,
+                accuracy,
+                pp,
+                priority,
+            )
+        };
+        assert!(new(MoveCategory::Special, Some(90), Some(100), 15, 0).is_ok());
+        assert!(new(MoveCategory::Status, None, None, 20, 0).is_ok());
+        let error = |m: Result<Move, MoveError>| m.err();
+        assert_eq!(
+            error(new(MoveCategory::Status, Some(40), Some(100), 20, 0)),
+            Some(MoveError::StatusPower(40))
+        );
+        assert_eq!(
+            error(new(MoveCategory::Physical, Some(0), Some(100), 20, 0)),
+            Some(MoveError::Power(0))
+        );
+        assert_eq!(
+            error(new(MoveCategory::Physical, Some(40), Some(101), 20, 0)),
+            Some(MoveError::Accuracy(101))
+        );
+        assert_eq!(
+            error(new(MoveCategory::Physical, Some(40), Some(100), 0, 0)),
+            Some(MoveError::Pp(0))
+        );
+        assert_eq!(
+            error(new(MoveCategory::Physical, Some(40), Some(100), 30, 6)),
+            Some(MoveError::Priority(6))
+        );
+    }
+
+    #[test]
+    fn it_should_build_learn_methods() {
+        assert_eq!(
+            LearnMethod::new(String::from("level-up"), Some(26)),
+            Ok(LearnMethod::LevelUp(26))
+        );
+        assert_eq!(
+            LearnMethod::new(String::from("TM"), None),
+            Ok(LearnMethod::Tm)
+        );
+        assert_eq!(
+            LearnMethod::new(String::from("level-up"), None),
+            Err(LearnMethodError::Level(None))
+        );
+        assert_eq!(
+            LearnMethod::new(String::from("egg"), Some(5)),
+            Err(LearnMethodError::UnexpectedLevel(String::from("egg")))
+        );
+        assert_eq!(
+            LearnMethod::new(String::from("event"), None),
+            Err(LearnMethodError::Unknown(String::from("event")))
+        );
+    }
+
+    #[test]
+    fn it_should_serialize_types_with_their_canonical_spelling() {
+        let types =
+            PokemonTypes::try_from(vec![String::from("grass"), String::from("POISON")]).unwrap();
+        assert_eq!(
+            Vec::<String>::from(types),
+            vec![String::from("Grass"), String::from("Poison")]
+        );
+    }
+}
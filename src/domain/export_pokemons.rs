@@ -0,0 +1,366 @@
+use std::fmt::{self, Display};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use super::create_pokemon;
+use super::entities::Pokemon;
+use crate::repositories::pokemon::{FetchAllError, Repository};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Format {
+    Csv,
+    Ndjson,
+    Json,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnknownFormat(pub String);
+
+impl Display for UnknownFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown format \"{}\", expected csv, ndjson or json",
+            self.0
+        )
+    }
+}
+
+impl TryFrom<&str> for Format {
+    type Error = UnknownFormat;
+
+    fn try_from(format: &str) -> Result<Self, Self::Error> {
+        match format.trim().to_ascii_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "ndjson" => Ok(Self::Ndjson),
+            "json" => Ok(Self::Json),
+            _ => Err(UnknownFormat(format.to_string())),
+        }
+    }
+}
+
+// A Pokémon as written to JSON and NDJSON, shaped like the body of `POST /`.
+#[derive(Serialize, Deserialize)]
+pub struct Record {
+    pub number: u16,
+    #[serde(default)]
+    pub form: Option<String>,
+    pub name: String,
+    pub types: Vec<String>,
+    #[serde(default)]
+    pub stats: Option<Stats>,
+    #[serde(default)]
+    pub abilities: Vec<String>,
+    #[serde(default)]
+    pub hidden_ability: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Stats {
+    hp: u16,
+    attack: u16,
+    defense: u16,
+    special_attack: u16,
+    special_defense: u16,
+    speed: u16,
+}
+
+// CSV has no nesting, so every list gets a fixed number of columns.
+#[derive(Serialize, Deserialize)]
+pub struct CsvRecord {
+    number: u16,
+    form: Option<String>,
+    name: String,
+    type1: String,
+    type2: Option<String>,
+    hp: Option<u16>,
+    attack: Option<u16>,
+    defense: Option<u16>,
+    special_attack: Option<u16>,
+    special_defense: Option<u16>,
+    speed: Option<u16>,
+    ability1: Option<String>,
+    ability2: Option<String>,
+    hidden_ability: Option<String>,
+}
+
+impl From<Pokemon> for Record {
+    fn from(pokemon: Pokemon) -> Self {
+        let (abilities, hidden_ability) = pokemon.abilities.into();
+        Self {
+            number: pokemon.number.into(),
+            form: pokemon.form.into(),
+            name: pokemon.name.into(),
+            types: Vec::<String>::from(pokemon.types),
+            stats: pokemon.stats.map(|stats| {
+                let [hp, attack, defense, special_attack, special_defense, speed] =
+                    <[u16; 6]>::from(stats);
+                Stats {
+                    hp,
+                    attack,
+                    defense,
+                    special_attack,
+                    special_defense,
+                    speed,
+                }
+            }),
+            abilities,
+            hidden_ability,
+        }
+    }
+}
+
+impl From<Record> for create_pokemon::Request {
+    fn from(record: Record) -> Self {
+        Self {
+            number: record.number,
+            form: record.form,
+            name: record.name,
+            types: record.types,
+            stats: record.stats.map(|stats| {
+                [
+                    stats.hp,
+                    stats.attack,
+                    stats.defense,
+                    stats.special_attack,
+                    stats.special_defense,
+                    stats.speed,
+                ]
+            }),
+            abilities: record.abilities,
+            hidden_ability: record.hidden_ability,
+        }
+    }
+}
+
+impl From<Record> for CsvRecord {
+    fn from(record: Record) -> Self {
+        let mut types = record.types.into_iter();
+        let mut abilities = record.abilities.into_iter();
+        let stats = record.stats.map(|stats| {
+            [
+                stats.hp,
+                stats.attack,
+                stats.defense,
+                stats.special_attack,
+                stats.special_defense,
+                stats.speed,
+            ]
+        });
+        let stat = |i: usize| stats.map(|stats| stats[i]);
+        Self {
+            number: record.number,
+            form: record.form,
+            name: record.name,
+            type1: types.next().unwrap_or_default(),
+            type2: types.next(),
+            hp: stat(0),
+            attack: stat(1),
+            defense: stat(2),
+            special_attack: stat(3),
+            special_defense: stat(4),
+            speed: stat(5),
+            ability1: abilities.next(),
+            ability2: abilities.next(),
+            hidden_ability: record.hidden_ability,
+        }
+    }
+}
+
+impl TryFrom<CsvRecord> for Record {
+    type Error = &'static str;
+
+    fn try_from(row: CsvRecord) -> Result<Self, Self::Error> {
+        let stats = match [
+            row.hp,
+            row.attack,
+            row.defense,
+            row.special_attack,
+            row.special_defense,
+            row.speed,
+        ] {
+            [Some(hp), Some(attack), Some(defense), Some(special_attack), Some(special_defense), Some(speed)] => {
+                Some(Stats {
+                    hp,
+                    attack,
+                    defense,
+                    special_attack,
+                    special_defense,
+                    speed,
+                })
+            }
+            [None, None, None, None, None, None] => None,
+            _ => return Err("stats must be either all set or all empty"),
+        };
+        Ok(Self {
+            number: row.number,
+            form: row.form,
+            name: row.name,
+            types: std::iter::once(row.type1).chain(row.type2).collect(),
+            stats,
+            abilities: row.ability1.into_iter().chain(row.ability2).collect(),
+            hidden_ability: row.hidden_ability,
+        })
+    }
+}
+
+pub enum Error {
+    BadRequest,
+    Unknown,
+}
+
+pub struct Request {
+    pub format: String,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<String, Error> {
+    let format = match Format::try_from(req.format.as_str()) {
+        Ok(format) => format,
+        _ => return Err(Error::BadRequest),
+    };
+    let records = match repo.fetch_all() {
+        Ok(pokemons) => pokemons.into_iter().map(Record::from),
+        Err(FetchAllError::Unknown) => return Err(Error::Unknown),
+    };
+
+    match format {
+        Format::Json => {
+            serde_json::to_string(&records.collect::<Vec<_>>()).map_err(|_| Error::Unknown)
+        }
+        Format::Ndjson => records
+            .map(|record| serde_json::to_string(&record).map(|line| line + "\n"))
+            .collect::<Result<String, _>>()
+            .map_err(|_| Error::Unknown),
+        Format::Csv => {
+            let mut writer = csv::Writer::from_writer(vec![]);
+            for record in records {
+                if writer.serialize(CsvRecord::from(record)).is_err() {
+                    return Err(Error::Unknown);
+                }
+            }
+            match writer.into_inner().map(String::from_utf8) {
+                Ok(Ok(csv)) => Ok(csv),
+                _ => Err(Error::Unknown),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        BaseStats, PokemonAbilities, PokemonForm, PokemonName, PokemonNumber, PokemonTypes,
+    };
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn repo() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            Some(BaseStats::pikachu()),
+            PokemonAbilities::pikachu(),
+        )
+        .ok()
+        .expect("pokemon to be inserted");
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonForm::default(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+            None,
+            PokemonAbilities::default(),
+        )
+        .ok()
+        .expect("pokemon to be inserted");
+        repo
+    }
+
+    fn export(format: &str) -> String {
+        let req = Request {
+            format: String::from(format),
+        };
+        match execute(repo(), req) {
+            Ok(data) => data,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_format_is_unknown() {
+        let req = Request {
+            format: String::from("xml"),
+        };
+
+        match execute(repo(), req) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_name_the_unknown_format() {
+        assert_eq!(Format::try_from(" NDJSON "), Ok(Format::Ndjson));
+        assert_eq!(
+            Format::try_from("xml"),
+            Err(UnknownFormat(String::from("xml")))
+        );
+    }
+
+    #[test]
+    fn it_should_export_one_csv_row_per_pokemon_after_a_header() {
+        let csv = export("CSV");
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("number,form,name,type1,type2,hp,"));
+        assert!(lines[1].starts_with("4,,Charmander,Fire,,,,,,,,,,"));
+        assert!(lines[2].starts_with("25,,Pikachu,Electric,,35,55,40,50,50,90,"));
+    }
+
+    #[test]
+    fn it_should_export_one_json_object_per_ndjson_line() {
+        let ndjson = export("ndjson");
+
+        assert_eq!(ndjson.lines().count(), 2);
+        for line in ndjson.lines() {
+            assert!(serde_json::from_str::<Record>(line).is_ok());
+        }
+    }
+
+    #[test]
+    fn it_should_export_a_json_array() {
+        let json = export("json");
+
+        match serde_json::from_str::<Vec<Record>>(&json) {
+            Ok(records) => assert_eq!(records.len(), 2),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_reject_csv_rows_with_partial_stats() {
+        let row = CsvRecord {
+            number: 25,
+            form: None,
+            name: String::from("Pikachu"),
+            type1: String::from("Electric"),
+            type2: None,
+            hp: Some(35),
+            attack: None,
+            defense: None,
+            special_attack: None,
+            special_defense: None,
+            speed: None,
+            ability1: None,
+            ability2: None,
+            hidden_ability: None,
+        };
+
+        assert!(Record::try_from(row).is_err());
+    }
+}
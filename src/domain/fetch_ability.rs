@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use crate::repositories::ability::{FetchError, Repository};
+
+use super::entities::AbilityName;
+
+pub enum Error {
+    Unknown,
+    BadRequest,
+    NotFound,
+}
+
+pub struct Request {
+    pub name: String,
+}
+
+pub struct Response {
+    pub name: String,
+    pub description: String,
+    pub generation: u8,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let name = match AbilityName::try_from(req.name) {
+        Ok(name) => name,
+        _ => return Err(Error::BadRequest),
+    };
+
+    match repo.fetch(name) {
+        Ok(ability) => Ok(Response {
+            name: ability.name.into(),
+            description: ability.description,
+            generation: ability.generation.into(),
+        }),
+        Err(FetchError::Unknown) => Err(Error::Unknown),
+        Err(FetchError::NotFound) => Err(Error::NotFound),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Ability, Generation};
+    use crate::repositories::ability::InMemoryRepository;
+
+    fn request(name: &str) -> Request {
+        Request {
+            name: String::from(name),
+        }
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        match execute(repo, request("Static")) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, request(" ")) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_repo_doesnot_contain_the_ability() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, request("Static")) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_the_ability_case_insensitively_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(Ability::new(
+            AbilityName::try_from(String::from("Static")).unwrap(),
+            String::from("May paralyze on contact."),
+            Generation::try_from(3).unwrap(),
+        ))
+        .ok()
+        .expect("ability to be inserted");
+
+        match execute(repo, request("static")) {
+            Ok(res) => {
+                assert_eq!(res.name, String::from("Static"));
+                assert_eq!(res.generation, 3);
+            }
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use crate::repositories::ability::{FetchAllError, Repository};
+
+pub enum Error {
+    Unknown,
+}
+
+pub struct Response {
+    pub name: String,
+    pub description: String,
+    pub generation: u8,
+}
+
+pub fn execute(repo: Arc<dyn Repository>) -> Result<Vec<Response>, Error> {
+    match repo.fetch_all() {
+        Ok(abilities) => Ok(abilities
+            .into_iter()
+            .map(|ability| Response {
+                name: ability.name.into(),
+                description: ability.description,
+                generation: ability.generation.into(),
+            })
+            .collect()),
+        Err(FetchAllError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Ability, AbilityName, Generation};
+    use crate::repositories::ability::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        match execute(repo) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_all_abilities_ordered_by_name_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        for name in ["Static", "Blaze", "Overgrow"] {
+            repo.insert(Ability::new(
+                AbilityName::try_from(String::from(name)).unwrap(),
+                String::new(),
+                Generation::try_from(3).unwrap(),
+            ))
+            .ok()
+            .expect("ability to be inserted");
+        }
+
+        match execute(repo) {
+            Ok(res) => assert_eq!(
+                res.into_iter().map(|a| a.name).collect::<Vec<_>>(),
+                vec![
+                    String::from("Blaze"),
+                    String::from("Overgrow"),
+                    String::from("Static")
+                ]
+            ),
+            _ => unreachable!(),
+        }
+    }
+}
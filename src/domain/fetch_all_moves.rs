@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use crate::repositories::moves::{FetchAllError, Repository};
+
+use super::create_move::Response;
+
+pub enum Error {
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>) -> Result<Vec<Response>, Error> {
+    match repo.fetch_all() {
+        Ok(moves) => Ok(moves.into_iter().map(Response::from).collect()),
+        Err(FetchAllError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Move, MoveCategory, MoveName, PokemonType};
+    use crate::repositories::moves::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        match execute(repo) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_all_moves_ordered_by_name_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        for name in ["Thunder Shock", "Quick Attack"] {
+            repo.insert(
+                Move::new(
+                    MoveName::try_from(String::from(name)).unwrap(),
+                    PokemonType::Normal,
+                    MoveCategory::Physical,
+                    Some(40),
+                    Some(100),
+                    30,
+                    0,
+                )
+                .unwrap(),
+            )
+            .ok()
+            .expect("move to be inserted");
+        }
+
+        match execute(repo) {
+            Ok(res) => assert_eq!(
+                res.into_iter().map(|m| m.name).collect::<Vec<_>>(),
+                vec![String::from("Quick Attack"), String::from("Thunder Shock")]
+            ),
+            _ => unreachable!(),
+        }
+    }
+}
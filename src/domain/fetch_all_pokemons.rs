@@ -1,28 +1,129 @@
 use std::sync::Arc;
 
-use crate::repositories::pokemon::{FetchAllError, Repository};
+use crate::repositories::pokemon::{FetchAllError, NameFilter, Query, Repository, SortKey};
+
+use super::entities::{Generation, PokemonType};
 
 pub enum Error {
     Unknown,
+    BadRequest(String),
+}
+
+#[derive(Default)]
+pub struct Request {
+    pub generation: Option<u8>,
+    pub pokemon_type: Option<String>,
+    pub name: Option<String>,
+    pub name_prefix: Option<String>,
+    pub min_number: Option<u16>,
+    pub max_number: Option<u16>,
+    pub sort: Option<String>,
+    pub order: Option<String>,
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
 }
 
 pub struct Response {
     pub name: String,
     pub number: u16,
+    pub form: Option<String>,
     pub types: Vec<String>,
+    pub stats: Option<[u16; 6]>,
+    pub abilities: Vec<String>,
+    pub hidden_ability: Option<String>,
+    pub generation: Option<u8>,
+    pub region: Option<String>,
+}
+
+pub struct Page {
+    pub pokemons: Vec<Response>,
+    pub total: usize,
 }
 
-pub fn execute(repo: Arc<dyn Repository>) -> Result<Vec<Response>, Error> {
-    let o = repo.fetch_all();
-    match o {
-        Ok(pokemons) => Ok(pokemons
-            .into_iter()
-            .map(|pokemon| Response {
-                name: pokemon.name.into(),
-                number: pokemon.number.into(),
-                types: Vec::<String>::from(pokemon.types),
-            })
-            .collect()),
+fn query(req: Request) -> Result<Query, String> {
+    let generation = req
+        .generation
+        .map(Generation::try_from)
+        .transpose()
+        .map_err(|e| e.to_string())?;
+    let pokemon_type = req
+        .pokemon_type
+        .map(PokemonType::try_from)
+        .transpose()
+        .map_err(|e| e.to_string())?;
+    let name = match (req.name_prefix, req.name) {
+        (Some(_), Some(_)) => return Err(String::from("name and name_prefix cannot be combined")),
+        (Some(prefix), None) => Some(NameFilter::Prefix(prefix)),
+        (None, Some(part)) => Some(NameFilter::Contains(part)),
+        (None, None) => None,
+    };
+
+    // The generation and the explicit bounds narrow down the same number range.
+    let (mut min, mut max) = (req.min_number, req.max_number);
+    if let Some(numbers) = generation.map(|g| g.numbers()) {
+        min = Some(min.map_or(*numbers.start(), |min| min.max(*numbers.start())));
+        max = Some(max.map_or(*numbers.end(), |max| max.min(*numbers.end())));
+    }
+    let numbers = match (min, max) {
+        (None, None) => None,
+        (min, max) => Some(min.unwrap_or(0)..=max.unwrap_or(u16::MAX)),
+    };
+
+    let sort = match req.sort.as_deref() {
+        None | Some("number") => SortKey::Number,
+        Some("name") => SortKey::Name,
+        Some(sort) => {
+            return Err(format!(
+                "cannot sort by \"{}\", expected number or name",
+                sort
+            ))
+        }
+    };
+    let descending = match req.order.as_deref() {
+        None | Some("asc") => false,
+        Some("desc") => true,
+        Some(order) => return Err(format!("unknown order \"{}\", expected asc or desc", order)),
+    };
+    if req.limit == Some(0) {
+        return Err(String::from("limit must be at least 1"));
+    }
+
+    Ok(Query {
+        pokemon_type,
+        name,
+        numbers,
+        sort,
+        descending,
+        offset: req.offset.unwrap_or(0),
+        limit: req.limit,
+    })
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Page, Error> {
+    let query = query(req).map_err(Error::BadRequest)?;
+
+    match repo.query(&query) {
+        Ok(page) => Ok(Page {
+            total: page.total,
+            pokemons: page
+                .pokemons
+                .into_iter()
+                .map(|pokemon| {
+                    let (abilities, hidden_ability) = pokemon.abilities.into();
+                    Response {
+                        generation: pokemon.number.generation().map(u8::from),
+                        region: pokemon.number.region().map(String::from),
+                        name: pokemon.name.into(),
+                        number: pokemon.number.into(),
+                        form: pokemon.form.into(),
+                        types: Vec::<String>::from(pokemon.types),
+                        stats: pokemon.stats.map(<[u16; 6]>::from),
+                        abilities,
+                        hidden_ability,
+                    }
+                })
+                .collect(),
+        }),
         Err(FetchAllError::Unknown) => Err(Error::Unknown),
     }
 }
@@ -30,13 +131,15 @@ pub fn execute(repo: Arc<dyn Repository>) -> Result<Vec<Response>, Error> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::entities::{PokemonName, PokemonNumber, PokemonTypes};
+    use crate::domain::entities::{
+        BaseStats, PokemonAbilities, PokemonForm, PokemonName, PokemonNumber, PokemonTypes,
+    };
     use crate::repositories::pokemon::InMemoryRepository;
 
     #[test]
     fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
         let repo = Arc::new(InMemoryRepository::new().with_error());
-        let res = execute(repo);
+        let res = execute(repo, Request::default());
 
         match res {
             Err(Error::Unknown) => {}
@@ -44,24 +147,85 @@ mod tests {
         }
     }
 
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_generation_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let res = execute(
+            repo,
+            Request {
+                generation: Some(0),
+                ..Request::default()
+            },
+        );
+
+        match res {
+            Err(Error::BadRequest(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_only_return_pokemons_of_the_requested_generation() {
+        let repo = Arc::new(InMemoryRepository::new());
+        for (n, name) in [(25, "Pikachu"), (172, "Pichu")] {
+            repo.insert(
+                PokemonNumber::try_from(n).unwrap(),
+                PokemonForm::default(),
+                PokemonName::try_from(String::from(name)).unwrap(),
+                PokemonTypes::pikachu(),
+                None,
+                PokemonAbilities::default(),
+            )
+            .ok()
+            .expect("pokemon to be inserted");
+        }
+        let res = execute(
+            repo,
+            Request {
+                generation: Some(2),
+                ..Request::default()
+            },
+        );
+
+        match res {
+            Ok(Page { pokemons: res, .. }) => {
+                assert_eq!(res.len(), 1);
+                assert_eq!(res[0].number, 172);
+                assert_eq!(res[0].generation, Some(2));
+                assert_eq!(res[0].region, Some(String::from("Johto")));
+            }
+            _ => unreachable!(),
+        }
+    }
+
     #[test]
     fn it_should_return_all_pokemons_ordered_by_number_otherwise() {
         let repo = Arc::new(InMemoryRepository::new());
 
         repo.insert(
             PokemonNumber::pikachu(),
+            PokemonForm::default(),
             PokemonName::pikachu(),
             PokemonTypes::pikachu(),
-        );
+            Some(BaseStats::pikachu()),
+            PokemonAbilities::default(),
+        )
+        .ok()
+        .expect("pokemon to be inserted");
         repo.insert(
             PokemonNumber::charmander(),
+            PokemonForm::default(),
             PokemonName::charmander(),
             PokemonTypes::charmander(),
-        );
-        let res = execute(repo);
+            Some(BaseStats::charmander()),
+            PokemonAbilities::default(),
+        )
+        .ok()
+        .expect("pokemon to be inserted");
+        let res = execute(repo, Request::default());
 
         match res {
-            Ok(res) => {
+            Ok(Page { pokemons: res, .. }) => {
                 assert_eq!(res[0].number, u16::from(PokemonNumber::charmander()));
                 assert_eq!(res[0].name, String::from(PokemonName::charmander()));
                 assert_eq!(
@@ -75,4 +239,94 @@ mod tests {
             _ => unreachable!(),
         }
     }
+
+    fn repo_with_starters() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        for (n, name, t) in [
+            (1, "Bulbasaur", "Grass"),
+            (4, "Charmander", "Fire"),
+            (5, "Charmeleon", "Fire"),
+            (6, "Charizard", "Fire"),
+            (155, "Cyndaquil", "Fire"),
+        ] {
+            repo.insert(
+                PokemonNumber::try_from(n).unwrap(),
+                PokemonForm::default(),
+                PokemonName::try_from(String::from(name)).unwrap(),
+                PokemonTypes::try_from(vec![String::from(t)]).unwrap(),
+                None,
+                PokemonAbilities::default(),
+            )
+            .ok()
+            .expect("pokemon to be inserted");
+        }
+        repo
+    }
+
+    fn numbers(res: Result<Page, Error>) -> (Vec<u16>, usize) {
+        match res {
+            Ok(page) => (
+                page.pokemons.into_iter().map(|p| p.number).collect(),
+                page.total,
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_filter_by_type_name_and_number_range() {
+        let repo = repo_with_starters();
+        let req = Request {
+            pokemon_type: Some(String::from("fire")),
+            name_prefix: Some(String::from("char")),
+            max_number: Some(5),
+            ..Request::default()
+        };
+        assert_eq!(numbers(execute(repo.clone(), req)), (vec![4, 5], 2));
+
+        let req = Request {
+            name: Some(String::from("QUIL")),
+            generation: Some(2),
+            ..Request::default()
+        };
+        assert_eq!(numbers(execute(repo, req)), (vec![155], 1));
+    }
+
+    #[test]
+    fn it_should_sort_and_paginate() {
+        let repo = repo_with_starters();
+        let req = Request {
+            sort: Some(String::from("name")),
+            order: Some(String::from("desc")),
+            offset: Some(1),
+            limit: Some(2),
+            ..Request::default()
+        };
+
+        assert_eq!(numbers(execute(repo, req)), (vec![5, 4], 5));
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_query_is_invalid() {
+        let repo = repo_with_starters();
+        for req in [
+            Request {
+                pokemon_type: Some(String::from("Shadow")),
+                ..Request::default()
+            },
+            Request {
+                sort: Some(String::from("weight")),
+                ..Request::default()
+            },
+            Request {
+                limit: Some(0),
+                ..Request::default()
+            },
+        ] {
+            match execute(repo.clone(), req) {
+                Err(Error::BadRequest(_)) => {}
+                _ => unreachable!(),
+            }
+        }
+    }
 }
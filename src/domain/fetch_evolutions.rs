@@ -0,0 +1,217 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::repositories::evolution::{self, FetchAllError};
+use crate::repositories::pokemon::{self, FetchError};
+
+use super::entities::{Evolution, Pokemon, PokemonForm, PokemonNumber};
+
+pub enum Error {
+    Unknown,
+    BadRequest,
+    NotFound,
+}
+
+pub struct Request {
+    pub number: u16,
+}
+
+pub struct Trigger {
+    pub trigger: String,
+    pub level: Option<u16>,
+    pub item: Option<String>,
+    pub condition: Option<String>,
+}
+
+pub struct Response {
+    pub number: u16,
+    pub name: String,
+    pub evolution: Option<Trigger>,
+    pub evolves_to: Vec<Response>,
+}
+
+pub fn execute(
+    pokemon_repo: Arc<dyn pokemon::Repository>,
+    evolution_repo: Arc<dyn evolution::Repository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let number = match PokemonNumber::try_from(req.number) {
+        Ok(number) => number,
+        _ => return Err(Error::BadRequest),
+    };
+
+    let mut root = match pokemon_repo.fetch(number, &PokemonForm::default()) {
+        Ok(pokemon) => pokemon,
+        Err(FetchError::NotFound) => return Err(Error::NotFound),
+        Err(FetchError::Unknown) => return Err(Error::Unknown),
+    };
+
+    let evolutions = match evolution_repo.fetch_all() {
+        Ok(evolutions) => evolutions,
+        Err(FetchAllError::Unknown) => return Err(Error::Unknown),
+    };
+
+    // The chain starts at the highest ancestor that still exists; the visited set guards
+    // against a loop in the recorded evolutions.
+    let mut visited = HashSet::from([u16::from(root.number.clone())]);
+    while let Some(pre_evolution) = evolutions.iter().find(|e| e.to == root.number) {
+        if !visited.insert(u16::from(pre_evolution.from.clone())) {
+            break;
+        }
+        match pokemon_repo.fetch(pre_evolution.from.clone(), &PokemonForm::default()) {
+            Ok(pokemon) => root = pokemon,
+            Err(FetchError::NotFound) => break,
+            Err(FetchError::Unknown) => return Err(Error::Unknown),
+        }
+    }
+
+    let mut visited = HashSet::from([u16::from(root.number.clone())]);
+    node(pokemon_repo.as_ref(), &evolutions, root, None, &mut visited)
+}
+
+fn node(
+    pokemon_repo: &dyn pokemon::Repository,
+    evolutions: &[Evolution],
+    pokemon: Pokemon,
+    evolution: Option<&Evolution>,
+    visited: &mut HashSet<u16>,
+) -> Result<Response, Error> {
+    let mut evolves_to = vec![];
+    for e in evolutions.iter().filter(|e| e.from == pokemon.number) {
+        if !visited.insert(u16::from(e.to.clone())) {
+            continue;
+        }
+        match pokemon_repo.fetch(e.to.clone(), &PokemonForm::default()) {
+            Ok(next) => evolves_to.push(node(pokemon_repo, evolutions, next, Some(e), visited)?),
+            // The evolved form was deleted since the evolution was recorded.
+            Err(FetchError::NotFound) => {}
+            Err(FetchError::Unknown) => return Err(Error::Unknown),
+        }
+    }
+    evolves_to.sort_by_key(|n| n.number);
+
+    Ok(Response {
+        number: pokemon.number.into(),
+        name: pokemon.name.into(),
+        evolution: evolution.map(|e| Trigger {
+            trigger: e.trigger.kind().to_string(),
+            level: e.trigger.level(),
+            item: e.trigger.item(),
+            condition: e.condition.clone(),
+        }),
+        evolves_to,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{EvolutionTrigger, PokemonAbilities, PokemonName, PokemonTypes};
+    use crate::repositories::evolution::Repository as _;
+    use crate::repositories::pokemon::Repository as _;
+
+    fn number(n: u16) -> PokemonNumber {
+        PokemonNumber::try_from(n).unwrap()
+    }
+
+    fn eevee_repos() -> (
+        Arc<pokemon::InMemoryRepository>,
+        Arc<evolution::InMemoryRepository>,
+    ) {
+        let pokemon_repo = Arc::new(pokemon::InMemoryRepository::new());
+        for (n, name) in [(133, "Eevee"), (134, "Vaporeon"), (135, "Jolteon")] {
+            pokemon_repo
+                .insert(
+                    number(n),
+                    PokemonForm::default(),
+                    PokemonName::try_from(String::from(name)).unwrap(),
+                    PokemonTypes::pikachu(),
+                    None,
+                    PokemonAbilities::default(),
+                )
+                .ok()
+                .expect("pokemon to be inserted");
+        }
+        let evolution_repo = Arc::new(evolution::InMemoryRepository::new());
+        for (to, item) in [(135, "Thunder Stone"), (134, "Water Stone")] {
+            evolution_repo
+                .insert(Evolution::new(
+                    number(133),
+                    number(to),
+                    EvolutionTrigger::Item(String::from(item)),
+                    None,
+                ))
+                .ok()
+                .expect("evolution to be inserted");
+        }
+        (pokemon_repo, evolution_repo)
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let (pokemon_repo, _) = eevee_repos();
+        let evolution_repo = Arc::new(evolution::InMemoryRepository::new().with_error());
+
+        match execute(pokemon_repo, evolution_repo, Request { number: 133 }) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let (pokemon_repo, evolution_repo) = eevee_repos();
+
+        match execute(pokemon_repo, evolution_repo, Request { number: 0 }) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_repo_doesnot_contain_the_pokemon() {
+        let (pokemon_repo, evolution_repo) = eevee_repos();
+
+        match execute(pokemon_repo, evolution_repo, Request { number: 25 }) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_start_the_chain_at_the_highest_ancestor_that_still_exists() {
+        let (pokemon_repo, evolution_repo) = eevee_repos();
+        pokemon_repo
+            .delete(number(133), &PokemonForm::default(), None, 0)
+            .ok()
+            .expect("pokemon to be deleted");
+
+        match execute(pokemon_repo, evolution_repo, Request { number: 134 }) {
+            Ok(res) => {
+                assert_eq!(res.number, 134);
+                assert!(res.evolves_to.is_empty());
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_the_whole_chain_from_any_member() {
+        let (pokemon_repo, evolution_repo) = eevee_repos();
+
+        match execute(pokemon_repo, evolution_repo, Request { number: 134 }) {
+            Ok(res) => {
+                assert_eq!(res.number, 133);
+                assert!(res.evolution.is_none());
+                assert_eq!(
+                    res.evolves_to.iter().map(|n| n.number).collect::<Vec<_>>(),
+                    vec![134, 135]
+                );
+                let vaporeon = res.evolves_to[0].evolution.as_ref().unwrap();
+                assert_eq!(vaporeon.trigger, String::from("item"));
+                assert_eq!(vaporeon.item, Some(String::from("Water Stone")));
+            }
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use crate::repositories::pokemon::{FetchError, Repository};
+
+use super::entities::PokemonNumber;
+use super::fetch_pokemon::Response;
+
+pub enum Error {
+    Unknown,
+    BadRequest,
+    NotFound,
+}
+
+pub struct Request {
+    pub number: u16,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Vec<Response>, Error> {
+    let number = match PokemonNumber::try_from(req.number) {
+        Ok(number) => number,
+        _ => return Err(Error::BadRequest),
+    };
+
+    match repo.fetch_forms(number) {
+        Ok(pokemons) => Ok(pokemons.into_iter().map(Response::from).collect()),
+        Err(FetchError::Unknown) => Err(Error::Unknown),
+        Err(FetchError::NotFound) => Err(Error::NotFound),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonAbilities, PokemonForm, PokemonName, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn insert_raichu(repo: &InMemoryRepository, form: &str) {
+        repo.insert(
+            PokemonNumber::try_from(26).unwrap(),
+            PokemonForm::try_from(String::from(form)).unwrap(),
+            PokemonName::try_from(String::from("Raichu")).unwrap(),
+            PokemonTypes::try_from(vec![String::from("Electric")]).unwrap(),
+            None,
+            PokemonAbilities::default(),
+        )
+        .ok()
+        .expect("pokemon to be inserted");
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_number_has_no_forms() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, Request { number: 26 }) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_every_form_with_the_default_first() {
+        let repo = Arc::new(InMemoryRepository::new());
+        insert_raichu(&repo, "alola");
+        insert_raichu(&repo, "");
+
+        match execute(repo, Request { number: 26 }) {
+            Ok(res) => assert_eq!(
+                res.into_iter().map(|p| p.form).collect::<Vec<_>>(),
+                vec![None, Some(String::from("alola"))]
+            ),
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use std::sync::Arc;
+
+use crate::repositories::{learnset, moves, pokemon};
+
+use super::entities::{PokemonForm, PokemonNumber};
+
+pub enum Error {
+    Unknown,
+    BadRequest(String),
+    NotFound,
+}
+
+pub struct Request {
+    pub number: u16,
+    pub method: Option<String>,
+}
+
+pub struct Response {
+    pub name: String,
+    pub move_type: String,
+    pub category: String,
+    pub power: Option<u8>,
+    pub accuracy: Option<u8>,
+    pub pp: u8,
+    pub priority: i8,
+    pub method: String,
+    pub level: Option<u16>,
+}
+
+const METHODS: [&str; 4] = ["level-up", "tm", "egg", "tutor"];
+
+pub fn execute(
+    pokemon_repo: Arc<dyn pokemon::Repository>,
+    move_repo: Arc<dyn moves::Repository>,
+    learnset_repo: Arc<dyn learnset::Repository>,
+    req: Request,
+) -> Result<Vec<Response>, Error> {
+    let number =
+        PokemonNumber::try_from(req.number).map_err(|e| Error::BadRequest(e.to_string()))?;
+    let method = req.method.map(|m| m.trim().to_ascii_lowercase());
+    if let Some(method) = &method {
+        if !METHODS.contains(&method.as_str()) {
+            return Err(Error::BadRequest(format!(
+                "unknown method \"{}\", expected one of {}",
+                method,
+                METHODS.join(", ")
+            )));
+        }
+    }
+
+    match pokemon_repo.fetch(number.clone(), &PokemonForm::default()) {
+        Ok(_) => {}
+        Err(pokemon::FetchError::NotFound) => return Err(Error::NotFound),
+        Err(pokemon::FetchError::Unknown) => return Err(Error::Unknown),
+    }
+
+    let entries = match learnset_repo.fetch(number) {
+        Ok(entries) => entries,
+        Err(learnset::FetchError::Unknown) => return Err(Error::Unknown),
+    };
+
+    let mut res = vec![];
+    for entry in entries
+        .into_iter()
+        .filter(|e| method.as_deref().is_none_or(|m| e.method.kind() == m))
+    {
+        let m = match move_repo.fetch(entry.move_name) {
+            Ok(m) => m,
+            // The move was removed from the catalogue after being learned.
+            Err(moves::FetchError::NotFound) => continue,
+            Err(moves::FetchError::Unknown) => return Err(Error::Unknown),
+        };
+        res.push(Response {
+            name: m.name.into(),
+            move_type: m.move_type.into(),
+            category: m.category.into(),
+            power: m.power,
+            accuracy: m.accuracy,
+            pp: m.pp,
+            priority: m.priority,
+            method: entry.method.kind().to_string(),
+            level: entry.method.level(),
+        });
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        LearnMethod, LearnsetEntry, Move, MoveCategory, MoveName, PokemonAbilities, PokemonName,
+        PokemonType, PokemonTypes,
+    };
+    use crate::repositories::learnset::Repository as _;
+    use crate::repositories::moves::Repository as _;
+    use crate::repositories::pokemon::Repository as _;
+
+    type Repos = (
+        Arc<pokemon::InMemoryRepository>,
+        Arc<moves::InMemoryRepository>,
+        Arc<learnset::InMemoryRepository>,
+    );
+
+    fn repos() -> Repos {
+        let pokemon_repo = Arc::new(pokemon::InMemoryRepository::new());
+        pokemon_repo
+            .insert(
+                PokemonNumber::pikachu(),
+                PokemonForm::default(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+                None,
+                PokemonAbilities::default(),
+            )
+            .ok()
+            .expect("pokemon to be inserted");
+        let move_repo = Arc::new(moves::InMemoryRepository::new());
+        let learnset_repo = Arc::new(learnset::InMemoryRepository::new());
+        for (name, method) in [
+            ("Thunderbolt", LearnMethod::Tm),
+            ("Thunder Shock", LearnMethod::LevelUp(1)),
+            ("Quick Attack", LearnMethod::LevelUp(1)),
+            ("Thunder", LearnMethod::LevelUp(30)),
+        ] {
+            let name = MoveName::try_from(String::from(name)).unwrap();
+            move_repo
+                .insert(
+                    Move::new(
+                        name.clone(),
+                        PokemonType::Electric,
+                        MoveCategory::Special,
+                        Some(90),
+                        Some(100),
+                        15,
+                        0,
+                    )
+                    .unwrap(),
+                )
+                .ok()
+                .expect("move to be inserted");
+            learnset_repo
+                .insert(LearnsetEntry::new(PokemonNumber::pikachu(), name, method))
+                .ok()
+                .expect("entry to be inserted");
+        }
+        (pokemon_repo, move_repo, learnset_repo)
+    }
+
+    fn request(method: Option<&str>) -> Request {
+        Request {
+            number: PokemonNumber::pikachu().into(),
+            method: method.map(String::from),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_method_is_unknown() {
+        let (pokemon_repo, move_repo, learnset_repo) = repos();
+
+        match execute(
+            pokemon_repo,
+            move_repo,
+            learnset_repo,
+            request(Some("event")),
+        ) {
+            Err(Error::BadRequest(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_repo_doesnot_contain_the_pokemon() {
+        let (_, move_repo, learnset_repo) = repos();
+        let pokemon_repo = Arc::new(pokemon::InMemoryRepository::new());
+
+        match execute(pokemon_repo, move_repo, learnset_repo, request(None)) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let (pokemon_repo, move_repo, _) = repos();
+        let learnset_repo = Arc::new(learnset::InMemoryRepository::new().with_error());
+
+        match execute(pokemon_repo, move_repo, learnset_repo, request(None)) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_the_filtered_learnset_ordered_by_level_otherwise() {
+        let (pokemon_repo, move_repo, learnset_repo) = repos();
+
+        match execute(
+            pokemon_repo,
+            move_repo,
+            learnset_repo,
+            request(Some("Level-Up")),
+        ) {
+            Ok(res) => {
+                assert_eq!(
+                    res.iter().map(|m| m.name.clone()).collect::<Vec<_>>(),
+                    vec![
+                        String::from("Thunder Shock"),
+                        String::from("Quick Attack"),
+                        String::from("Thunder")
+                    ]
+                );
+                assert_eq!(res[2].level, Some(30));
+            }
+            _ => unreachable!(),
+        }
+    }
+}
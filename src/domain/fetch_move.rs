@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use crate::repositories::moves::{FetchError, Repository};
+
+use super::create_move::Response;
+use super::entities::MoveName;
+
+pub enum Error {
+    Unknown,
+    BadRequest,
+    NotFound,
+}
+
+pub struct Request {
+    pub name: String,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let name = match MoveName::try_from(req.name) {
+        Ok(name) => name,
+        _ => return Err(Error::BadRequest),
+    };
+
+    match repo.fetch(name) {
+        Ok(m) => Ok(m.into()),
+        Err(FetchError::Unknown) => Err(Error::Unknown),
+        Err(FetchError::NotFound) => Err(Error::NotFound),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::moves::InMemoryRepository;
+
+    fn request(name: &str) -> Request {
+        Request {
+            name: String::from(name),
+        }
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        match execute(repo, request("Thunderbolt")) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, request("")) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_repo_doesnot_contain_the_move() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, request("Thunderbolt")) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
+}
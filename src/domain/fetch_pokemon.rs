@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use crate::repositories::pokemon::{FetchError, Repository};
 
-use super::entities::PokemonNumber;
+use super::entities::{Pokemon, PokemonForm, PokemonNumber};
 
 pub enum Error {
     Unknown,
@@ -12,27 +12,52 @@ pub enum Error {
 
 pub struct Request {
     pub number: u16,
+    pub form: Option<String>,
 }
 
 pub struct Response {
     pub name: String,
     pub number: u16,
+    pub form: Option<String>,
     pub types: Vec<String>,
+    pub stats: Option<[u16; 6]>,
+    pub abilities: Vec<String>,
+    pub hidden_ability: Option<String>,
+    pub generation: Option<u8>,
+    pub region: Option<String>,
+    pub version: u64,
+}
+
+impl From<Pokemon> for Response {
+    fn from(pokemon: Pokemon) -> Self {
+        let (abilities, hidden_ability) = pokemon.abilities.into();
+        Self {
+            generation: pokemon.number.generation().map(u8::from),
+            region: pokemon.number.region().map(String::from),
+            name: pokemon.name.into(),
+            number: pokemon.number.into(),
+            form: pokemon.form.into(),
+            types: Vec::<String>::from(pokemon.types),
+            stats: pokemon.stats.map(<[u16; 6]>::from),
+            abilities,
+            hidden_ability,
+            version: pokemon.version,
+        }
+    }
 }
 
 pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
-    let pokemon_number = match PokemonNumber::try_from(req.number) {
-        Ok(pokemon_number) => pokemon_number,
+    let (pokemon_number, form) = match (
+        PokemonNumber::try_from(req.number),
+        PokemonForm::try_from(req.form),
+    ) {
+        (Ok(pokemon_number), Ok(form)) => (pokemon_number, form),
         _ => return Err(Error::BadRequest),
     };
 
-    let res = repo.fetch(pokemon_number);
+    let res = repo.fetch(pokemon_number, &form);
     match res {
-        Ok(pokemon) => Ok(Response {
-            name: pokemon.name.into(),
-            number: pokemon.number.into(),
-            types: Vec::<String>::from(pokemon.types),
-        }),
+        Ok(pokemon) => Ok(pokemon.into()),
         Err(FetchError::Unknown) => Err(Error::Unknown),
         Err(FetchError::NotFound) => Err(Error::NotFound),
     }
@@ -41,7 +66,9 @@ pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Erro
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::entities::{PokemonName, PokemonNumber, PokemonTypes};
+    use crate::domain::entities::{
+        BaseStats, PokemonAbilities, PokemonForm, PokemonName, PokemonNumber, PokemonTypes,
+    };
     use crate::repositories::pokemon::InMemoryRepository;
 
     #[test]
@@ -49,6 +76,7 @@ mod tests {
         let repo = Arc::new(InMemoryRepository::new().with_error());
         let req = Request {
             number: PokemonNumber::pikachu().into(),
+            form: None,
         };
         let res = execute(repo, req);
 
@@ -63,6 +91,7 @@ mod tests {
         let repo = Arc::new(InMemoryRepository::new());
         let req = Request {
             number: PokemonNumber::bad().into(),
+            form: None,
         };
         let res = execute(repo, req);
 
@@ -77,13 +106,17 @@ mod tests {
         let repo = Arc::new(InMemoryRepository::new());
         repo.insert(
             PokemonNumber::pikachu(),
+            PokemonForm::default(),
             PokemonName::pikachu(),
             PokemonTypes::pikachu(),
+            Some(BaseStats::pikachu()),
+            PokemonAbilities::default(),
         )
         .ok()
         .expect("pokemon to be inserted");
         let req = Request {
             number: PokemonNumber::charmander().into(),
+            form: None,
         };
         let res = execute(repo, req);
 
@@ -97,13 +130,17 @@ mod tests {
         let repo = Arc::new(InMemoryRepository::new());
         repo.insert(
             PokemonNumber::pikachu(),
+            PokemonForm::default(),
             PokemonName::pikachu(),
             PokemonTypes::pikachu(),
+            Some(BaseStats::pikachu()),
+            PokemonAbilities::default(),
         )
         .ok()
         .expect("pokemon to be inserted");
         let req = Request {
             number: PokemonNumber::pikachu().into(),
+            form: None,
         };
         let res = execute(repo, req);
 
@@ -112,12 +149,66 @@ mod tests {
                 number,
                 name,
                 types,
+                stats,
+                generation,
+                region,
+                ..
             }) => {
                 assert_eq!(number, u16::from(PokemonNumber::pikachu()));
                 assert_eq!(name, String::from(PokemonName::pikachu()));
                 assert_eq!(types, Vec::<String>::from(PokemonTypes::pikachu()));
+                assert_eq!(stats, Some(<[u16; 6]>::from(BaseStats::pikachu())));
+                assert_eq!(generation, Some(1));
+                assert_eq!(region, Some(String::from("Kanto")));
             }
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn it_should_return_the_requested_form() {
+        let repo = Arc::new(InMemoryRepository::new());
+        for (form, types) in [
+            ("", vec!["Electric"]),
+            ("alola", vec!["Electric", "Psychic"]),
+        ] {
+            repo.insert(
+                PokemonNumber::try_from(26).unwrap(),
+                PokemonForm::try_from(String::from(form)).unwrap(),
+                PokemonName::try_from(String::from("Raichu")).unwrap(),
+                PokemonTypes::try_from(types.into_iter().map(String::from).collect::<Vec<_>>())
+                    .unwrap(),
+                None,
+                PokemonAbilities::default(),
+            )
+            .ok()
+            .expect("pokemon to be inserted");
+        }
+
+        let req = |form: Option<&str>| Request {
+            number: 26,
+            form: form.map(String::from),
+        };
+        match execute(repo.clone(), req(None)) {
+            Ok(Response { form, types, .. }) => {
+                assert_eq!(form, None);
+                assert_eq!(types, vec![String::from("Electric")]);
+            }
+            _ => unreachable!(),
+        }
+        match execute(repo.clone(), req(Some("Alola"))) {
+            Ok(Response { form, types, .. }) => {
+                assert_eq!(form, Some(String::from("alola")));
+                assert_eq!(
+                    types,
+                    vec![String::from("Electric"), String::from("Psychic")]
+                );
+            }
+            _ => unreachable!(),
+        }
+        match execute(repo, req(Some("galar"))) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
 }
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use crate::repositories::pokemon::{FetchError, Repository};
+
+use super::entities::PokemonName;
+use super::fetch_pokemon::{Error, Response};
+
+pub struct Request {
+    pub name: String,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let name = match PokemonName::try_from(req.name) {
+        Ok(name) => name,
+        _ => return Err(Error::BadRequest),
+    };
+
+    match repo.fetch_by_name(&name) {
+        Ok(pokemon) => Ok(pokemon.into()),
+        Err(FetchError::Unknown) => Err(Error::Unknown),
+        Err(FetchError::NotFound) => Err(Error::NotFound),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonAbilities, PokemonForm, PokemonNumber, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn repo() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            None,
+            PokemonAbilities::default(),
+        )
+        .ok()
+        .expect("pokemon to be inserted");
+        repo
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_name_is_empty() {
+        match execute(
+            repo(),
+            Request {
+                name: String::new(),
+            },
+        ) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_no_pokemon_has_the_name() {
+        let req = Request {
+            name: String::from("Charmander"),
+        };
+
+        match execute(repo(), req) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_find_the_pokemon_ignoring_case_otherwise() {
+        let req = Request {
+            name: String::from("pikaCHU"),
+        };
+
+        match execute(repo(), req) {
+            Ok(Response { number, name, .. }) => {
+                assert_eq!(number, u16::from(PokemonNumber::pikachu()));
+                assert_eq!(name, String::from(PokemonName::pikachu()));
+            }
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use crate::repositories::pokemon::{FetchAllError, Repository, Tombstone};
+
+pub enum Error {
+    Unknown,
+}
+
+pub struct Response {
+    pub number: u16,
+    pub form: Option<String>,
+    pub name: String,
+    pub types: Vec<String>,
+    pub deleted_at: u64,
+}
+
+impl From<Tombstone> for Response {
+    fn from(
+        Tombstone {
+            pokemon,
+            deleted_at,
+        }: Tombstone,
+    ) -> Self {
+        Self {
+            number: pokemon.number.into(),
+            form: pokemon.form.into(),
+            name: pokemon.name.into(),
+            types: Vec::<String>::from(pokemon.types),
+            deleted_at,
+        }
+    }
+}
+
+pub fn execute(repo: Arc<dyn Repository>) -> Result<Vec<Response>, Error> {
+    match repo.fetch_trash() {
+        Ok(tombstones) => Ok(tombstones.into_iter().map(Response::from).collect()),
+        Err(FetchAllError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        BaseStats, PokemonAbilities, PokemonForm, PokemonName, PokemonNumber, PokemonTypes,
+    };
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        match execute(repo) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_the_deleted_pokemons_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            Some(BaseStats::pikachu()),
+            PokemonAbilities::pikachu(),
+        )
+        .ok()
+        .expect("pokemon to be inserted");
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonForm::default(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+            Some(BaseStats::charmander()),
+            PokemonAbilities::default(),
+        )
+        .ok()
+        .expect("pokemon to be inserted");
+        repo.delete(
+            PokemonNumber::pikachu(),
+            &PokemonForm::default(),
+            None,
+            1_700_000_000,
+        )
+        .ok()
+        .expect("pokemon to be deleted");
+
+        match execute(repo) {
+            Ok(res) => {
+                assert_eq!(res.len(), 1);
+                assert_eq!(res[0].number, u16::from(PokemonNumber::pikachu()));
+                assert_eq!(res[0].name, String::from(PokemonName::pikachu()));
+                assert_eq!(res[0].deleted_at, 1_700_000_000);
+            }
+            _ => unreachable!(),
+        }
+    }
+}
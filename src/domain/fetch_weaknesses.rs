@@ -0,0 +1,156 @@
+use std::sync::Arc;
+
+use crate::repositories::pokemon::{FetchError, Repository};
+
+use super::entities::{PokemonForm, PokemonNumber, PokemonType};
+use super::type_chart;
+
+pub enum Error {
+    Unknown,
+    BadRequest,
+    NotFound,
+}
+
+pub struct Request {
+    pub number: u16,
+}
+
+pub struct Matchup {
+    pub attacking_type: String,
+    pub multiplier: f32,
+}
+
+pub struct Response {
+    pub number: u16,
+    pub name: String,
+    pub types: Vec<String>,
+    pub weaknesses: Vec<Matchup>,
+    pub resistances: Vec<Matchup>,
+    pub immunities: Vec<Matchup>,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let pokemon_number = match PokemonNumber::try_from(req.number) {
+        Ok(pokemon_number) => pokemon_number,
+        _ => return Err(Error::BadRequest),
+    };
+
+    let pokemon = match repo.fetch(pokemon_number, &PokemonForm::default()) {
+        Ok(pokemon) => pokemon,
+        Err(FetchError::Unknown) => return Err(Error::Unknown),
+        Err(FetchError::NotFound) => return Err(Error::NotFound),
+    };
+
+    let mut weaknesses = vec![];
+    let mut resistances = vec![];
+    let mut immunities = vec![];
+    for attacking_type in PokemonType::ALL {
+        let multiplier = type_chart::multiplier(attacking_type, &pokemon.types);
+        let matchup = Matchup {
+            attacking_type: attacking_type.into(),
+            multiplier,
+        };
+        if multiplier == 0.0 {
+            immunities.push(matchup);
+        } else if multiplier < 1.0 {
+            resistances.push(matchup);
+        } else if multiplier > 1.0 {
+            weaknesses.push(matchup);
+        }
+    }
+
+    Ok(Response {
+        number: pokemon.number.into(),
+        name: pokemon.name.into(),
+        types: Vec::<String>::from(pokemon.types),
+        weaknesses,
+        resistances,
+        immunities,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{BaseStats, PokemonAbilities, PokemonName, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request {
+            number: PokemonNumber::pikachu().into(),
+        };
+
+        match execute(repo, req) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            number: PokemonNumber::bad().into(),
+        };
+
+        match execute(repo, req) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_repo_doesnot_contain_the_pokemon() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            number: PokemonNumber::pikachu().into(),
+        };
+
+        match execute(repo, req) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_the_type_matchups_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            Some(BaseStats::pikachu()),
+            PokemonAbilities::default(),
+        )
+        .ok()
+        .expect("pokemon to be inserted");
+        let req = Request {
+            number: PokemonNumber::pikachu().into(),
+        };
+
+        match execute(repo, req) {
+            Ok(res) => {
+                let names = |ms: &[Matchup]| {
+                    ms.iter()
+                        .map(|m| m.attacking_type.clone())
+                        .collect::<Vec<_>>()
+                };
+                assert_eq!(names(&res.weaknesses), vec![String::from("Ground")]);
+                assert_eq!(
+                    names(&res.resistances),
+                    vec![
+                        String::from("Electric"),
+                        String::from("Flying"),
+                        String::from("Steel")
+                    ]
+                );
+                assert!(res.immunities.is_empty());
+                assert!(res.resistances.iter().all(|m| m.multiplier == 0.5));
+            }
+            _ => unreachable!(),
+        }
+    }
+}
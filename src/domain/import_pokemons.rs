@@ -0,0 +1,309 @@
+use std::sync::Arc;
+
+use serde_json::value::RawValue;
+
+use super::bulk_create_pokemons::{self, ItemError};
+use super::create_pokemon;
+use super::export_pokemons::{CsvRecord, Format, Record};
+use crate::repositories::ability;
+use crate::repositories::pokemon::Repository;
+
+pub struct Request {
+    pub format: String,
+    pub data: String,
+    pub continue_on_error: bool,
+}
+
+pub struct Rejection {
+    pub line: usize,
+    pub error: ItemError,
+}
+
+pub struct Response {
+    pub imported: usize,
+    pub rejected: Vec<Rejection>,
+}
+
+pub enum Error {
+    BadRequest,
+    // A malformed file is refused as a whole, whatever the mode.
+    Parse(usize, String),
+    // The row starting at this line was rejected, so nothing was imported.
+    Rejected(usize, ItemError),
+    Unknown,
+}
+
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    ability_repo: Arc<dyn ability::Repository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let format = match Format::try_from(req.format.as_str()) {
+        Ok(format) => format,
+        _ => return Err(Error::BadRequest),
+    };
+    let rows = match format {
+        Format::Csv => parse_csv(&req.data),
+        Format::Ndjson => parse_ndjson(&req.data),
+        Format::Json => parse_json(&req.data),
+    }
+    .map_err(|(line, message)| Error::Parse(line, message))?;
+
+    let (lines, pokemons): (Vec<usize>, Vec<create_pokemon::Request>) = rows
+        .into_iter()
+        .map(|(line, record)| (line, record.into()))
+        .unzip();
+    let req = bulk_create_pokemons::Request {
+        pokemons,
+        continue_on_error: req.continue_on_error,
+    };
+    match bulk_create_pokemons::execute(repo, ability_repo, req) {
+        Ok(results) => {
+            let mut res = Response {
+                imported: 0,
+                rejected: vec![],
+            };
+            for (line, result) in lines.into_iter().zip(results) {
+                match result {
+                    Ok(_) => res.imported += 1,
+                    Err(error) => res.rejected.push(Rejection { line, error }),
+                }
+            }
+            Ok(res)
+        }
+        Err(bulk_create_pokemons::Error::Rejected(i, e)) => Err(Error::Rejected(lines[i], e)),
+        Err(bulk_create_pokemons::Error::Unknown) => Err(Error::Unknown),
+    }
+}
+
+// serde_json appends the position to its messages; ours are positioned by line already.
+fn message(e: &serde_json::Error) -> String {
+    let message = e.to_string();
+    match message.rsplit_once(" at line ") {
+        Some((message, _)) => message.to_string(),
+        None => message,
+    }
+}
+
+fn parse_csv(data: &str) -> Result<Vec<(usize, Record)>, (usize, String)> {
+    let mut reader = csv::Reader::from_reader(data.as_bytes());
+    let headers = reader.headers().map_err(|e| (1, e.to_string()))?.clone();
+
+    let mut rows = vec![];
+    for row in reader.records() {
+        let row = row.map_err(|e| {
+            let line = e.position().map_or(0, |position| position.line());
+            (line as usize, e.to_string())
+        })?;
+        let line = row.position().map_or(0, |position| position.line()) as usize;
+        let record = row
+            .deserialize::<CsvRecord>(Some(&headers))
+            .map_err(|e| (line, e.to_string()))?;
+        rows.push((
+            line,
+            Record::try_from(record).map_err(|e| (line, e.to_string()))?,
+        ));
+    }
+    Ok(rows)
+}
+
+fn parse_ndjson(data: &str) -> Result<Vec<(usize, Record)>, (usize, String)> {
+    data.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| match serde_json::from_str(line) {
+            Ok(record) => Ok((i + 1, record)),
+            Err(e) => Err((i + 1, message(&e))),
+        })
+        .collect()
+}
+
+// Parses the array lazily so that every record can be traced back to the line it starts on.
+fn parse_json(data: &str) -> Result<Vec<(usize, Record)>, (usize, String)> {
+    let items: Vec<&RawValue> = serde_json::from_str(data).map_err(|e| (e.line(), message(&e)))?;
+
+    items
+        .into_iter()
+        .map(|item| {
+            let offset = item.get().as_ptr() as usize - data.as_ptr() as usize;
+            let line = data[..offset].matches('\n').count() + 1;
+            match serde_json::from_str(item.get()) {
+                Ok(record) => Ok((line, record)),
+                Err(e) => Err((line + e.line().saturating_sub(1), message(&e))),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Ability, AbilityName, Generation, PokemonTypesError};
+    use crate::domain::export_pokemons;
+    use crate::repositories::ability::Repository as _;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    const HEADER: &str = "number,form,name,type1,type2,hp,attack,defense,special_attack,special_defense,speed,ability1,ability2,hidden_ability";
+
+    fn ability_repo() -> Arc<ability::InMemoryRepository> {
+        let repo = Arc::new(ability::InMemoryRepository::new());
+        for name in ["Static", "Lightning Rod"] {
+            repo.insert(Ability::new(
+                AbilityName::try_from(String::from(name)).unwrap(),
+                String::new(),
+                Generation::try_from(3).unwrap(),
+            ))
+            .ok()
+            .expect("ability to be inserted");
+        }
+        repo
+    }
+
+    fn import(
+        repo: Arc<InMemoryRepository>,
+        format: &str,
+        data: &str,
+        continue_on_error: bool,
+    ) -> Result<Response, Error> {
+        let req = Request {
+            format: String::from(format),
+            data: String::from(data),
+            continue_on_error,
+        };
+        execute(repo, ability_repo(), req)
+    }
+
+    #[test]
+    fn it_should_import_csv_rows() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let csv = format!(
+            "{}\n25,,Pikachu,Electric,,35,55,40,50,50,90,Static,,Lightning Rod\n4,,Charmander,Fire,,,,,,,,,,\n",
+            HEADER
+        );
+
+        match import(repo.clone(), "csv", &csv, false) {
+            Ok(res) => {
+                assert_eq!(res.imported, 2);
+                assert!(res.rejected.is_empty());
+            }
+            _ => unreachable!(),
+        }
+        match repo.fetch_all() {
+            Ok(pokemons) => assert_eq!(pokemons.len(), 2),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_import_what_was_exported_in_every_format() {
+        let source = Arc::new(InMemoryRepository::new());
+        import(
+            source.clone(),
+            "csv",
+            &format!(
+                "{}\n25,alola,Pikachu,Electric,Psychic,35,55,40,50,50,90,Static,,Lightning Rod\n",
+                HEADER
+            ),
+            false,
+        )
+        .ok()
+        .expect("pokemon to be imported");
+
+        for format in ["csv", "ndjson", "json"] {
+            let data = match export_pokemons::execute(
+                source.clone(),
+                export_pokemons::Request {
+                    format: String::from(format),
+                },
+            ) {
+                Ok(data) => data,
+                _ => unreachable!(),
+            };
+            let target = Arc::new(InMemoryRepository::new());
+            import(target.clone(), format, &data, false)
+                .ok()
+                .expect("pokemon to be imported");
+
+            match target.fetch_all() {
+                Ok(pokemons) => {
+                    assert_eq!(pokemons.len(), 1);
+                    assert_eq!(pokemons[0].form.to_string(), "alola");
+                    assert_eq!(pokemons[0].abilities.regular().len(), 1);
+                    assert!(pokemons[0].stats.is_some());
+                }
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_report_the_line_of_a_rejected_row() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let csv = format!(
+            "{}\n25,,Pikachu,Electric,,,,,,,,,,\n4,,Charmander,Fyre,,,,,,,,,,\n",
+            HEADER
+        );
+
+        match import(repo.clone(), "csv", &csv, false) {
+            Err(Error::Rejected(3, ItemError::Invalid(errors))) => {
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].field(), "types");
+            }
+            _ => unreachable!(),
+        }
+        match repo.fetch_all() {
+            Ok(pokemons) => assert!(pokemons.is_empty()),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_collect_rejected_rows_when_continuing_on_error() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let ndjson = "{\"number\":25,\"name\":\"Pikachu\",\"types\":[\"Electric\"]}\n\n{\"number\":4,\"name\":\"Charmander\",\"types\":[\"Fyre\"]}\n";
+
+        match import(repo, "ndjson", ndjson, true) {
+            Ok(res) => {
+                assert_eq!(res.imported, 1);
+                assert_eq!(res.rejected.len(), 1);
+                assert_eq!(res.rejected[0].line, 3);
+                match &res.rejected[0].error {
+                    ItemError::Invalid(errors) => assert_eq!(
+                        errors[0].to_string(),
+                        PokemonTypesError::UnknownType(String::from("Fyre")).to_string()
+                    ),
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_report_the_line_of_a_malformed_record() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let json = "[\n  {\"number\": 25, \"name\": \"Pikachu\", \"types\": [\"Electric\"]},\n  {\"number\": \"four\", \"name\": \"Charmander\", \"types\": [\"Fire\"]}\n]";
+
+        match import(repo.clone(), "json", json, false) {
+            Err(Error::Parse(line, _)) => assert_eq!(line, 3),
+            _ => unreachable!(),
+        }
+        match import(
+            repo,
+            "csv",
+            &format!("{}\nfour,,Charmander,Fire", HEADER),
+            false,
+        ) {
+            Err(Error::Parse(line, _)) => assert_eq!(line, 2),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_format_is_unknown() {
+        match import(Arc::new(InMemoryRepository::new()), "yaml", "", false) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,333 @@
+use core::fmt;
+use std::fmt::Display;
+
+use super::entities::BaseStats;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Nature {
+    Hardy,
+    Lonely,
+    Brave,
+    Adamant,
+    Naughty,
+    Bold,
+    Docile,
+    Relaxed,
+    Impish,
+    Lax,
+    Timid,
+    Hasty,
+    Serious,
+    Jolly,
+    Naive,
+    Modest,
+    Mild,
+    Quiet,
+    Bashful,
+    Rash,
+    Calm,
+    Gentle,
+    Sassy,
+    Careful,
+    Quirky,
+}
+
+impl Nature {
+    // Natures are laid out as a 5x5 grid: the row is the raised stat and the
+    // column the lowered one, both in Atk, Def, Spe, Sp. Atk, Sp. Def order.
+    pub const ALL: [Nature; 25] = [
+        Self::Hardy,
+        Self::Lonely,
+        Self::Brave,
+        Self::Adamant,
+        Self::Naughty,
+        Self::Bold,
+        Self::Docile,
+        Self::Relaxed,
+        Self::Impish,
+        Self::Lax,
+        Self::Timid,
+        Self::Hasty,
+        Self::Serious,
+        Self::Jolly,
+        Self::Naive,
+        Self::Modest,
+        Self::Mild,
+        Self::Quiet,
+        Self::Bashful,
+        Self::Rash,
+        Self::Calm,
+        Self::Gentle,
+        Self::Sassy,
+        Self::Careful,
+        Self::Quirky,
+    ];
+
+    // Indices into the usual HP, Atk, Def, Sp. Atk, Sp. Def, Speed order.
+    const GRID: [usize; 5] = [1, 2, 5, 3, 4];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Hardy => "Hardy",
+            Self::Lonely => "Lonely",
+            Self::Brave => "Brave",
+            Self::Adamant => "Adamant",
+            Self::Naughty => "Naughty",
+            Self::Bold => "Bold",
+            Self::Docile => "Docile",
+            Self::Relaxed => "Relaxed",
+            Self::Impish => "Impish",
+            Self::Lax => "Lax",
+            Self::Timid => "Timid",
+            Self::Hasty => "Hasty",
+            Self::Serious => "Serious",
+            Self::Jolly => "Jolly",
+            Self::Naive => "Naive",
+            Self::Modest => "Modest",
+            Self::Mild => "Mild",
+            Self::Quiet => "Quiet",
+            Self::Bashful => "Bashful",
+            Self::Rash => "Rash",
+            Self::Calm => "Calm",
+            Self::Gentle => "Gentle",
+            Self::Sassy => "Sassy",
+            Self::Careful => "Careful",
+            Self::Quirky => "Quirky",
+        }
+    }
+
+    /// Multiplier, in percent, applied to the stat at `index`.
+    fn modifier(&self, index: usize) -> u32 {
+        let position = Self::ALL.iter().position(|n| n == self).unwrap_or(0);
+        let raised = Self::GRID[position / 5];
+        let lowered = Self::GRID[position % 5];
+        if raised == lowered {
+            100
+        } else if index == raised {
+            110
+        } else if index == lowered {
+            90
+        } else {
+            100
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum NatureError {
+    Unknown(String),
+}
+
+impl Display for NatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(n) => write!(f, "unknown nature \"{}\"", n),
+        }
+    }
+}
+
+impl TryFrom<String> for Nature {
+    type Error = NatureError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        let trimmed = s.trim();
+        Self::ALL
+            .into_iter()
+            .find(|n| n.as_str().eq_ignore_ascii_case(trimmed))
+            .ok_or(NatureError::Unknown(s))
+    }
+}
+
+impl From<Nature> for String {
+    fn from(nature: Nature) -> String {
+        nature.as_str().to_string()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum IndividualError {
+    Level(u16),
+    Iv(u16),
+    Ev(u16),
+    EvTotal(u16),
+    Nature(String),
+}
+
+impl IndividualError {
+    pub fn field(&self) -> &'static str {
+        match self {
+            Self::Level(_) => "level",
+            Self::Iv(_) => "ivs",
+            Self::Ev(_) | Self::EvTotal(_) => "evs",
+            Self::Nature(_) => "nature",
+        }
+    }
+}
+
+impl Display for IndividualError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Level(l) => write!(f, "level must be between 1 and 100, got {}", l),
+            Self::Iv(iv) => write!(f, "IVs must be between 0 and 31, got {}", iv),
+            Self::Ev(ev) => write!(f, "EVs must be between 0 and 252, got {}", ev),
+            Self::EvTotal(total) => write!(f, "EVs must total at most 510, got {}", total),
+            Self::Nature(n) => write!(f, "unknown nature \"{}\"", n),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Individual {
+    level: u16,
+    ivs: [u16; 6],
+    evs: [u16; 6],
+    nature: Nature,
+}
+
+impl Individual {
+    pub const MAX_LEVEL: u16 = 100;
+    pub const MAX_IV: u16 = 31;
+    pub const MAX_EV: u16 = 252;
+    pub const MAX_EV_TOTAL: u16 = 510;
+
+    pub fn new(
+        level: u16,
+        ivs: [u16; 6],
+        evs: [u16; 6],
+        nature: String,
+    ) -> Result<Self, IndividualError> {
+        if !(1..=Self::MAX_LEVEL).contains(&level) {
+            return Err(IndividualError::Level(level));
+        }
+        if let Some(iv) = ivs.iter().find(|iv| **iv > Self::MAX_IV) {
+            return Err(IndividualError::Iv(*iv));
+        }
+        if let Some(ev) = evs.iter().find(|ev| **ev > Self::MAX_EV) {
+            return Err(IndividualError::Ev(*ev));
+        }
+        let total = evs.iter().sum();
+        if total > Self::MAX_EV_TOTAL {
+            return Err(IndividualError::EvTotal(total));
+        }
+        let nature = match Nature::try_from(nature.clone()) {
+            Ok(nature) => nature,
+            _ => return Err(IndividualError::Nature(nature)),
+        };
+
+        Ok(Self {
+            level,
+            ivs,
+            evs,
+            nature,
+        })
+    }
+
+    pub fn level(&self) -> u16 {
+        self.level
+    }
+
+    pub fn nature(&self) -> Nature {
+        self.nature
+    }
+
+    /// Actual stats of this individual, in HP, Atk, Def, Sp. Atk, Sp. Def, Speed order.
+    pub fn stats(&self, base: &BaseStats) -> [u16; 6] {
+        let base = <[u16; 6]>::from(*base);
+        let level = u32::from(self.level);
+        let mut stats = [0; 6];
+        for (i, stat) in stats.iter_mut().enumerate() {
+            let b = u32::from(base[i]);
+            let iv = u32::from(self.ivs[i]);
+            let ev = u32::from(self.evs[i]);
+            let raw = (2 * b + iv + ev / 4) * level / 100;
+            *stat = if i == 0 {
+                // Shedinja, the only species with a base HP of 1, always has 1 HP.
+                if b == 1 {
+                    1
+                } else {
+                    raw + level + 10
+                }
+            } else {
+                (raw + 5) * self.nature.modifier(i) / 100
+            } as u16;
+        }
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_compute_stats_with_the_official_formulas() {
+        let garchomp = BaseStats::try_from([108, 130, 95, 80, 85, 102]).unwrap();
+        let individual = Individual::new(
+            78,
+            [24, 12, 30, 16, 23, 5],
+            [74, 190, 91, 48, 84, 23],
+            String::from("Adamant"),
+        )
+        .unwrap();
+
+        assert_eq!(individual.stats(&garchomp), [289, 278, 193, 135, 171, 171]);
+    }
+
+    #[test]
+    fn it_should_give_shedinja_one_hp() {
+        let shedinja = BaseStats::try_from([1, 90, 45, 30, 30, 40]).unwrap();
+        let individual =
+            Individual::new(100, [31; 6], [0, 252, 0, 0, 0, 252], String::from("Hardy")).unwrap();
+
+        assert_eq!(individual.stats(&shedinja)[0], 1);
+    }
+
+    #[test]
+    fn it_should_apply_nature_modifiers() {
+        assert_eq!(Nature::Adamant.modifier(1), 110);
+        assert_eq!(Nature::Adamant.modifier(3), 90);
+        assert_eq!(Nature::Timid.modifier(5), 110);
+        assert_eq!(Nature::Timid.modifier(1), 90);
+        assert_eq!(Nature::Serious.modifier(5), 100);
+        assert_eq!(Nature::Adamant.modifier(0), 100);
+    }
+
+    #[test]
+    fn it_should_parse_natures_case_insensitively() {
+        assert_eq!(Nature::try_from(String::from("jolly")), Ok(Nature::Jolly));
+        assert_eq!(
+            Nature::try_from(String::from("Grumpy")),
+            Err(NatureError::Unknown(String::from("Grumpy")))
+        );
+    }
+
+    #[test]
+    fn it_should_reject_invalid_individuals() {
+        let new = |level, ivs, evs| Individual::new(level, ivs, evs, String::from("Hardy"));
+
+        assert_eq!(
+            new(0, [0; 6], [0; 6]).unwrap_err(),
+            IndividualError::Level(0)
+        );
+        assert_eq!(
+            new(101, [0; 6], [0; 6]).unwrap_err(),
+            IndividualError::Level(101)
+        );
+        assert_eq!(
+            new(50, [32, 0, 0, 0, 0, 0], [0; 6]).unwrap_err(),
+            IndividualError::Iv(32)
+        );
+        assert_eq!(
+            new(50, [0; 6], [253, 0, 0, 0, 0, 0]).unwrap_err(),
+            IndividualError::Ev(253)
+        );
+        assert_eq!(
+            new(50, [0; 6], [252, 252, 8, 0, 0, 0]).unwrap_err(),
+            IndividualError::EvTotal(512)
+        );
+        assert_eq!(
+            Individual::new(50, [0; 6], [0; 6], String::from("Grumpy")).unwrap_err(),
+            IndividualError::Nature(String::from("Grumpy"))
+        );
+    }
+}
@@ -1,5 +1,31 @@
+pub mod bulk_create_pokemons;
+pub mod calculate_stats;
+pub mod create_ability;
+pub mod create_evolution;
+pub mod create_learnset_entry;
+pub mod create_move;
 pub mod create_pokemon;
+pub mod delete_ability;
 pub mod delete_pokemon;
 pub mod entities;
+pub mod export_pokemons;
+pub mod fetch_ability;
+pub mod fetch_all_abilities;
+pub mod fetch_all_moves;
 pub mod fetch_all_pokemons;
+pub mod fetch_evolutions;
+pub mod fetch_forms;
+pub mod fetch_learnset;
+pub mod fetch_move;
 pub mod fetch_pokemon;
+pub mod fetch_pokemon_by_name;
+pub mod fetch_trash;
+pub mod fetch_weaknesses;
+pub mod import_pokemons;
+pub mod individual;
+pub mod purge_trash;
+pub mod restore_pokemon;
+pub mod search_pokemons;
+pub mod type_chart;
+pub mod update_ability;
+pub mod update_pokemon;
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::repositories::pokemon::{PurgeError, Repository};
+
+pub enum Error {
+    Unknown,
+}
+
+pub struct Request {
+    // How long a deleted Pokémon stays restorable.
+    pub retention: Duration,
+    // Seconds since the epoch.
+    pub now: u64,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<usize, Error> {
+    match repo.purge_trash(req.now.saturating_sub(req.retention.as_secs())) {
+        Ok(purged) => Ok(purged),
+        Err(PurgeError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        BaseStats, PokemonAbilities, PokemonForm, PokemonName, PokemonNumber, PokemonTypes,
+    };
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request {
+            retention: Duration::ZERO,
+            now: 0,
+        };
+
+        match execute(repo, req) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    const DELETED_AT: u64 = 1_700_000_000;
+
+    fn trashed_pikachu() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            Some(BaseStats::pikachu()),
+            PokemonAbilities::pikachu(),
+        )
+        .ok()
+        .expect("pokemon to be inserted");
+        repo.delete(
+            PokemonNumber::pikachu(),
+            &PokemonForm::default(),
+            None,
+            DELETED_AT,
+        )
+        .ok()
+        .expect("pokemon to be deleted");
+        repo
+    }
+
+    #[test]
+    fn it_should_keep_pokemons_deleted_within_the_retention_period() {
+        let repo = trashed_pikachu();
+        let req = Request {
+            retention: Duration::from_secs(3600),
+            now: DELETED_AT + 3600,
+        };
+
+        match execute(repo.clone(), req) {
+            Ok(0) => {}
+            _ => unreachable!(),
+        }
+        assert_eq!(repo.fetch_trash().ok().map(|trash| trash.len()), Some(1));
+    }
+
+    #[test]
+    fn it_should_purge_pokemons_deleted_before_the_retention_period() {
+        let repo = trashed_pikachu();
+        let req = Request {
+            retention: Duration::from_secs(3600),
+            now: DELETED_AT + 3601,
+        };
+
+        match execute(repo.clone(), req) {
+            Ok(1) => {}
+            _ => unreachable!(),
+        }
+        assert_eq!(repo.fetch_trash().ok().map(|trash| trash.len()), Some(0));
+    }
+}
@@ -0,0 +1,157 @@
+use std::sync::Arc;
+
+use crate::repositories::ability;
+use crate::repositories::pokemon::{Repository, RestoreError};
+
+use super::entities::{PokemonForm, PokemonNumber};
+use super::fetch_pokemon;
+
+pub enum Error {
+    Unknown,
+    BadRequest,
+    NotFound,
+    Conflict,
+}
+
+pub struct Request {
+    pub number: u16,
+    pub form: Option<String>,
+}
+
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    ability_repo: Arc<dyn ability::Repository>,
+    req: Request,
+) -> Result<fetch_pokemon::Response, Error> {
+    let (number, form) = match (
+        PokemonNumber::try_from(req.number),
+        PokemonForm::try_from(req.form),
+    ) {
+        (Ok(number), Ok(form)) => (number, form),
+        _ => return Err(Error::BadRequest),
+    };
+
+    // A restored Pokémon references its abilities again.
+    let _references = ability_repo.reference();
+    match repo.restore(number, &form) {
+        Ok(pokemon) => Ok(pokemon.into()),
+        Err(RestoreError::Unknown) => Err(Error::Unknown),
+        Err(RestoreError::NotFound) => Err(Error::NotFound),
+        Err(RestoreError::Conflict) => Err(Error::Conflict),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        BaseStats, PokemonAbilities, PokemonName, PokemonNumber, PokemonTypes,
+    };
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn insert_pikachu(repo: &InMemoryRepository) {
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            Some(BaseStats::pikachu()),
+            PokemonAbilities::pikachu(),
+        )
+        .ok()
+        .expect("pokemon to be inserted");
+    }
+
+    fn pikachu() -> Request {
+        Request {
+            number: PokemonNumber::pikachu().into(),
+            form: None,
+        }
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        match execute(
+            repo,
+            Arc::new(ability::InMemoryRepository::new()),
+            pikachu(),
+        ) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            number: PokemonNumber::bad().into(),
+            form: None,
+        };
+
+        match execute(repo, Arc::new(ability::InMemoryRepository::new()), req) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_pokemon_is_not_in_the_trash() {
+        let repo = Arc::new(InMemoryRepository::new());
+        insert_pikachu(&repo);
+
+        match execute(
+            repo,
+            Arc::new(ability::InMemoryRepository::new()),
+            pikachu(),
+        ) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_error_when_the_pokemon_was_recreated() {
+        let repo = Arc::new(InMemoryRepository::new());
+        insert_pikachu(&repo);
+        repo.delete(PokemonNumber::pikachu(), &PokemonForm::default(), None, 0)
+            .ok()
+            .expect("pokemon to be deleted");
+        insert_pikachu(&repo);
+
+        match execute(
+            repo,
+            Arc::new(ability::InMemoryRepository::new()),
+            pikachu(),
+        ) {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_the_restored_pokemon_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        insert_pikachu(&repo);
+        repo.delete(PokemonNumber::pikachu(), &PokemonForm::default(), None, 0)
+            .ok()
+            .expect("pokemon to be deleted");
+
+        match execute(
+            repo.clone(),
+            Arc::new(ability::InMemoryRepository::new()),
+            pikachu(),
+        ) {
+            Ok(res) => {
+                assert_eq!(res.name, String::from(PokemonName::pikachu()));
+                assert_eq!(res.version, 2);
+            }
+            _ => unreachable!(),
+        }
+        assert!(repo
+            .fetch(PokemonNumber::pikachu(), &PokemonForm::default())
+            .is_ok());
+    }
+}
@@ -0,0 +1,244 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use crate::repositories::pokemon::{FetchAllError, Repository};
+
+use super::entities::PokemonName;
+
+const DEFAULT_MAX_RESULTS: usize = 10;
+const MAX_RESULTS: usize = 100;
+// Below this score a candidate only shares a stray trigram with the query.
+const MIN_SCORE: f32 = 0.3;
+
+pub enum Error {
+    Unknown,
+    BadRequest(String),
+}
+
+pub struct Request {
+    pub q: String,
+    pub max_results: Option<usize>,
+}
+
+pub struct Response {
+    pub number: u16,
+    pub form: Option<String>,
+    pub name: String,
+    pub types: Vec<String>,
+    pub score: f32,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Vec<Response>, Error> {
+    let max_results = match req.max_results {
+        None => DEFAULT_MAX_RESULTS,
+        Some(n) if (1..=MAX_RESULTS).contains(&n) => n,
+        Some(n) => {
+            return Err(Error::BadRequest(format!(
+                "max_results {} is out of range, expected 1 to {}",
+                n, MAX_RESULTS
+            )))
+        }
+    };
+    let q = PokemonName::try_from(req.q).map_err(|e| Error::BadRequest(e.to_string()))?;
+
+    let candidates = match repo.fetch_by_trigrams(&q) {
+        Ok(candidates) => candidates,
+        Err(FetchAllError::Unknown) => return Err(Error::Unknown),
+    };
+
+    let mut res: Vec<Response> = candidates
+        .into_iter()
+        .map(|pokemon| {
+            let score = score(&q, &pokemon.name);
+            Response {
+                number: pokemon.number.into(),
+                form: pokemon.form.into(),
+                name: pokemon.name.into(),
+                types: Vec::<String>::from(pokemon.types),
+                score,
+            }
+        })
+        .filter(|res| res.score >= MIN_SCORE)
+        .collect();
+    res.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.number.cmp(&b.number))
+    });
+    res.truncate(max_results);
+    Ok(res)
+}
+
+// Averages the trigram overlap with the normalized edit distance, so that both
+// transpositions and missing letters still rank close matches first.
+fn score(q: &PokemonName, name: &PokemonName) -> f32 {
+    let similarity = jaccard(&q.trigrams(), &name.trigrams());
+
+    let (a, b) = (q.as_str().to_lowercase(), name.as_str().to_lowercase());
+    let len = a.chars().count().max(b.chars().count());
+    let closeness = 1.0 - levenshtein(&a, &b) as f32 / len as f32;
+
+    (similarity + closeness) / 2.0
+}
+
+fn jaccard(a: &BTreeSet<String>, b: &BTreeSet<String>) -> f32 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f32 / union as f32
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonAbilities, PokemonForm, PokemonNumber, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn repo() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            None,
+            PokemonAbilities::default(),
+        )
+        .ok()
+        .expect("pokemon to be inserted");
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonForm::default(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+            None,
+            PokemonAbilities::default(),
+        )
+        .ok()
+        .expect("pokemon to be inserted");
+        repo
+    }
+
+    #[test]
+    fn it_should_compute_the_edit_distance() {
+        assert_eq!(levenshtein("pikachu", "pikachu"), 0);
+        assert_eq!(levenshtein("pikchu", "pikachu"), 1);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "mew"), 3);
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_query_is_empty() {
+        let req = Request {
+            q: String::new(),
+            max_results: None,
+        };
+
+        match execute(repo(), req) {
+            Err(Error::BadRequest(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_max_results_is_out_of_bounds() {
+        for max_results in [0, MAX_RESULTS + 1] {
+            let req = Request {
+                q: String::from("pika"),
+                max_results: Some(max_results),
+            };
+
+            match execute(repo(), req) {
+                Err(Error::BadRequest(_)) => {}
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request {
+            q: String::from("pika"),
+            max_results: None,
+        };
+
+        match execute(repo, req) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_rank_the_closest_names_first() {
+        let req = Request {
+            q: String::from("pikchu"),
+            max_results: None,
+        };
+
+        match execute(repo(), req) {
+            Ok(res) => {
+                assert_eq!(res.len(), 1);
+                assert_eq!(res[0].name, String::from(PokemonName::pikachu()));
+                assert!(res[0].score > 0.5 && res[0].score < 1.0);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_give_an_exact_match_a_perfect_score() {
+        let req = Request {
+            q: String::from("CHARMANDER"),
+            max_results: None,
+        };
+
+        match execute(repo(), req) {
+            Ok(res) => {
+                assert_eq!(res[0].number, u16::from(PokemonNumber::charmander()));
+                assert_eq!(res[0].score, 1.0);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_at_most_max_results() {
+        let repo = repo();
+        repo.insert(
+            PokemonNumber::try_from(26).unwrap(),
+            PokemonForm::default(),
+            PokemonName::try_from(String::from("Raichu")).unwrap(),
+            PokemonTypes::pikachu(),
+            None,
+            PokemonAbilities::default(),
+        )
+        .ok()
+        .expect("pokemon to be inserted");
+        let req = Request {
+            q: String::from("pichu"),
+            max_results: Some(1),
+        };
+
+        match execute(repo, req) {
+            Ok(res) => assert_eq!(res.len(), 1),
+            _ => unreachable!(),
+        }
+    }
+}
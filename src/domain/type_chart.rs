@@ -0,0 +1,110 @@
+use super::entities::{PokemonType, PokemonTypes};
+
+/// Damage multiplier of a move of type `attacking` hitting a single `defending` type.
+pub fn effectiveness(attacking: PokemonType, defending: PokemonType) -> f32 {
+    use PokemonType::*;
+
+    let (super_effective, not_very_effective, no_effect): (&[_], &[_], &[_]) = match attacking {
+        Normal => (&[], &[Rock, Steel], &[Ghost]),
+        Fire => (&[Grass, Ice, Bug, Steel], &[Fire, Water, Rock, Dragon], &[]),
+        Water => (&[Fire, Ground, Rock], &[Water, Grass, Dragon], &[]),
+        Electric => (&[Water, Flying], &[Electric, Grass, Dragon], &[Ground]),
+        Grass => (
+            &[Water, Ground, Rock],
+            &[Fire, Grass, Poison, Flying, Bug, Dragon, Steel],
+            &[],
+        ),
+        Ice => (
+            &[Grass, Ground, Flying, Dragon],
+            &[Fire, Water, Ice, Steel],
+            &[],
+        ),
+        Fighting => (
+            &[Normal, Ice, Rock, Dark, Steel],
+            &[Poison, Flying, Psychic, Bug, Fairy],
+            &[Ghost],
+        ),
+        Poison => (&[Grass, Fairy], &[Poison, Ground, Rock, Ghost], &[Steel]),
+        Ground => (
+            &[Fire, Electric, Poison, Rock, Steel],
+            &[Grass, Bug],
+            &[Flying],
+        ),
+        Flying => (&[Grass, Fighting, Bug], &[Electric, Rock, Steel], &[]),
+        Psychic => (&[Fighting, Poison], &[Psychic, Steel], &[Dark]),
+        Bug => (
+            &[Grass, Psychic, Dark],
+            &[Fire, Fighting, Poison, Flying, Ghost, Steel, Fairy],
+            &[],
+        ),
+        Rock => (&[Fire, Ice, Flying, Bug], &[Fighting, Ground, Steel], &[]),
+        Ghost => (&[Psychic, Ghost], &[Dark], &[Normal]),
+        Dragon => (&[Dragon], &[Steel], &[Fairy]),
+        Dark => (&[Psychic, Ghost], &[Fighting, Dark, Fairy], &[]),
+        Steel => (&[Ice, Rock, Fairy], &[Fire, Water, Electric, Steel], &[]),
+        Fairy => (&[Fighting, Dragon, Dark], &[Fire, Poison, Steel], &[]),
+    };
+
+    if no_effect.contains(&defending) {
+        0.0
+    } else if super_effective.contains(&defending) {
+        2.0
+    } else if not_very_effective.contains(&defending) {
+        0.5
+    } else {
+        1.0
+    }
+}
+
+/// Combined damage multiplier of `attacking` against every type of a pokemon,
+/// i.e. one of 0, 0.25, 0.5, 1, 2 or 4.
+pub fn multiplier(attacking: PokemonType, defending: &PokemonTypes) -> f32 {
+    defending
+        .iter()
+        .map(|t| effectiveness(attacking, *t))
+        .product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use PokemonType::*;
+
+    fn types(ts: &[&str]) -> PokemonTypes {
+        PokemonTypes::try_from(ts.iter().map(|t| t.to_string()).collect::<Vec<_>>()).unwrap()
+    }
+
+    #[test]
+    fn it_should_return_single_type_multipliers() {
+        assert_eq!(effectiveness(Water, Fire), 2.0);
+        assert_eq!(effectiveness(Fire, Water), 0.5);
+        assert_eq!(effectiveness(Normal, Normal), 1.0);
+        assert_eq!(effectiveness(Electric, Ground), 0.0);
+        assert_eq!(effectiveness(Dragon, Fairy), 0.0);
+    }
+
+    #[test]
+    fn it_should_multiply_against_both_types() {
+        let bulbasaur = types(&["Grass", "Poison"]);
+        assert_eq!(multiplier(Psychic, &bulbasaur), 2.0);
+        assert_eq!(multiplier(Grass, &bulbasaur), 0.25);
+        assert_eq!(multiplier(Water, &bulbasaur), 0.5);
+
+        let charizard = types(&["Fire", "Flying"]);
+        assert_eq!(multiplier(Rock, &charizard), 4.0);
+        assert_eq!(multiplier(Ground, &charizard), 0.0);
+        assert_eq!(multiplier(Bug, &charizard), 0.25);
+    }
+
+    #[test]
+    fn it_should_only_produce_known_multipliers() {
+        for a in PokemonType::ALL {
+            for d1 in PokemonType::ALL {
+                for d2 in PokemonType::ALL.into_iter().filter(|d2| *d2 != d1) {
+                    let m = multiplier(a, &types(&[d1.as_str(), d2.as_str()]));
+                    assert!([0.0, 0.25, 0.5, 1.0, 2.0, 4.0].contains(&m));
+                }
+            }
+        }
+    }
+}
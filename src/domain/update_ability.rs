@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use crate::repositories::ability::{Repository, UpdateError};
+
+use super::entities::{Ability, AbilityName, Generation};
+
+pub struct Request {
+    pub name: String,
+    pub description: String,
+    pub generation: u8,
+}
+
+pub enum Error {
+    BadRequest(String),
+    NotFound,
+    Unknown,
+}
+
+pub struct Response {
+    pub name: String,
+    pub description: String,
+    pub generation: u8,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let name = AbilityName::try_from(req.name).map_err(|e| Error::BadRequest(e.to_string()))?;
+    let generation =
+        Generation::try_from(req.generation).map_err(|e| Error::BadRequest(e.to_string()))?;
+    let ability = Ability::new(name, req.description, generation);
+
+    match repo.update(ability) {
+        Ok(ability) => Ok(Response {
+            name: ability.name.into(),
+            description: ability.description,
+            generation: ability.generation.into(),
+        }),
+        Err(UpdateError::NotFound) => Err(Error::NotFound),
+        Err(UpdateError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::ability::InMemoryRepository;
+
+    fn request(generation: u8) -> Request {
+        Request {
+            name: String::from("Static"),
+            description: String::from("Contact may paralyze the attacker."),
+            generation,
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, request(0)) {
+            Err(Error::BadRequest(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_repo_doesnot_contain_the_ability() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, request(3)) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        match execute(repo, request(3)) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_the_updated_ability_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(Ability::new(
+            AbilityName::try_from(String::from("Static")).unwrap(),
+            String::new(),
+            Generation::try_from(3).unwrap(),
+        ))
+        .ok()
+        .expect("ability to be inserted");
+
+        match execute(repo, request(4)) {
+            Ok(res) => {
+                assert_eq!(
+                    res.description,
+                    String::from("Contact may paralyze the attacker.")
+                );
+                assert_eq!(res.generation, 4);
+            }
+            _ => unreachable!(),
+        }
+    }
+}
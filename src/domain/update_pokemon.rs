@@ -0,0 +1,326 @@
+use std::sync::Arc;
+
+use super::create_pokemon::{self, resolve_abilities};
+use super::entities::{Pokemon, ValidationError};
+use super::fetch_pokemon::Response;
+use crate::repositories::ability;
+use crate::repositories::pokemon::{FetchError, Repository, UpdateError};
+
+pub struct Request {
+    pub number: u16,
+    pub form: Option<String>,
+    pub name: String,
+    pub types: Vec<String>,
+    pub stats: Option<[u16; 6]>,
+    pub abilities: Vec<String>,
+    pub hidden_ability: Option<String>,
+    // The version the caller last saw, if it wants the update refused once it is stale.
+    pub version: Option<u64>,
+}
+
+pub enum Error {
+    Invalid(Vec<ValidationError>),
+    UnknownAbility(String),
+    NotFound,
+    Conflict,
+    PreconditionFailed,
+    Unknown,
+}
+
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    ability_repo: Arc<dyn ability::Repository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let pokemon = create_pokemon::validate(create_pokemon::Request {
+        number: req.number,
+        form: req.form,
+        name: req.name,
+        types: req.types,
+        stats: req.stats,
+        abilities: req.abilities,
+        hidden_ability: req.hidden_ability,
+    })
+    .map_err(Error::Invalid)?;
+    let _references = ability_repo.reference();
+    // A catalogue that lost an ability must not stop every later edit of a Pokémon using it.
+    let known = match repo.fetch(pokemon.number.clone(), &pokemon.form) {
+        Ok(stored) => stored.abilities,
+        Err(FetchError::NotFound) => return Err(Error::NotFound),
+        Err(FetchError::Unknown) => return Err(Error::Unknown),
+    };
+    let abilities = match resolve_abilities(ability_repo.clone(), &pokemon.abilities, &known) {
+        Ok(abilities) => abilities,
+        Err(create_pokemon::Error::Invalid(errors)) => return Err(Error::Invalid(errors)),
+        Err(create_pokemon::Error::UnknownAbility(name)) => {
+            return Err(Error::UnknownAbility(name))
+        }
+        Err(_) => return Err(Error::Unknown),
+    };
+
+    match repo.update(
+        Pokemon {
+            abilities,
+            ..pokemon
+        },
+        req.version,
+    ) {
+        Ok(pokemon) => Ok(pokemon.into()),
+        Err(UpdateError::NotFound) => Err(Error::NotFound),
+        Err(UpdateError::Conflict) => Err(Error::Conflict),
+        Err(UpdateError::VersionMismatch) => Err(Error::PreconditionFailed),
+        Err(UpdateError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        Ability, AbilityName, Generation, PokemonAbilities, PokemonForm, PokemonName,
+        PokemonNameError, PokemonNumber, PokemonTypes, PokemonTypesError,
+    };
+    use crate::repositories::ability::Repository as _;
+    use crate::repositories::pokemon::{FileRepository, InMemoryRepository, SqliteRepository};
+
+    fn repo() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonForm::default(),
+            PokemonName::try_from(String::from("Pikachuu")).unwrap(),
+            PokemonTypes::pikachu(),
+            None,
+            PokemonAbilities::default(),
+        )
+        .ok()
+        .expect("pokemon to be inserted");
+        repo
+    }
+
+    fn ability_repo() -> Arc<ability::InMemoryRepository> {
+        let repo = Arc::new(ability::InMemoryRepository::new());
+        repo.insert(Ability::new(
+            AbilityName::try_from(String::from("Static")).unwrap(),
+            String::new(),
+            Generation::try_from(3).unwrap(),
+        ))
+        .ok()
+        .expect("ability to be inserted");
+        repo
+    }
+
+    fn request(number: u16, types: Vec<&str>) -> Request {
+        Request {
+            number,
+            form: None,
+            name: String::from("Pikachu"),
+            types: types.into_iter().map(String::from).collect(),
+            stats: Some([35, 55, 40, 50, 50, 90]),
+            abilities: vec![String::from("static")],
+            hidden_ability: None,
+            version: None,
+        }
+    }
+
+    #[test]
+    fn it_should_replace_the_pokemon_otherwise() {
+        match execute(repo(), ability_repo(), request(25, vec!["Electric"])) {
+            Ok(Response {
+                name,
+                stats,
+                abilities,
+                ..
+            }) => {
+                assert_eq!(name, String::from("Pikachu"));
+                assert_eq!(stats, Some([35, 55, 40, 50, 50, 90]));
+                assert_eq!(abilities, vec![String::from("Static")]);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_bump_the_version_unless_it_is_stale() {
+        let repo = repo();
+        let mut req = request(25, vec!["Electric"]);
+        req.version = Some(1);
+
+        match execute(repo.clone(), ability_repo(), req) {
+            Ok(Response { version, .. }) => assert_eq!(version, 2),
+            _ => unreachable!(),
+        }
+        let mut req = request(25, vec!["Electric"]);
+        req.version = Some(1);
+        match execute(repo, ability_repo(), req) {
+            Err(Error::PreconditionFailed) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_pokemon_does_not_exist() {
+        match execute(repo(), ability_repo(), request(4, vec!["Fire"])) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_an_invalid_error_when_types_are_invalid() {
+        match execute(
+            repo(),
+            ability_repo(),
+            request(25, vec!["Electric", "Shadow"]),
+        ) {
+            Err(Error::Invalid(errors)) => assert_eq!(
+                errors,
+                vec![ValidationError::Types(PokemonTypesError::UnknownType(
+                    String::from("Shadow")
+                ))]
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_an_invalid_error_when_the_name_is_empty() {
+        let mut req = request(25, vec!["Electric"]);
+        req.name = String::new();
+
+        match execute(repo(), ability_repo(), req) {
+            Err(Error::Invalid(errors)) => {
+                assert_eq!(errors, vec![ValidationError::Name(PokemonNameError::Empty)])
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_error_when_the_name_belongs_to_another_number() {
+        let repo = repo();
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonForm::default(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+            None,
+            PokemonAbilities::default(),
+        )
+        .ok()
+        .expect("pokemon to be inserted");
+        let mut req = request(25, vec!["Electric"]);
+        req.name = String::from("charmander");
+
+        match execute(repo, ability_repo(), req) {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        match execute(repo, ability_repo(), request(25, vec!["Electric"])) {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_keep_abilities_the_catalogue_no_longer_has() {
+        let repo = repo();
+        let ability_repo = ability_repo();
+        execute(
+            repo.clone(),
+            ability_repo.clone(),
+            request(25, vec!["Electric"]),
+        )
+        .ok()
+        .expect("pokemon to be updated");
+        ability_repo
+            .delete(
+                AbilityName::try_from(String::from("Static")).unwrap(),
+                &|_| Ok(false),
+            )
+            .ok()
+            .expect("ability to be deleted");
+
+        match execute(
+            repo.clone(),
+            ability_repo.clone(),
+            request(25, vec!["Electric"]),
+        ) {
+            Ok(Response { abilities, .. }) => assert_eq!(abilities, vec![String::from("Static")]),
+            _ => unreachable!(),
+        }
+        let mut req = request(25, vec!["Electric"]);
+        req.hidden_ability = Some(String::from("Lightning Rod"));
+        match execute(repo, ability_repo, req) {
+            Err(Error::UnknownAbility(name)) => assert_eq!(name, "Lightning Rod"),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_update_a_pokemon_after_the_repositories_are_reopened() {
+        let dir = std::env::temp_dir().join(format!("pokedex-reopen-{}", std::process::id()));
+        let db = std::env::temp_dir().join(format!("pokedex-reopen-{}.db", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let _ = std::fs::remove_file(&db);
+        let open = |file: bool| -> (Arc<dyn Repository>, Arc<dyn ability::Repository>) {
+            if file {
+                let repo = FileRepository::open(&dir).unwrap();
+                let abilities = repo.abilities().unwrap();
+                (Arc::new(repo), Arc::new(abilities))
+            } else {
+                let repo = SqliteRepository::open(db.to_str().unwrap()).unwrap();
+                let abilities = repo.abilities();
+                (Arc::new(repo), Arc::new(abilities))
+            }
+        };
+
+        for file in [true, false] {
+            let (repo, ability_repo) = open(file);
+            for name in ["Static", "Lightning Rod"] {
+                ability_repo
+                    .insert(Ability::new(
+                        AbilityName::try_from(String::from(name)).unwrap(),
+                        String::new(),
+                        Generation::try_from(3).unwrap(),
+                    ))
+                    .ok()
+                    .expect("ability to be inserted");
+            }
+            repo.insert(
+                PokemonNumber::pikachu(),
+                PokemonForm::default(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+                None,
+                PokemonAbilities::try_from((vec![String::from("Static")], None)).unwrap(),
+            )
+            .ok()
+            .expect("pokemon to be inserted");
+            drop((repo, ability_repo));
+
+            let (repo, ability_repo) = open(file);
+            let mut req = request(25, vec!["Electric"]);
+            req.hidden_ability = Some(String::from("lightning rod"));
+            match execute(repo, ability_repo, req) {
+                Ok(Response {
+                    hidden_ability,
+                    version,
+                    ..
+                }) => {
+                    assert_eq!(hidden_ability, Some(String::from("Lightning Rod")));
+                    assert_eq!(version, 2);
+                }
+                _ => unreachable!(),
+            }
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+        std::fs::remove_file(&db).unwrap();
+    }
+}
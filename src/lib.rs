@@ -0,0 +1,8 @@
+pub mod api;
+pub mod config;
+pub mod domain;
+pub mod repositories;
+
+#[macro_use]
+extern crate rouille;
+extern crate serde;
@@ -1,15 +1,83 @@
-use repositories::pokemon::InMemoryRepository;
+use clap::Parser;
+use pokedex::api;
+use pokedex::config::{self, Config, LogLevel};
+use pokedex::domain::purge_trash;
+use pokedex::repositories::pokemon::{self, Repository};
+use pokedex::repositories::{evolution, learnset, moves};
 use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 
-mod api;
-mod domain;
-mod repositories;
+const PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
 
-#[macro_use]
-extern crate rouille;
-extern crate serde;
+#[derive(Parser)]
+#[command(name = "pokedex", about = "Serve the Pokédex over HTTP")]
+struct Cli {
+    #[command(flatten)]
+    config: config::Args,
+}
+
+fn exit(errors: &[String]) -> ! {
+    for e in errors {
+        eprintln!("error: {}", e);
+    }
+    std::process::exit(1);
+}
+
+// Sweeps the trash once at startup, then every `PURGE_INTERVAL`.
+fn spawn_trash_purge(repo: Arc<dyn Repository>, retention: Duration, log_level: LogLevel) {
+    thread::spawn(move || loop {
+        let req = purge_trash::Request {
+            retention,
+            now: pokemon::now(),
+        };
+        match purge_trash::execute(repo.clone(), req) {
+            Ok(0) => {}
+            Ok(purged) if log_level >= LogLevel::Info => {
+                eprintln!("info purged {} pokemons from the trash", purged)
+            }
+            Ok(_) => {}
+            Err(purge_trash::Error::Unknown) if log_level >= LogLevel::Error => {
+                eprintln!("error cannot purge the trash")
+            }
+            Err(purge_trash::Error::Unknown) => {}
+        }
+        thread::sleep(PURGE_INTERVAL);
+    });
+}
 
 fn main() {
-    let repo = Arc::new(InMemoryRepository::new());
-    api::serve("localhost:8111", repo);
+    let config = match Config::load(Cli::parse().config) {
+        Ok(config) => config,
+        Err(errors) => exit(&errors.iter().map(ToString::to_string).collect::<Vec<_>>()),
+    };
+    if config.log_level >= LogLevel::Debug {
+        eprintln!("debug {:?}", config);
+    }
+    if let Err(e) = config.configure_dex_range() {
+        exit(&[e.to_string()]);
+    }
+
+    let (repo, ability_repo) = match config.open_repositories() {
+        Ok(repos) => (repos.pokemon, repos.ability),
+        Err(e) => exit(&[e]),
+    };
+    let evolution_repo = Arc::new(evolution::InMemoryRepository::new());
+    let move_repo = Arc::new(moves::InMemoryRepository::new());
+    let learnset_repo = Arc::new(learnset::InMemoryRepository::new());
+
+    if config.log_level >= LogLevel::Info {
+        eprintln!("info using the {} backend", config.backend);
+    }
+    spawn_trash_purge(repo.clone(), config.trash_retention, config.log_level);
+    if let Err(e) = api::serve(
+        &config,
+        repo,
+        evolution_repo,
+        ability_repo,
+        move_repo,
+        learnset_repo,
+    ) {
+        exit(&[e]);
+    }
 }
@@ -0,0 +1,496 @@
+use crate::domain::entities::{Ability, AbilityName, Generation};
+use rusqlite::{params, Connection, ErrorCode, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError, RwLock, RwLockReadGuard};
+
+pub trait Repository: Send + Sync {
+    fn insert(&self, ability: Ability) -> Result<Ability, InsertError>;
+    fn fetch_all(&self) -> Result<Vec<Ability>, FetchAllError>;
+    fn fetch(&self, name: AbilityName) -> Result<Ability, FetchError>;
+    fn update(&self, ability: Ability) -> Result<Ability, UpdateError>;
+
+    // Held while a Pokémon is stored or restored with abilities checked against the catalogue,
+    // so that `delete` cannot remove one of them in between.
+    fn reference(&self) -> RwLockReadGuard<'_, ()>;
+
+    // Deletes the ability unless `in_use` finds a Pokémon referencing it. The check and the
+    // delete run under the lock `reference` shares.
+    fn delete(
+        &self,
+        name: AbilityName,
+        in_use: &dyn Fn(&AbilityName) -> Result<bool, ()>,
+    ) -> Result<Ability, DeleteError>;
+}
+
+pub enum InsertError {
+    Conflict,
+    Unknown,
+}
+
+pub enum FetchAllError {
+    Unknown,
+}
+
+pub enum FetchError {
+    Unknown,
+    NotFound,
+}
+
+pub enum UpdateError {
+    Unknown,
+    NotFound,
+}
+
+pub enum DeleteError {
+    Unknown,
+    NotFound,
+    InUse,
+}
+
+#[derive(Default)]
+struct References(RwLock<()>);
+
+impl References {
+    fn read(&self) -> RwLockReadGuard<'_, ()> {
+        self.0.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn check(
+        &self,
+        name: &AbilityName,
+        in_use: &dyn Fn(&AbilityName) -> Result<bool, ()>,
+        delete: impl FnOnce() -> Result<Ability, DeleteError>,
+    ) -> Result<Ability, DeleteError> {
+        let _references = self.0.write().unwrap_or_else(PoisonError::into_inner);
+        match in_use(name) {
+            Ok(false) => delete(),
+            Ok(true) => Err(DeleteError::InUse),
+            Err(()) => Err(DeleteError::Unknown),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct InMemoryRepository {
+    abilities: Mutex<Vec<Ability>>,
+    references: References,
+    error: bool,
+}
+
+impl InMemoryRepository {
+    pub fn new() -> Self {
+        let abilities: Mutex<Vec<Ability>> = Mutex::new(vec![]);
+        Self {
+            abilities,
+            references: References::default(),
+            error: false,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_error(mut self) -> Self {
+        self.error = true;
+        self
+    }
+}
+
+impl Repository for InMemoryRepository {
+    fn insert(&self, ability: Ability) -> Result<Ability, InsertError> {
+        if self.error {
+            return Err(InsertError::Unknown);
+        }
+
+        match self.abilities.lock() {
+            Ok(mut abilities) => insert(&mut abilities, ability),
+            _ => Err(InsertError::Unknown),
+        }
+    }
+
+    fn fetch_all(&self) -> Result<Vec<Ability>, FetchAllError> {
+        if self.error {
+            return Err(FetchAllError::Unknown);
+        }
+
+        match self.abilities.lock() {
+            Ok(abilities) => Ok(sorted(&abilities)),
+            _ => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn fetch(&self, name: AbilityName) -> Result<Ability, FetchError> {
+        if self.error {
+            return Err(FetchError::Unknown);
+        }
+
+        match self.abilities.lock() {
+            Ok(abilities) => find(&abilities, &name),
+            _ => Err(FetchError::Unknown),
+        }
+    }
+
+    fn update(&self, ability: Ability) -> Result<Ability, UpdateError> {
+        if self.error {
+            return Err(UpdateError::Unknown);
+        }
+
+        match self.abilities.lock() {
+            Ok(mut abilities) => update(&mut abilities, ability),
+            _ => Err(UpdateError::Unknown),
+        }
+    }
+
+    fn reference(&self) -> RwLockReadGuard<'_, ()> {
+        self.references.read()
+    }
+
+    fn delete(
+        &self,
+        name: AbilityName,
+        in_use: &dyn Fn(&AbilityName) -> Result<bool, ()>,
+    ) -> Result<Ability, DeleteError> {
+        if self.error {
+            return Err(DeleteError::Unknown);
+        }
+
+        self.references
+            .check(&name, in_use, || match self.abilities.lock() {
+                Ok(mut abilities) => delete(&mut abilities, &name),
+                _ => Err(DeleteError::Unknown),
+            })
+    }
+}
+
+// The catalogue operations shared by the in-memory and file repositories.
+fn insert(abilities: &mut Vec<Ability>, ability: Ability) -> Result<Ability, InsertError> {
+    if abilities.iter().any(|a| a.name == ability.name) {
+        Err(InsertError::Conflict)
+    } else {
+        abilities.push(ability.clone());
+        Ok(ability)
+    }
+}
+
+fn sorted(abilities: &[Ability]) -> Vec<Ability> {
+    let mut abilities = abilities.to_vec();
+    abilities.sort_by_key(|a| String::from(a.name.clone()).to_lowercase());
+    abilities
+}
+
+fn find(abilities: &[Ability], name: &AbilityName) -> Result<Ability, FetchError> {
+    match abilities.iter().find(|a| a.name == *name) {
+        Some(ability) => Ok(ability.clone()),
+        _ => Err(FetchError::NotFound),
+    }
+}
+
+fn update(abilities: &mut [Ability], ability: Ability) -> Result<Ability, UpdateError> {
+    match abilities.iter_mut().find(|a| a.name == ability.name) {
+        Some(a) => {
+            *a = ability.clone();
+            Ok(ability)
+        }
+        None => Err(UpdateError::NotFound),
+    }
+}
+
+fn delete(abilities: &mut Vec<Ability>, name: &AbilityName) -> Result<Ability, DeleteError> {
+    match abilities.iter().position(|a| a.name == *name) {
+        Some(idx) => Ok(abilities.remove(idx)),
+        None => Err(DeleteError::NotFound),
+    }
+}
+
+// Lives in the same database as the Pokémon, whose migrations create its table.
+pub struct SqliteRepository {
+    connection: Arc<Mutex<Connection>>,
+    references: References,
+}
+
+impl SqliteRepository {
+    pub(crate) fn new(connection: Arc<Mutex<Connection>>) -> Self {
+        Self {
+            connection,
+            references: References::default(),
+        }
+    }
+
+    fn delete_unchecked(&self, name: AbilityName) -> Result<Ability, DeleteError> {
+        let connection = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(DeleteError::Unknown),
+        };
+
+        let tx = match connection.unchecked_transaction() {
+            Ok(tx) => tx,
+            _ => return Err(DeleteError::Unknown),
+        };
+        let name = String::from(name);
+        let row = match tx
+            .query_row(
+                &format!("SELECT {} FROM abilities WHERE name = ?1", COLUMNS),
+                [&name],
+                read_row,
+            )
+            .optional()
+        {
+            Ok(Some(Some(ability))) => ability,
+            Ok(None) => return Err(DeleteError::NotFound),
+            _ => return Err(DeleteError::Unknown),
+        };
+        match tx
+            .execute("DELETE FROM abilities WHERE name = ?1", [&name])
+            .and_then(|_| tx.commit())
+        {
+            Ok(()) => Ok(row),
+            Err(_) => Err(DeleteError::Unknown),
+        }
+    }
+}
+
+const COLUMNS: &str = "name, description, generation";
+
+fn read_row(row: &Row) -> rusqlite::Result<Option<Ability>> {
+    let (name, description, generation): (String, String, u8) =
+        (row.get(0)?, row.get(1)?, row.get(2)?);
+    Ok(
+        match (
+            AbilityName::try_from(name),
+            Generation::try_from(generation),
+        ) {
+            (Ok(name), Ok(generation)) => Some(Ability::new(name, description, generation)),
+            _ => None,
+        },
+    )
+}
+
+fn ability_params(ability: &Ability) -> (String, String, u8) {
+    (
+        String::from(ability.name.clone()),
+        ability.description.clone(),
+        u8::from(ability.generation),
+    )
+}
+
+impl Repository for SqliteRepository {
+    fn insert(&self, ability: Ability) -> Result<Ability, InsertError> {
+        let connection = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(InsertError::Unknown),
+        };
+
+        let (name, description, generation) = ability_params(&ability);
+        match connection.execute(
+            "INSERT INTO abilities (name, description, generation) VALUES (?1, ?2, ?3)",
+            params![name, description, generation],
+        ) {
+            Ok(_) => Ok(ability),
+            Err(rusqlite::Error::SqliteFailure(e, _))
+                if e.code == ErrorCode::ConstraintViolation =>
+            {
+                Err(InsertError::Conflict)
+            }
+            Err(_) => Err(InsertError::Unknown),
+        }
+    }
+
+    fn fetch_all(&self) -> Result<Vec<Ability>, FetchAllError> {
+        let connection = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let rows = connection
+            .prepare(&format!(
+                "SELECT {} FROM abilities ORDER BY name COLLATE NOCASE",
+                COLUMNS
+            ))
+            .and_then(|mut stmt| {
+                stmt.query_map([], read_row)?
+                    .collect::<rusqlite::Result<Vec<_>>>()
+            });
+        match rows {
+            Ok(rows) => rows
+                .into_iter()
+                .collect::<Option<_>>()
+                .ok_or(FetchAllError::Unknown),
+            Err(_) => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn fetch(&self, name: AbilityName) -> Result<Ability, FetchError> {
+        let connection = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchError::Unknown),
+        };
+
+        let row = connection
+            .query_row(
+                &format!("SELECT {} FROM abilities WHERE name = ?1", COLUMNS),
+                [String::from(name)],
+                read_row,
+            )
+            .optional();
+        match row {
+            Ok(Some(Some(ability))) => Ok(ability),
+            Ok(None) => Err(FetchError::NotFound),
+            _ => Err(FetchError::Unknown),
+        }
+    }
+
+    fn update(&self, ability: Ability) -> Result<Ability, UpdateError> {
+        let connection = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        let (name, description, generation) = ability_params(&ability);
+        match connection.execute(
+            "UPDATE abilities SET name = ?1, description = ?2, generation = ?3 WHERE name = ?1",
+            params![name, description, generation],
+        ) {
+            Ok(0) => Err(UpdateError::NotFound),
+            Ok(_) => Ok(ability),
+            Err(_) => Err(UpdateError::Unknown),
+        }
+    }
+
+    fn reference(&self) -> RwLockReadGuard<'_, ()> {
+        self.references.read()
+    }
+
+    fn delete(
+        &self,
+        name: AbilityName,
+        in_use: &dyn Fn(&AbilityName) -> Result<bool, ()>,
+    ) -> Result<Ability, DeleteError> {
+        self.references
+            .check(&name, in_use, || self.delete_unchecked(name.clone()))
+    }
+}
+
+const ABILITIES_FILE: &str = "abilities.json";
+
+#[derive(Serialize, Deserialize)]
+struct StoredAbility {
+    name: String,
+    description: String,
+    generation: u8,
+}
+
+// Rewrites `abilities.json` whole on every change: the catalogue is small and rarely edited.
+// It sits in the Pokémon data directory, whose lock the Pokémon repository holds.
+pub struct FileRepository {
+    path: PathBuf,
+    abilities: Mutex<Vec<Ability>>,
+    references: References,
+}
+
+impl FileRepository {
+    pub(crate) fn open(dir: &Path) -> io::Result<Self> {
+        let path = dir.join(ABILITIES_FILE);
+        let abilities = match fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice::<Vec<StoredAbility>>(&bytes)
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "corrupted abilities"))?
+                .into_iter()
+                .map(|a| {
+                    match (
+                        AbilityName::try_from(a.name),
+                        Generation::try_from(a.generation),
+                    ) {
+                        (Ok(name), Ok(generation)) => {
+                            Ok(Ability::new(name, a.description, generation))
+                        }
+                        _ => Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "invalid ability in abilities file",
+                        )),
+                    }
+                })
+                .collect::<io::Result<_>>()?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => vec![],
+            Err(e) => return Err(e),
+        };
+        Ok(Self {
+            path,
+            abilities: Mutex::new(abilities),
+            references: References::default(),
+        })
+    }
+
+    // Applies `change` to a copy and only keeps it once the copy is on disk.
+    fn write<T, E>(
+        &self,
+        change: impl FnOnce(&mut Vec<Ability>) -> Result<T, E>,
+        unknown: E,
+    ) -> Result<T, E> {
+        let mut abilities = match self.abilities.lock() {
+            Ok(lock) => lock,
+            _ => return Err(unknown),
+        };
+
+        let mut next = abilities.clone();
+        let result = change(&mut next)?;
+        if self.save(&next).is_err() {
+            return Err(unknown);
+        }
+        *abilities = next;
+        Ok(result)
+    }
+
+    fn save(&self, abilities: &[Ability]) -> io::Result<()> {
+        let stored = abilities
+            .iter()
+            .map(|a| StoredAbility {
+                name: a.name.clone().into(),
+                description: a.description.clone(),
+                generation: a.generation.into(),
+            })
+            .collect::<Vec<_>>();
+        let tmp = self.path.with_extension("json.tmp");
+        let mut file = File::create(&tmp)?;
+        file.write_all(&serde_json::to_vec(&stored).map_err(io::Error::from)?)?;
+        file.sync_all()?;
+        fs::rename(&tmp, &self.path)
+    }
+}
+
+impl Repository for FileRepository {
+    fn insert(&self, ability: Ability) -> Result<Ability, InsertError> {
+        self.write(|abilities| insert(abilities, ability), InsertError::Unknown)
+    }
+
+    fn fetch_all(&self) -> Result<Vec<Ability>, FetchAllError> {
+        match self.abilities.lock() {
+            Ok(abilities) => Ok(sorted(&abilities)),
+            _ => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn fetch(&self, name: AbilityName) -> Result<Ability, FetchError> {
+        match self.abilities.lock() {
+            Ok(abilities) => find(&abilities, &name),
+            _ => Err(FetchError::Unknown),
+        }
+    }
+
+    fn update(&self, ability: Ability) -> Result<Ability, UpdateError> {
+        self.write(|abilities| update(abilities, ability), UpdateError::Unknown)
+    }
+
+    fn reference(&self) -> RwLockReadGuard<'_, ()> {
+        self.references.read()
+    }
+
+    fn delete(
+        &self,
+        name: AbilityName,
+        in_use: &dyn Fn(&AbilityName) -> Result<bool, ()>,
+    ) -> Result<Ability, DeleteError> {
+        self.references.check(&name, in_use, || {
+            self.write(|abilities| delete(abilities, &name), DeleteError::Unknown)
+        })
+    }
+}
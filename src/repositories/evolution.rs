@@ -0,0 +1,91 @@
+use crate::domain::entities::{Evolution, PokemonNumber};
+use std::sync::Mutex;
+
+pub trait Repository: Send + Sync {
+    // Keeps chains trees: refuses a second pre-evolution and anything that would loop.
+    fn insert(&self, evolution: Evolution) -> Result<Evolution, InsertError>;
+    fn fetch_all(&self) -> Result<Vec<Evolution>, FetchAllError>;
+}
+
+pub enum InsertError {
+    Conflict,
+    Cycle,
+    Unknown,
+}
+
+pub enum FetchAllError {
+    Unknown,
+}
+
+#[derive(Default)]
+pub struct InMemoryRepository {
+    evolutions: Mutex<Vec<Evolution>>,
+    error: bool,
+}
+
+impl InMemoryRepository {
+    pub fn new() -> Self {
+        let evolutions: Mutex<Vec<Evolution>> = Mutex::new(vec![]);
+        Self {
+            evolutions,
+            error: false,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_error(mut self) -> Self {
+        self.error = true;
+        self
+    }
+}
+
+impl Repository for InMemoryRepository {
+    fn insert(&self, evolution: Evolution) -> Result<Evolution, InsertError> {
+        if self.error {
+            return Err(InsertError::Unknown);
+        }
+
+        let mut evolutions = match self.evolutions.lock() {
+            Ok(lock) => lock,
+            _ => return Err(InsertError::Unknown),
+        };
+
+        // Checked under the same lock as the push, so concurrent inserts cannot close a loop.
+        if evolutions.iter().any(|e| e.to == evolution.to) {
+            return Err(InsertError::Conflict);
+        }
+        if reaches(&evolutions, &evolution.to, &evolution.from) {
+            return Err(InsertError::Cycle);
+        }
+        evolutions.push(evolution.clone());
+        Ok(evolution)
+    }
+
+    fn fetch_all(&self) -> Result<Vec<Evolution>, FetchAllError> {
+        if self.error {
+            return Err(FetchAllError::Unknown);
+        }
+
+        let evolutions = match self.evolutions.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchAllError::Unknown),
+        };
+        Ok(evolutions.to_vec())
+    }
+}
+
+fn reaches(evolutions: &[Evolution], from: &PokemonNumber, target: &PokemonNumber) -> bool {
+    let mut stack = vec![from];
+    while let Some(current) = stack.pop() {
+        if current == target {
+            return true;
+        }
+        stack.extend(
+            evolutions
+                .iter()
+                .filter(|e| &e.from == current)
+                .map(|e| &e.to),
+        );
+    }
+    false
+}
@@ -0,0 +1,78 @@
+use crate::domain::entities::{LearnsetEntry, PokemonNumber};
+use std::sync::Mutex;
+
+pub trait Repository: Send + Sync {
+    fn insert(&self, entry: LearnsetEntry) -> Result<LearnsetEntry, InsertError>;
+    fn fetch(&self, number: PokemonNumber) -> Result<Vec<LearnsetEntry>, FetchError>;
+}
+
+pub enum InsertError {
+    Conflict,
+    Unknown,
+}
+
+pub enum FetchError {
+    Unknown,
+}
+
+#[derive(Default)]
+pub struct InMemoryRepository {
+    entries: Mutex<Vec<LearnsetEntry>>,
+    error: bool,
+}
+
+impl InMemoryRepository {
+    pub fn new() -> Self {
+        let entries: Mutex<Vec<LearnsetEntry>> = Mutex::new(vec![]);
+        Self {
+            entries,
+            error: false,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_error(mut self) -> Self {
+        self.error = true;
+        self
+    }
+}
+
+impl Repository for InMemoryRepository {
+    fn insert(&self, entry: LearnsetEntry) -> Result<LearnsetEntry, InsertError> {
+        if self.error {
+            return Err(InsertError::Unknown);
+        }
+
+        let mut entries = match self.entries.lock() {
+            Ok(lock) => lock,
+            _ => return Err(InsertError::Unknown),
+        };
+
+        if entries.iter().any(|e| {
+            e.number == entry.number && e.move_name == entry.move_name && e.method == entry.method
+        }) {
+            Err(InsertError::Conflict)
+        } else {
+            entries.push(entry.clone());
+            Ok(entry)
+        }
+    }
+
+    fn fetch(&self, number: PokemonNumber) -> Result<Vec<LearnsetEntry>, FetchError> {
+        if self.error {
+            return Err(FetchError::Unknown);
+        }
+
+        let entries = match self.entries.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchError::Unknown),
+        };
+        let mut entries = entries
+            .iter()
+            .filter(|e| e.number == number)
+            .cloned()
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|e| e.method);
+        Ok(entries)
+    }
+}
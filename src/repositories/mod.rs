@@ -1 +1,5 @@
+pub mod ability;
+pub mod evolution;
+pub mod learnset;
+pub mod moves;
 pub mod pokemon;
@@ -0,0 +1,94 @@
+use crate::domain::entities::{Move, MoveName};
+use std::sync::Mutex;
+
+pub trait Repository: Send + Sync {
+    fn insert(&self, m: Move) -> Result<Move, InsertError>;
+    fn fetch_all(&self) -> Result<Vec<Move>, FetchAllError>;
+    fn fetch(&self, name: MoveName) -> Result<Move, FetchError>;
+}
+
+pub enum InsertError {
+    Conflict,
+    Unknown,
+}
+
+pub enum FetchAllError {
+    Unknown,
+}
+
+pub enum FetchError {
+    Unknown,
+    NotFound,
+}
+
+#[derive(Default)]
+pub struct InMemoryRepository {
+    moves: Mutex<Vec<Move>>,
+    error: bool,
+}
+
+impl InMemoryRepository {
+    pub fn new() -> Self {
+        let moves: Mutex<Vec<Move>> = Mutex::new(vec![]);
+        Self {
+            moves,
+            error: false,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_error(mut self) -> Self {
+        self.error = true;
+        self
+    }
+}
+
+impl Repository for InMemoryRepository {
+    fn insert(&self, m: Move) -> Result<Move, InsertError> {
+        if self.error {
+            return Err(InsertError::Unknown);
+        }
+
+        let mut moves = match self.moves.lock() {
+            Ok(lock) => lock,
+            _ => return Err(InsertError::Unknown),
+        };
+
+        if moves.iter().any(|existing| existing.name == m.name) {
+            Err(InsertError::Conflict)
+        } else {
+            moves.push(m.clone());
+            Ok(m)
+        }
+    }
+
+    fn fetch_all(&self) -> Result<Vec<Move>, FetchAllError> {
+        if self.error {
+            return Err(FetchAllError::Unknown);
+        }
+
+        let moves = match self.moves.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchAllError::Unknown),
+        };
+        let mut moves = moves.to_vec();
+        moves.sort_by_key(|m| String::from(m.name.clone()).to_lowercase());
+        Ok(moves)
+    }
+
+    fn fetch(&self, name: MoveName) -> Result<Move, FetchError> {
+        if self.error {
+            return Err(FetchError::Unknown);
+        }
+
+        let moves = match self.moves.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchError::Unknown),
+        };
+
+        match moves.iter().find(|m| m.name == name) {
+            Some(m) => Ok(m.clone()),
+            _ => Err(FetchError::NotFound),
+        }
+    }
+}
@@ -1,17 +1,152 @@
-use crate::domain::entities::{Pokemon, PokemonName, PokemonNumber, PokemonTypes};
-use std::sync::Mutex;
+use crate::domain::entities::{
+    BaseStats, Pokemon, PokemonAbilities, PokemonForm, PokemonName, PokemonNumber, PokemonType,
+    PokemonTypes,
+};
+use rusqlite::{params, Connection, ErrorCode, OptionalExtension, Row, ToSql};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{self, Read, Write};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::ability;
 
 pub trait Repository: Send + Sync {
     fn insert(
         &self,
         number: PokemonNumber,
+        form: PokemonForm,
         name: PokemonName,
         types: PokemonTypes,
+        stats: Option<BaseStats>,
+        abilities: PokemonAbilities,
     ) -> Result<Pokemon, InsertError>;
 
+    // Items are inserted in order, so each one is also checked against the ones before it.
+    fn insert_many(
+        &self,
+        pokemons: Vec<Pokemon>,
+        mode: BulkMode,
+    ) -> Result<Vec<Result<Pokemon, InsertError>>, InsertManyError>;
+
     fn fetch_all(&self) -> Result<Vec<Pokemon>, FetchAllError>;
-    fn fetch(&self, number: PokemonNumber) -> Result<Pokemon, FetchError>;
-    fn delete(&self, number: PokemonNumber) -> Result<Pokemon, DeleteError>;
+
+    // Backends that can filter and paginate natively should override this.
+    fn query(&self, query: &Query) -> Result<Page, FetchAllError> {
+        self.fetch_all().map(|pokemons| query.apply(pokemons))
+    }
+
+    fn fetch(&self, number: PokemonNumber, form: &PokemonForm) -> Result<Pokemon, FetchError>;
+    fn fetch_by_name(&self, name: &PokemonName) -> Result<Pokemon, FetchError>;
+    // Candidates for fuzzy search: every Pokémon sharing at least one trigram with `name`.
+    fn fetch_by_trigrams(&self, name: &PokemonName) -> Result<Vec<Pokemon>, FetchAllError>;
+    fn fetch_forms(&self, number: PokemonNumber) -> Result<Vec<Pokemon>, FetchError>;
+    // When `version` is given, the change only goes through if the stored Pokémon is still at it.
+    fn update(&self, pokemon: Pokemon, version: Option<u64>) -> Result<Pokemon, UpdateError>;
+    // Moves the Pokémon to the trash, where it stays until restored or purged. `deleted_at`
+    // is in seconds since the epoch, like `purge_trash`'s cutoff.
+    fn delete(
+        &self,
+        number: PokemonNumber,
+        form: &PokemonForm,
+        version: Option<u64>,
+        deleted_at: u64,
+    ) -> Result<Pokemon, DeleteError>;
+    fn fetch_trash(&self) -> Result<Vec<Tombstone>, FetchAllError>;
+    fn restore(&self, number: PokemonNumber, form: &PokemonForm) -> Result<Pokemon, RestoreError>;
+    // Drops the tombstones older than `deleted_before`, in seconds since the epoch.
+    fn purge_trash(&self, deleted_before: u64) -> Result<usize, PurgeError>;
+}
+
+pub enum NameFilter {
+    Prefix(String),
+    Contains(String),
+}
+
+#[derive(Clone, Copy, Default)]
+pub enum SortKey {
+    #[default]
+    Number,
+    Name,
+}
+
+#[derive(Default)]
+pub struct Query {
+    pub pokemon_type: Option<PokemonType>,
+    pub name: Option<NameFilter>,
+    pub numbers: Option<RangeInclusive<u16>>,
+    pub sort: SortKey,
+    pub descending: bool,
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+pub struct Page {
+    pub pokemons: Vec<Pokemon>,
+    pub total: usize,
+}
+
+#[derive(Clone)]
+pub struct Tombstone {
+    pub pokemon: Pokemon,
+    // Seconds since the epoch.
+    pub deleted_at: u64,
+}
+
+// Seconds since the epoch, the clock tombstones are stamped with.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+impl Query {
+    // Names fold ASCII case only, like SQLite's LIKE and NOCASE, so every backend agrees.
+    fn matches(&self, pokemon: &Pokemon) -> bool {
+        let name = name_key(&pokemon.name);
+        self.pokemon_type
+            .is_none_or(|t| pokemon.types.iter().any(|pt| *pt == t))
+            && self
+                .numbers
+                .as_ref()
+                .is_none_or(|numbers| numbers.contains(&u16::from(pokemon.number.clone())))
+            && match &self.name {
+                Some(NameFilter::Prefix(prefix)) => name.starts_with(&prefix.to_ascii_lowercase()),
+                Some(NameFilter::Contains(part)) => name.contains(&part.to_ascii_lowercase()),
+                None => true,
+            }
+    }
+
+    fn compare(&self, pokemon1: &Pokemon, pokemon2: &Pokemon) -> Ordering {
+        let key = match self.sort {
+            SortKey::Number => Ordering::Equal,
+            SortKey::Name => name_key(&pokemon1.name).cmp(&name_key(&pokemon2.name)),
+        };
+        let ordering = key.then_with(|| {
+            (&pokemon1.number, &pokemon1.form).cmp(&(&pokemon2.number, &pokemon2.form))
+        });
+        if self.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+
+    pub fn apply(&self, pokemons: Vec<Pokemon>) -> Page {
+        let mut pokemons: Vec<Pokemon> = pokemons.into_iter().filter(|p| self.matches(p)).collect();
+        pokemons.sort_by(|pokemon1, pokemon2| self.compare(pokemon1, pokemon2));
+        let total = pokemons.len();
+        let pokemons = pokemons
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect();
+        Page { pokemons, total }
+    }
 }
 
 pub enum InsertError {
@@ -19,6 +154,21 @@ pub enum InsertError {
     Unknown,
 }
 
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum BulkMode {
+    // Nothing is stored unless every item is.
+    #[default]
+    AllOrNothing,
+    // Every item that can be stored is; the others report their error.
+    ContinueOnError,
+}
+
+pub enum InsertManyError {
+    // The item at this index was rejected in all-or-nothing mode, so nothing was stored.
+    Rejected(usize, InsertError),
+    Unknown,
+}
+
 pub enum FetchAllError {
     Unknown,
 }
@@ -28,25 +178,326 @@ pub enum FetchError {
     NotFound,
 }
 
+pub enum UpdateError {
+    Unknown,
+    NotFound,
+    Conflict,
+    VersionMismatch,
+}
+
 pub enum DeleteError {
     Unknown,
     NotFound,
+    VersionMismatch,
+}
+
+pub enum RestoreError {
+    Unknown,
+    NotFound,
+    // Another Pokémon took the number, form or name in the meantime.
+    Conflict,
+}
+
+pub enum PurgeError {
+    Unknown,
+}
+
+fn name_key(name: &PokemonName) -> String {
+    name.as_str().to_ascii_lowercase()
+}
+
+// Pokémon held in memory with a case-insensitive name index and a trigram index for fuzzy
+// search. A name belongs to a single number, but every form of that number may reuse it,
+// hence the per-name form count.
+#[derive(Default)]
+struct Collection {
+    pokemons: Vec<Pokemon>,
+    names: HashMap<String, (PokemonNumber, usize)>,
+    trigrams: HashMap<String, BTreeSet<(PokemonNumber, PokemonForm)>>,
+    // Deleted Pokémon are kept out of every index; deleting a key twice keeps the latest.
+    trash: BTreeMap<(PokemonNumber, PokemonForm), Tombstone>,
+}
+
+impl Collection {
+    fn position(&self, number: &PokemonNumber, form: &PokemonForm) -> Option<usize> {
+        self.pokemons
+            .iter()
+            .position(|p| p.number == *number && p.form == *form)
+    }
+
+    fn name_taken(&self, pokemon: &Pokemon) -> bool {
+        matches!(
+            self.names.get(&name_key(&pokemon.name)),
+            Some((number, _)) if *number != pokemon.number
+        )
+    }
+
+    fn check_insert(&self, pokemon: &Pokemon) -> Result<(), InsertError> {
+        if self.position(&pokemon.number, &pokemon.form).is_some() || self.name_taken(pokemon) {
+            Err(InsertError::Conflict)
+        } else {
+            Ok(())
+        }
+    }
+
+    // Returns where the Pokémon is stored, along with the version it takes once updated.
+    fn check_update(
+        &self,
+        pokemon: &Pokemon,
+        version: Option<u64>,
+    ) -> Result<(usize, u64), UpdateError> {
+        match self.position(&pokemon.number, &pokemon.form) {
+            None => Err(UpdateError::NotFound),
+            Some(idx) if version.is_some_and(|v| v != self.pokemons[idx].version) => {
+                Err(UpdateError::VersionMismatch)
+            }
+            Some(_) if self.name_taken(pokemon) => Err(UpdateError::Conflict),
+            Some(idx) => Ok((idx, self.pokemons[idx].version + 1)),
+        }
+    }
+
+    fn check_delete(
+        &self,
+        number: &PokemonNumber,
+        form: &PokemonForm,
+        version: Option<u64>,
+    ) -> Result<usize, DeleteError> {
+        match self.position(number, form) {
+            None => Err(DeleteError::NotFound),
+            Some(idx) if version.is_some_and(|v| v != self.pokemons[idx].version) => {
+                Err(DeleteError::VersionMismatch)
+            }
+            Some(idx) => Ok(idx),
+        }
+    }
+
+    fn index(&mut self, pokemon: &Pokemon) {
+        self.names
+            .entry(name_key(&pokemon.name))
+            .or_insert((pokemon.number.clone(), 0))
+            .1 += 1;
+        for trigram in pokemon.name.trigrams() {
+            self.trigrams
+                .entry(trigram)
+                .or_default()
+                .insert((pokemon.number.clone(), pokemon.form.clone()));
+        }
+    }
+
+    fn unindex(&mut self, pokemon: &Pokemon) {
+        let key = name_key(&pokemon.name);
+        if let Some((_, count)) = self.names.get_mut(&key) {
+            *count -= 1;
+            if *count == 0 {
+                self.names.remove(&key);
+            }
+        }
+        for trigram in pokemon.name.trigrams() {
+            if let Some(keys) = self.trigrams.get_mut(&trigram) {
+                keys.remove(&(pokemon.number.clone(), pokemon.form.clone()));
+                if keys.is_empty() {
+                    self.trigrams.remove(&trigram);
+                }
+            }
+        }
+    }
+
+    // A Pokémon created again after a delete carries on from its tombstone's version, so
+    // validators handed out before the delete never match it.
+    fn reborn(&self, pokemon: Pokemon) -> Pokemon {
+        match self
+            .trash
+            .get(&(pokemon.number.clone(), pokemon.form.clone()))
+        {
+            Some(tombstone) => Pokemon {
+                version: tombstone.pokemon.version + 1,
+                ..pokemon
+            },
+            None => pokemon,
+        }
+    }
+
+    fn insert(&mut self, pokemon: Pokemon) {
+        self.index(&pokemon);
+        self.pokemons.push(pokemon);
+    }
+
+    fn replace(&mut self, idx: usize, pokemon: Pokemon) {
+        self.index(&pokemon);
+        let old = std::mem::replace(&mut self.pokemons[idx], pokemon);
+        self.unindex(&old);
+    }
+
+    fn remove(&mut self, idx: usize) -> Pokemon {
+        let pokemon = self.pokemons.remove(idx);
+        self.unindex(&pokemon);
+        pokemon
+    }
+
+    fn discard(&mut self, idx: usize, deleted_at: u64) -> Pokemon {
+        let pokemon = self.remove(idx);
+        self.trash.insert(
+            (pokemon.number.clone(), pokemon.form.clone()),
+            Tombstone {
+                pokemon: pokemon.clone(),
+                deleted_at,
+            },
+        );
+        pokemon
+    }
+
+    // Returns the Pokémon as it will be once restored, a version past its tombstone.
+    fn check_restore(
+        &self,
+        number: &PokemonNumber,
+        form: &PokemonForm,
+    ) -> Result<Pokemon, RestoreError> {
+        let tombstone = match self.trash.get(&(number.clone(), form.clone())) {
+            Some(tombstone) => tombstone,
+            None => return Err(RestoreError::NotFound),
+        };
+        let pokemon = Pokemon {
+            version: tombstone.pokemon.version + 1,
+            ..tombstone.pokemon.clone()
+        };
+        match self.check_insert(&pokemon) {
+            Ok(()) => Ok(pokemon),
+            Err(_) => Err(RestoreError::Conflict),
+        }
+    }
+
+    fn restore(&mut self, pokemon: Pokemon) {
+        self.trash
+            .remove(&(pokemon.number.clone(), pokemon.form.clone()));
+        self.insert(pokemon);
+    }
+
+    fn purge(&mut self, deleted_before: u64) -> usize {
+        let len = self.trash.len();
+        self.trash
+            .retain(|_, tombstone| tombstone.deleted_at >= deleted_before);
+        len - self.trash.len()
+    }
+
+    fn purge_count(&self, deleted_before: u64) -> usize {
+        self.trash
+            .values()
+            .filter(|tombstone| tombstone.deleted_at < deleted_before)
+            .count()
+    }
+
+    fn trashed(&self) -> Vec<Tombstone> {
+        self.trash.values().cloned().collect()
+    }
+
+    fn insert_many(
+        &mut self,
+        pokemons: Vec<Pokemon>,
+        mode: BulkMode,
+    ) -> Result<Vec<Result<Pokemon, InsertError>>, InsertManyError> {
+        let len = self.pokemons.len();
+        let mut results = Vec::with_capacity(pokemons.len());
+        for (i, pokemon) in pokemons.into_iter().enumerate() {
+            let pokemon = self.reborn(pokemon);
+            match self.check_insert(&pokemon) {
+                Ok(()) => {
+                    self.insert(pokemon.clone());
+                    results.push(Ok(pokemon));
+                }
+                Err(e) if mode == BulkMode::ContinueOnError => results.push(Err(e)),
+                Err(e) => {
+                    self.truncate(len);
+                    return Err(InsertManyError::Rejected(i, e));
+                }
+            }
+        }
+        Ok(results)
+    }
+
+    // Undoes the inserts made since the collection held `len` Pokémon.
+    fn truncate(&mut self, len: usize) {
+        while self.pokemons.len() > len {
+            self.remove(self.pokemons.len() - 1);
+        }
+    }
+
+    // Inserts or replaces without checks, for changes that were validated before being logged.
+    // Replays never trust that the writer checked names, since a bad count corrupts the index.
+    fn upsert(&mut self, pokemon: Pokemon) -> Result<(), InsertError> {
+        if self.name_taken(&pokemon) {
+            return Err(InsertError::Conflict);
+        }
+        match self.position(&pokemon.number, &pokemon.form) {
+            Some(idx) => self.replace(idx, pokemon),
+            None => self.insert(pokemon),
+        }
+        Ok(())
+    }
+
+    fn get(&self, number: &PokemonNumber, form: &PokemonForm) -> Option<Pokemon> {
+        self.position(number, form)
+            .map(|idx| self.pokemons[idx].clone())
+    }
+
+    // Prefers the default form when several forms of the owning number share the name.
+    fn get_by_name(&self, name: &PokemonName) -> Option<Pokemon> {
+        let key = name_key(name);
+        let (number, _) = self.names.get(&key)?;
+        self.pokemons
+            .iter()
+            .filter(|p| p.number == *number && name_key(&p.name) == key)
+            .min_by(|pokemon1, pokemon2| pokemon1.form.cmp(&pokemon2.form))
+            .cloned()
+    }
+
+    fn get_by_trigrams(&self, name: &PokemonName) -> Vec<Pokemon> {
+        let keys: BTreeSet<&(PokemonNumber, PokemonForm)> = name
+            .trigrams()
+            .iter()
+            .filter_map(|trigram| self.trigrams.get(trigram))
+            .flatten()
+            .collect();
+        keys.into_iter()
+            .filter_map(|(number, form)| self.get(number, form))
+            .collect()
+    }
+
+    fn all(&self) -> Vec<Pokemon> {
+        let mut pokemons = self.pokemons.to_vec();
+        pokemons.sort_by(|pokemon1, pokemon2| {
+            (&pokemon1.number, &pokemon1.form).cmp(&(&pokemon2.number, &pokemon2.form))
+        });
+        pokemons
+    }
+
+    fn forms(&self, number: &PokemonNumber) -> Vec<Pokemon> {
+        let mut forms: Vec<Pokemon> = self
+            .pokemons
+            .iter()
+            .filter(|pokemon| pokemon.number == *number)
+            .cloned()
+            .collect();
+        forms.sort_by(|pokemon1, pokemon2| pokemon1.form.cmp(&pokemon2.form));
+        forms
+    }
 }
 
+#[derive(Default)]
 pub struct InMemoryRepository {
-    pokemons: Mutex<Vec<Pokemon>>,
+    pokemons: Mutex<Collection>,
     error: bool,
 }
 
 impl InMemoryRepository {
     pub fn new() -> Self {
-        let pokemons: Mutex<Vec<Pokemon>> = Mutex::new(vec![]);
+        let pokemons: Mutex<Collection> = Mutex::new(Collection::default());
         Self {
             pokemons,
             error: false,
         }
     }
 
+    #[cfg(test)]
     pub fn with_error(mut self) -> Self {
         self.error = true;
         self
@@ -57,8 +508,11 @@ impl Repository for InMemoryRepository {
     fn insert(
         &self,
         number: PokemonNumber,
+        form: PokemonForm,
         name: PokemonName,
         types: PokemonTypes,
+        stats: Option<BaseStats>,
+        abilities: PokemonAbilities,
     ) -> Result<Pokemon, InsertError> {
         if self.error {
             return Err(InsertError::Unknown);
@@ -69,34 +523,77 @@ impl Repository for InMemoryRepository {
             _ => return Err(InsertError::Unknown),
         };
 
-        if pokemons.iter().any(|pokemon| pokemon.number == number) {
-            Err(InsertError::Conflict)
-        } else {
-            let pokemon = Pokemon {
-                number,
-                name,
-                types,
-            };
-            pokemons.push(pokemon.clone());
+        let pokemon = pokemons.reborn(Pokemon::new(number, form, name, types, stats, abilities));
+        pokemons.check_insert(&pokemon)?;
+        pokemons.insert(pokemon.clone());
+
+        Ok(pokemon)
+    }
 
-            Ok(pokemon)
+    fn insert_many(
+        &self,
+        pokemons: Vec<Pokemon>,
+        mode: BulkMode,
+    ) -> Result<Vec<Result<Pokemon, InsertError>>, InsertManyError> {
+        if self.error {
+            return Err(InsertManyError::Unknown);
+        }
+
+        match self.pokemons.lock() {
+            Ok(mut lock) => lock.insert_many(pokemons, mode),
+            _ => Err(InsertManyError::Unknown),
         }
     }
+
     fn fetch_all(&self) -> Result<Vec<Pokemon>, FetchAllError> {
         if self.error {
             return Err(FetchAllError::Unknown);
         }
 
+        match self.pokemons.lock() {
+            Ok(pokemons) => Ok(pokemons.all()),
+            _ => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn fetch(&self, number: PokemonNumber, form: &PokemonForm) -> Result<Pokemon, FetchError> {
+        if self.error {
+            return Err(FetchError::Unknown);
+        }
+
         let pokemons = match self.pokemons.lock() {
             Ok(lock) => lock,
-            _ => return Err(FetchAllError::Unknown),
+            _ => return Err(FetchError::Unknown),
+        };
+
+        pokemons.get(&number, form).ok_or(FetchError::NotFound)
+    }
+
+    fn fetch_by_name(&self, name: &PokemonName) -> Result<Pokemon, FetchError> {
+        if self.error {
+            return Err(FetchError::Unknown);
+        }
+
+        let pokemons = match self.pokemons.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchError::Unknown),
         };
-        let mut pokemons = pokemons.to_vec();
-        pokemons.sort_by(|pokemon1, pokemon2| pokemon1.number.cmp(&pokemon2.number));
-        Ok(pokemons)
+
+        pokemons.get_by_name(name).ok_or(FetchError::NotFound)
+    }
+
+    fn fetch_by_trigrams(&self, name: &PokemonName) -> Result<Vec<Pokemon>, FetchAllError> {
+        if self.error {
+            return Err(FetchAllError::Unknown);
+        }
+
+        match self.pokemons.lock() {
+            Ok(pokemons) => Ok(pokemons.get_by_trigrams(name)),
+            _ => Err(FetchAllError::Unknown),
+        }
     }
 
-    fn fetch(&self, number: PokemonNumber) -> Result<Pokemon, FetchError> {
+    fn fetch_forms(&self, number: PokemonNumber) -> Result<Vec<Pokemon>, FetchError> {
         if self.error {
             return Err(FetchError::Unknown);
         }
@@ -106,14 +603,35 @@ impl Repository for InMemoryRepository {
             _ => return Err(FetchError::Unknown),
         };
 
-        let mut iter = pokemons.iter().filter(|pokemon| pokemon.number == number);
-        match iter.next() {
-            Some(pokemon) => Ok(pokemon.clone()),
-            _ => Err(FetchError::NotFound),
+        match pokemons.forms(&number) {
+            forms if forms.is_empty() => Err(FetchError::NotFound),
+            forms => Ok(forms),
+        }
+    }
+
+    fn update(&self, pokemon: Pokemon, version: Option<u64>) -> Result<Pokemon, UpdateError> {
+        if self.error {
+            return Err(UpdateError::Unknown);
         }
+
+        let mut pokemons = match self.pokemons.lock() {
+            Ok(lock) => lock,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        let (idx, version) = pokemons.check_update(&pokemon, version)?;
+        let pokemon = Pokemon { version, ..pokemon };
+        pokemons.replace(idx, pokemon.clone());
+        Ok(pokemon)
     }
 
-    fn delete(&self, number: PokemonNumber) -> Result<Pokemon, DeleteError> {
+    fn delete(
+        &self,
+        number: PokemonNumber,
+        form: &PokemonForm,
+        version: Option<u64>,
+        deleted_at: u64,
+    ) -> Result<Pokemon, DeleteError> {
         if self.error {
             return Err(DeleteError::Unknown);
         }
@@ -123,9 +641,2346 @@ impl Repository for InMemoryRepository {
             _ => return Err(DeleteError::Unknown),
         };
 
-        match pokemons.iter().position(|p| p.number == number) {
-            Some(idx) => Ok(pokemons.remove(idx)),
-            None => Err(DeleteError::NotFound),
+        let idx = pokemons.check_delete(&number, form, version)?;
+        Ok(pokemons.discard(idx, deleted_at))
+    }
+
+    fn fetch_trash(&self) -> Result<Vec<Tombstone>, FetchAllError> {
+        if self.error {
+            return Err(FetchAllError::Unknown);
+        }
+
+        match self.pokemons.lock() {
+            Ok(pokemons) => Ok(pokemons.trashed()),
+            _ => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn restore(&self, number: PokemonNumber, form: &PokemonForm) -> Result<Pokemon, RestoreError> {
+        if self.error {
+            return Err(RestoreError::Unknown);
+        }
+
+        let mut pokemons = match self.pokemons.lock() {
+            Ok(lock) => lock,
+            _ => return Err(RestoreError::Unknown),
+        };
+
+        let pokemon = pokemons.check_restore(&number, form)?;
+        pokemons.restore(pokemon.clone());
+        Ok(pokemon)
+    }
+
+    fn purge_trash(&self, deleted_before: u64) -> Result<usize, PurgeError> {
+        if self.error {
+            return Err(PurgeError::Unknown);
         }
+
+        match self.pokemons.lock() {
+            Ok(mut pokemons) => Ok(pokemons.purge(deleted_before)),
+            _ => Err(PurgeError::Unknown),
+        }
+    }
+}
+
+enum Migration {
+    Sql(&'static str),
+    Code(fn(&Connection) -> rusqlite::Result<()>),
+}
+
+// Each entry upgrades the schema by one version; `PRAGMA user_version` records how many ran.
+const MIGRATIONS: &[Migration] = &[
+    Migration::Sql(
+        "CREATE TABLE pokemons (
+        number INTEGER NOT NULL,
+        form TEXT NOT NULL DEFAULT '',
+        name TEXT NOT NULL,
+        types TEXT NOT NULL,
+        stats TEXT,
+        abilities TEXT NOT NULL,
+        hidden_ability TEXT,
+        PRIMARY KEY (number, form)
+    )",
+    ),
+    Migration::Sql("CREATE INDEX pokemons_name ON pokemons (name COLLATE NOCASE)"),
+    Migration::Code(create_trigram_index),
+    Migration::Sql("ALTER TABLE pokemons ADD COLUMN version INTEGER NOT NULL DEFAULT 1"),
+    Migration::Sql(
+        "CREATE TABLE pokemon_trash (
+        number INTEGER NOT NULL,
+        form TEXT NOT NULL DEFAULT '',
+        name TEXT NOT NULL,
+        types TEXT NOT NULL,
+        stats TEXT,
+        abilities TEXT NOT NULL,
+        hidden_ability TEXT,
+        version INTEGER NOT NULL,
+        deleted_at INTEGER NOT NULL,
+        PRIMARY KEY (number, form)
+    )",
+    ),
+    Migration::Code(enforce_name_owners),
+    Migration::Sql(
+        "CREATE TABLE abilities (
+        name TEXT NOT NULL PRIMARY KEY COLLATE NOCASE,
+        description TEXT NOT NULL,
+        generation INTEGER NOT NULL
+    )",
+    ),
+];
+
+// A name belongs to a single number, which no index can express since forms share names.
+// Triggers hold every writer to it, including other processes opening the same database.
+fn enforce_name_owners(connection: &Connection) -> rusqlite::Result<()> {
+    let shared = connection
+        .query_row(
+            "SELECT a.name FROM pokemons a JOIN pokemons b
+            ON a.name = b.name COLLATE NOCASE AND a.number < b.number
+            LIMIT 1",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()?;
+    if let Some(name) = shared {
+        return Err(rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_CONSTRAINT),
+            Some(format!(
+                "several numbers are named \"{}\", rename them before upgrading",
+                name
+            )),
+        ));
+    }
+    connection.execute_batch(
+        "CREATE TRIGGER pokemons_name_insert BEFORE INSERT ON pokemons
+        WHEN EXISTS (
+            SELECT 1 FROM pokemons WHERE name = NEW.name COLLATE NOCASE AND number != NEW.number
+        )
+        BEGIN SELECT RAISE(ABORT, 'name taken by another number'); END;
+        CREATE TRIGGER pokemons_name_update BEFORE UPDATE OF name, number ON pokemons
+        WHEN EXISTS (
+            SELECT 1 FROM pokemons WHERE name = NEW.name COLLATE NOCASE AND number != NEW.number
+        )
+        BEGIN SELECT RAISE(ABORT, 'name taken by another number'); END;",
+    )
+}
+
+fn create_trigram_index(connection: &Connection) -> rusqlite::Result<()> {
+    connection.execute_batch(
+        "CREATE TABLE pokemon_trigrams (
+            trigram TEXT NOT NULL,
+            number INTEGER NOT NULL,
+            form TEXT NOT NULL,
+            PRIMARY KEY (trigram, number, form)
+        )",
+    )?;
+    let rows = connection
+        .prepare("SELECT number, form, name FROM pokemons")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+        .collect::<rusqlite::Result<Vec<(u16, String, String)>>>()?;
+    for (number, form, name) in rows {
+        if let Ok(name) = PokemonName::try_from(name) {
+            reindex_trigrams(connection, number, &form, Some(&name))?;
+        }
+    }
+    Ok(())
+}
+
+// Replaces the trigram rows of one Pokémon; `None` just drops them.
+fn reindex_trigrams(
+    connection: &Connection,
+    number: u16,
+    form: &str,
+    name: Option<&PokemonName>,
+) -> rusqlite::Result<()> {
+    connection.execute(
+        "DELETE FROM pokemon_trigrams WHERE number = ?1 AND form = ?2",
+        params![number, form],
+    )?;
+    for trigram in name.map(PokemonName::trigrams).unwrap_or_default() {
+        connection.execute(
+            "INSERT INTO pokemon_trigrams (trigram, number, form) VALUES (?1, ?2, ?3)",
+            params![trigram, number, form],
+        )?;
+    }
+    Ok(())
+}
+
+// Names are unique per number, ignoring ASCII case, but forms of one number may share them.
+const NAME_TAKEN: &str = "SELECT EXISTS (
+    SELECT 1 FROM pokemons WHERE name = ?1 COLLATE NOCASE AND number != ?2
+)";
+
+const COLUMNS: &str = "number, form, name, types, stats, abilities, hidden_ability, version";
+
+// Returns the Pokémon as stored, its version seeded past any tombstone like `Collection::reborn`.
+fn insert_row(connection: &Connection, pokemon: Pokemon) -> Result<Pokemon, InsertError> {
+    let mut row = PokemonRow::from(pokemon.clone());
+    match connection.query_row(NAME_TAKEN, params![row.name, row.number], |r| r.get(0)) {
+        Ok(false) => {}
+        Ok(true) => return Err(InsertError::Conflict),
+        Err(_) => return Err(InsertError::Unknown),
+    }
+    let trashed = connection
+        .query_row(
+            "SELECT version FROM pokemon_trash WHERE number = ?1 AND form = ?2",
+            params![row.number, row.form],
+            |r| r.get::<_, i64>(0),
+        )
+        .optional();
+    match trashed {
+        Ok(Some(version)) => row.version = version + 1,
+        Ok(None) => {}
+        Err(_) => return Err(InsertError::Unknown),
+    }
+    match connection.execute(
+        &format!(
+            "INSERT INTO pokemons ({}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            COLUMNS
+        ),
+        params![
+            row.number,
+            row.form,
+            row.name,
+            row.types,
+            row.stats,
+            row.abilities,
+            row.hidden_ability,
+            row.version
+        ],
+    ) {
+        Ok(_) => {}
+        Err(rusqlite::Error::SqliteFailure(e, _)) if e.code == ErrorCode::ConstraintViolation => {
+            return Err(InsertError::Conflict)
+        }
+        Err(_) => return Err(InsertError::Unknown),
+    }
+    reindex_trigrams(connection, row.number, &row.form, Some(&pokemon.name))
+        .map_err(|_| InsertError::Unknown)?;
+    Ok(Pokemon {
+        version: row.version as u64,
+        ..pokemon
+    })
+}
+
+fn stored_version(
+    connection: &Connection,
+    number: u16,
+    form: &str,
+) -> rusqlite::Result<Option<u64>> {
+    connection
+        .query_row(
+            "SELECT version FROM pokemons WHERE number = ?1 AND form = ?2",
+            params![number, form],
+            |row| row.get::<_, i64>(0),
+        )
+        .optional()
+        .map(|version| version.map(|version| version as u64))
+}
+
+pub struct SqliteRepository {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteRepository {
+    pub fn open(path: &str) -> rusqlite::Result<Self> {
+        let mut connection = Connection::open(path)?;
+        Self::migrate(&mut connection)?;
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
+    // The ability catalogue in the same database, sharing this connection.
+    pub fn abilities(&self) -> ability::SqliteRepository {
+        ability::SqliteRepository::new(self.connection.clone())
+    }
+
+    // A database written by a newer binary is left alone rather than downgraded.
+    fn migrate(connection: &mut Connection) -> rusqlite::Result<()> {
+        let version: usize = connection.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        if version > MIGRATIONS.len() {
+            return Err(rusqlite::Error::SqliteFailure(
+                rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_CANTOPEN),
+                Some(format!(
+                    "schema version {} is newer than the {} this build knows",
+                    version,
+                    MIGRATIONS.len()
+                )),
+            ));
+        }
+        let tx = connection.transaction()?;
+        for (i, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+            match migration {
+                Migration::Sql(sql) => tx.execute_batch(sql)?,
+                Migration::Code(migrate) => migrate(&tx)?,
+            }
+            tx.pragma_update(None, "user_version", i + 1)?;
+        }
+        tx.commit()
+    }
+}
+
+struct PokemonRow {
+    number: u16,
+    form: String,
+    name: String,
+    types: String,
+    stats: Option<String>,
+    abilities: String,
+    hidden_ability: Option<String>,
+    version: i64,
+}
+
+impl PokemonRow {
+    fn read(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            number: row.get(0)?,
+            form: row.get(1)?,
+            name: row.get(2)?,
+            types: row.get(3)?,
+            stats: row.get(4)?,
+            abilities: row.get(5)?,
+            hidden_ability: row.get(6)?,
+            version: row.get(7)?,
+        })
+    }
+}
+
+impl From<Pokemon> for PokemonRow {
+    fn from(pokemon: Pokemon) -> Self {
+        let (abilities, hidden_ability) = pokemon.abilities.into();
+        Self {
+            number: pokemon.number.into(),
+            form: Option::<String>::from(pokemon.form).unwrap_or_default(),
+            name: pokemon.name.into(),
+            types: serde_json::to_string(&Vec::<String>::from(pokemon.types))
+                .expect("types to serialize"),
+            stats: pokemon.stats.map(|stats| {
+                serde_json::to_string(&<[u16; 6]>::from(stats)).expect("stats to serialize")
+            }),
+            abilities: serde_json::to_string(&abilities).expect("abilities to serialize"),
+            hidden_ability,
+            version: pokemon.version as i64,
+        }
+    }
+}
+
+impl TryFrom<PokemonRow> for Pokemon {
+    type Error = ();
+
+    fn try_from(row: PokemonRow) -> Result<Self, Self::Error> {
+        let types: Vec<String> = serde_json::from_str(&row.types).map_err(|_| ())?;
+        let stats = match row.stats {
+            Some(stats) => Some(
+                serde_json::from_str::<[u16; 6]>(&stats)
+                    .map_err(|_| ())
+                    .and_then(|stats| BaseStats::try_from(stats).map_err(|_| ()))?,
+            ),
+            None => None,
+        };
+        let abilities: Vec<String> = serde_json::from_str(&row.abilities).map_err(|_| ())?;
+
+        Ok(Pokemon {
+            version: u64::try_from(row.version).map_err(|_| ())?,
+            ..Pokemon::new(
+                PokemonNumber::from_stored(row.number),
+                PokemonForm::try_from(row.form).map_err(|_| ())?,
+                PokemonName::try_from(row.name).map_err(|_| ())?,
+                PokemonTypes::try_from(types).map_err(|_| ())?,
+                stats,
+                PokemonAbilities::try_from((abilities, row.hidden_ability)).map_err(|_| ())?,
+            )
+        })
+    }
+}
+
+impl Repository for SqliteRepository {
+    fn insert(
+        &self,
+        number: PokemonNumber,
+        form: PokemonForm,
+        name: PokemonName,
+        types: PokemonTypes,
+        stats: Option<BaseStats>,
+        abilities: PokemonAbilities,
+    ) -> Result<Pokemon, InsertError> {
+        let connection = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(InsertError::Unknown),
+        };
+
+        let pokemon = Pokemon::new(number, form, name, types, stats, abilities);
+        let tx = match connection.unchecked_transaction() {
+            Ok(tx) => tx,
+            _ => return Err(InsertError::Unknown),
+        };
+        let pokemon = insert_row(&tx, pokemon)?;
+        match tx.commit() {
+            Ok(()) => Ok(pokemon),
+            Err(_) => Err(InsertError::Unknown),
+        }
+    }
+
+    fn insert_many(
+        &self,
+        pokemons: Vec<Pokemon>,
+        mode: BulkMode,
+    ) -> Result<Vec<Result<Pokemon, InsertError>>, InsertManyError> {
+        let connection = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(InsertManyError::Unknown),
+        };
+
+        // A failed statement only undoes itself, so rejected items leave the rest of the
+        // transaction intact; dropping it without a commit rolls everything back.
+        let tx = match connection.unchecked_transaction() {
+            Ok(tx) => tx,
+            _ => return Err(InsertManyError::Unknown),
+        };
+        let mut results = Vec::with_capacity(pokemons.len());
+        for (i, pokemon) in pokemons.into_iter().enumerate() {
+            match insert_row(&tx, pokemon) {
+                Ok(pokemon) => results.push(Ok(pokemon)),
+                Err(InsertError::Unknown) => return Err(InsertManyError::Unknown),
+                Err(e) if mode == BulkMode::ContinueOnError => results.push(Err(e)),
+                Err(e) => return Err(InsertManyError::Rejected(i, e)),
+            }
+        }
+        match tx.commit() {
+            Ok(()) => Ok(results),
+            Err(_) => Err(InsertManyError::Unknown),
+        }
+    }
+
+    fn fetch_all(&self) -> Result<Vec<Pokemon>, FetchAllError> {
+        let connection = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let rows = connection
+            .prepare(&format!(
+                "SELECT {} FROM pokemons ORDER BY number, form",
+                COLUMNS
+            ))
+            .and_then(|mut stmt| {
+                stmt.query_map([], PokemonRow::read)?
+                    .collect::<rusqlite::Result<Vec<_>>>()
+            });
+        match rows {
+            Ok(rows) => rows
+                .into_iter()
+                .map(Pokemon::try_from)
+                .collect::<Result<_, _>>()
+                .map_err(|_| FetchAllError::Unknown),
+            Err(_) => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn query(&self, query: &Query) -> Result<Page, FetchAllError> {
+        let connection = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let mut conditions = vec![];
+        let mut values: Vec<Box<dyn ToSql>> = vec![];
+        if let Some(t) = query.pokemon_type {
+            conditions.push("EXISTS (SELECT 1 FROM json_each(types) WHERE value = ?)");
+            values.push(Box::new(t.as_str()));
+        }
+        if let Some(numbers) = &query.numbers {
+            conditions.push("number BETWEEN ? AND ?");
+            values.push(Box::new(*numbers.start()));
+            values.push(Box::new(*numbers.end()));
+        }
+        if let Some(name) = &query.name {
+            let escape = |s: &str| {
+                s.replace('\\', "\\\\")
+                    .replace('%', "\\%")
+                    .replace('_', "\\_")
+            };
+            conditions.push("name LIKE ? ESCAPE '\\'");
+            values.push(Box::new(match name {
+                NameFilter::Prefix(prefix) => format!("{}%", escape(prefix)),
+                NameFilter::Contains(part) => format!("%{}%", escape(part)),
+            }));
+        }
+        let filter = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+        let direction = if query.descending { "DESC" } else { "ASC" };
+        let order = match query.sort {
+            SortKey::Number => format!("number {0}, form {0}", direction),
+            SortKey::Name => format!("name COLLATE NOCASE {0}, number {0}, form {0}", direction),
+        };
+        let limit = query.limit.map_or(-1, |limit| limit as i64);
+        let params = rusqlite::params_from_iter(values.iter());
+
+        let total = connection.query_row(
+            &format!("SELECT COUNT(*) FROM pokemons {}", filter),
+            params.clone(),
+            |row| row.get::<_, usize>(0),
+        );
+        let rows = connection
+            .prepare(&format!(
+                "SELECT {} FROM pokemons {} ORDER BY {} LIMIT {} OFFSET {}",
+                COLUMNS, filter, order, limit, query.offset
+            ))
+            .and_then(|mut stmt| {
+                stmt.query_map(params, PokemonRow::read)?
+                    .collect::<rusqlite::Result<Vec<_>>>()
+            });
+        match (total, rows) {
+            (Ok(total), Ok(rows)) => rows
+                .into_iter()
+                .map(Pokemon::try_from)
+                .collect::<Result<_, _>>()
+                .map(|pokemons| Page { pokemons, total })
+                .map_err(|_| FetchAllError::Unknown),
+            _ => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn fetch(&self, number: PokemonNumber, form: &PokemonForm) -> Result<Pokemon, FetchError> {
+        let connection = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchError::Unknown),
+        };
+
+        let row = connection
+            .query_row(
+                &format!(
+                    "SELECT {} FROM pokemons WHERE number = ?1 AND form = ?2",
+                    COLUMNS
+                ),
+                params![
+                    u16::from(number),
+                    Option::<String>::from(form.clone()).unwrap_or_default()
+                ],
+                PokemonRow::read,
+            )
+            .optional();
+        match row {
+            Ok(Some(row)) => Pokemon::try_from(row).map_err(|_| FetchError::Unknown),
+            Ok(None) => Err(FetchError::NotFound),
+            Err(_) => Err(FetchError::Unknown),
+        }
+    }
+
+    fn fetch_by_name(&self, name: &PokemonName) -> Result<Pokemon, FetchError> {
+        let connection = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchError::Unknown),
+        };
+
+        let row = connection
+            .query_row(
+                &format!(
+                    "SELECT {} FROM pokemons WHERE name = ?1 COLLATE NOCASE
+                    ORDER BY number, form LIMIT 1",
+                    COLUMNS
+                ),
+                [name.as_str()],
+                PokemonRow::read,
+            )
+            .optional();
+        match row {
+            Ok(Some(row)) => Pokemon::try_from(row).map_err(|_| FetchError::Unknown),
+            Ok(None) => Err(FetchError::NotFound),
+            Err(_) => Err(FetchError::Unknown),
+        }
+    }
+
+    fn fetch_by_trigrams(&self, name: &PokemonName) -> Result<Vec<Pokemon>, FetchAllError> {
+        let connection = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let trigrams: Vec<String> = name.trigrams().into_iter().collect();
+        let placeholders = vec!["?"; trigrams.len()].join(", ");
+        let rows = connection
+            .prepare(&format!(
+                "SELECT {} FROM pokemons WHERE (number, form) IN (
+                    SELECT number, form FROM pokemon_trigrams WHERE trigram IN ({})
+                ) ORDER BY number, form",
+                COLUMNS, placeholders
+            ))
+            .and_then(|mut stmt| {
+                stmt.query_map(
+                    rusqlite::params_from_iter(trigrams.iter()),
+                    PokemonRow::read,
+                )?
+                .collect::<rusqlite::Result<Vec<_>>>()
+            });
+        match rows {
+            Ok(rows) => rows
+                .into_iter()
+                .map(Pokemon::try_from)
+                .collect::<Result<_, _>>()
+                .map_err(|_| FetchAllError::Unknown),
+            Err(_) => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn fetch_forms(&self, number: PokemonNumber) -> Result<Vec<Pokemon>, FetchError> {
+        let connection = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchError::Unknown),
+        };
+
+        let rows = connection
+            .prepare(&format!(
+                "SELECT {} FROM pokemons WHERE number = ?1 ORDER BY form",
+                COLUMNS
+            ))
+            .and_then(|mut stmt| {
+                stmt.query_map([u16::from(number)], PokemonRow::read)?
+                    .collect::<rusqlite::Result<Vec<_>>>()
+            });
+        match rows {
+            Ok(rows) if rows.is_empty() => Err(FetchError::NotFound),
+            Ok(rows) => rows
+                .into_iter()
+                .map(Pokemon::try_from)
+                .collect::<Result<_, _>>()
+                .map_err(|_| FetchError::Unknown),
+            Err(_) => Err(FetchError::Unknown),
+        }
+    }
+
+    fn update(&self, pokemon: Pokemon, version: Option<u64>) -> Result<Pokemon, UpdateError> {
+        let connection = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        let tx = match connection.unchecked_transaction() {
+            Ok(tx) => tx,
+            _ => return Err(UpdateError::Unknown),
+        };
+        let (number, form) = (
+            u16::from(pokemon.number.clone()),
+            Option::<String>::from(pokemon.form.clone()).unwrap_or_default(),
+        );
+        let pokemon = match stored_version(&tx, number, &form) {
+            Ok(Some(stored)) if version.is_some_and(|v| v != stored) => {
+                return Err(UpdateError::VersionMismatch)
+            }
+            Ok(Some(stored)) => Pokemon {
+                version: stored + 1,
+                ..pokemon
+            },
+            Ok(None) => return Err(UpdateError::NotFound),
+            Err(_) => return Err(UpdateError::Unknown),
+        };
+        let row = PokemonRow::from(pokemon.clone());
+        match tx.query_row(NAME_TAKEN, params![row.name, row.number], |r| r.get(0)) {
+            Ok(false) => {}
+            Ok(true) => return Err(UpdateError::Conflict),
+            Err(_) => return Err(UpdateError::Unknown),
+        }
+        match tx.execute(
+            "UPDATE pokemons
+            SET name = ?3, types = ?4, stats = ?5, abilities = ?6, hidden_ability = ?7,
+                version = ?8
+            WHERE number = ?1 AND form = ?2",
+            params![
+                row.number,
+                row.form,
+                row.name,
+                row.types,
+                row.stats,
+                row.abilities,
+                row.hidden_ability,
+                row.version
+            ],
+        ) {
+            Ok(0) => return Err(UpdateError::NotFound),
+            Ok(_) => {}
+            Err(rusqlite::Error::SqliteFailure(e, _))
+                if e.code == ErrorCode::ConstraintViolation =>
+            {
+                return Err(UpdateError::Conflict)
+            }
+            Err(_) => return Err(UpdateError::Unknown),
+        }
+        match reindex_trigrams(&tx, row.number, &row.form, Some(&pokemon.name))
+            .and_then(|_| tx.commit())
+        {
+            Ok(()) => Ok(pokemon),
+            Err(_) => Err(UpdateError::Unknown),
+        }
+    }
+
+    fn delete(
+        &self,
+        number: PokemonNumber,
+        form: &PokemonForm,
+        version: Option<u64>,
+        deleted_at: u64,
+    ) -> Result<Pokemon, DeleteError> {
+        let connection = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(DeleteError::Unknown),
+        };
+
+        let tx = match connection.unchecked_transaction() {
+            Ok(tx) => tx,
+            _ => return Err(DeleteError::Unknown),
+        };
+        let (number, form) = (
+            u16::from(number),
+            Option::<String>::from(form.clone()).unwrap_or_default(),
+        );
+        match stored_version(&tx, number, &form) {
+            Ok(Some(stored)) if version.is_some_and(|v| v != stored) => {
+                return Err(DeleteError::VersionMismatch)
+            }
+            Ok(Some(_)) => {}
+            Ok(None) => return Err(DeleteError::NotFound),
+            Err(_) => return Err(DeleteError::Unknown),
+        }
+        let row = tx
+            .query_row(
+                &format!(
+                    "DELETE FROM pokemons WHERE number = ?1 AND form = ?2 RETURNING {}",
+                    COLUMNS
+                ),
+                params![number, form],
+                PokemonRow::read,
+            )
+            .optional();
+        let row = match row {
+            Ok(Some(row)) => row,
+            Ok(None) => return Err(DeleteError::NotFound),
+            Err(_) => return Err(DeleteError::Unknown),
+        };
+        let trashed = tx.execute(
+            &format!(
+                "INSERT OR REPLACE INTO pokemon_trash ({}, deleted_at)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                COLUMNS
+            ),
+            params![
+                row.number,
+                row.form,
+                row.name,
+                row.types,
+                row.stats,
+                row.abilities,
+                row.hidden_ability,
+                row.version,
+                deleted_at as i64
+            ],
+        );
+        match trashed
+            .and_then(|_| reindex_trigrams(&tx, number, &form, None))
+            .and_then(|_| tx.commit())
+        {
+            Ok(()) => Pokemon::try_from(row).map_err(|_| DeleteError::Unknown),
+            Err(_) => Err(DeleteError::Unknown),
+        }
+    }
+
+    fn fetch_trash(&self) -> Result<Vec<Tombstone>, FetchAllError> {
+        let connection = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let rows = connection
+            .prepare(&format!(
+                "SELECT {}, deleted_at FROM pokemon_trash ORDER BY number, form",
+                COLUMNS
+            ))
+            .and_then(|mut stmt| {
+                stmt.query_map([], |row| {
+                    Ok((PokemonRow::read(row)?, row.get::<_, i64>(8)?))
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()
+            });
+        match rows {
+            Ok(rows) => rows
+                .into_iter()
+                .map(|(row, deleted_at)| {
+                    Ok(Tombstone {
+                        pokemon: Pokemon::try_from(row)?,
+                        deleted_at: u64::try_from(deleted_at).map_err(|_| ())?,
+                    })
+                })
+                .collect::<Result<_, ()>>()
+                .map_err(|_| FetchAllError::Unknown),
+            Err(_) => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn restore(&self, number: PokemonNumber, form: &PokemonForm) -> Result<Pokemon, RestoreError> {
+        let connection = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(RestoreError::Unknown),
+        };
+
+        let tx = match connection.unchecked_transaction() {
+            Ok(tx) => tx,
+            _ => return Err(RestoreError::Unknown),
+        };
+        let (number, form) = (
+            u16::from(number),
+            Option::<String>::from(form.clone()).unwrap_or_default(),
+        );
+        let row = tx
+            .query_row(
+                &format!(
+                    "DELETE FROM pokemon_trash WHERE number = ?1 AND form = ?2 RETURNING {}",
+                    COLUMNS
+                ),
+                params![number, form],
+                PokemonRow::read,
+            )
+            .optional();
+        let pokemon = match row.map(|row| row.map(Pokemon::try_from)) {
+            Ok(Some(Ok(pokemon))) => Pokemon {
+                version: pokemon.version + 1,
+                ..pokemon
+            },
+            Ok(Some(Err(()))) | Err(_) => return Err(RestoreError::Unknown),
+            Ok(None) => return Err(RestoreError::NotFound),
+        };
+        let pokemon = match insert_row(&tx, pokemon) {
+            Ok(pokemon) => pokemon,
+            Err(InsertError::Conflict) => return Err(RestoreError::Conflict),
+            Err(_) => return Err(RestoreError::Unknown),
+        };
+        match tx.commit() {
+            Ok(()) => Ok(pokemon),
+            Err(_) => Err(RestoreError::Unknown),
+        }
+    }
+
+    fn purge_trash(&self, deleted_before: u64) -> Result<usize, PurgeError> {
+        let connection = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(PurgeError::Unknown),
+        };
+
+        connection
+            .execute(
+                "DELETE FROM pokemon_trash WHERE deleted_at < ?1",
+                params![deleted_before as i64],
+            )
+            .map_err(|_| PurgeError::Unknown)
+    }
+}
+
+const SNAPSHOT_FILE: &str = "snapshot.json";
+const LOG_FILE: &str = "log.ndjson";
+const LOCK_FILE: &str = "lock";
+const DEFAULT_COMPACT_EVERY: usize = 1000;
+
+// Appends every change to `log.ndjson` and periodically folds the log into `snapshot.json`.
+// Records carry a sequence number so a log that outlived its snapshot is not replayed twice.
+pub struct FileRepository {
+    store: Mutex<FileStore>,
+}
+
+struct FileStore {
+    // Held for as long as the repository is open; the OS releases it if the process dies.
+    _lock: File,
+    dir: PathBuf,
+    log: File,
+    log_len: u64,
+    pokemons: Collection,
+    seq: u64,
+    pending: usize,
+    compact_every: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredPokemon {
+    number: u16,
+    form: Option<String>,
+    name: String,
+    types: Vec<String>,
+    stats: Option<[u16; 6]>,
+    abilities: Vec<String>,
+    hidden_ability: Option<String>,
+    // Missing from data written before Pokémon were versioned.
+    #[serde(default = "first_version")]
+    version: u64,
+}
+
+fn first_version() -> u64 {
+    Pokemon::FIRST_VERSION
+}
+
+impl From<Pokemon> for StoredPokemon {
+    fn from(pokemon: Pokemon) -> Self {
+        let (abilities, hidden_ability) = pokemon.abilities.into();
+        Self {
+            number: pokemon.number.into(),
+            form: pokemon.form.into(),
+            name: pokemon.name.into(),
+            types: Vec::<String>::from(pokemon.types),
+            stats: pokemon.stats.map(<[u16; 6]>::from),
+            abilities,
+            hidden_ability,
+            version: pokemon.version,
+        }
+    }
+}
+
+impl TryFrom<StoredPokemon> for Pokemon {
+    type Error = ();
+
+    fn try_from(p: StoredPokemon) -> Result<Self, Self::Error> {
+        Ok(Pokemon {
+            version: p.version,
+            ..Pokemon::new(
+                PokemonNumber::from_stored(p.number),
+                PokemonForm::try_from(p.form).map_err(|_| ())?,
+                PokemonName::try_from(p.name).map_err(|_| ())?,
+                PokemonTypes::try_from(p.types).map_err(|_| ())?,
+                p.stats
+                    .map(BaseStats::try_from)
+                    .transpose()
+                    .map_err(|_| ())?,
+                PokemonAbilities::try_from((p.abilities, p.hidden_ability)).map_err(|_| ())?,
+            )
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredTombstone {
+    pokemon: StoredPokemon,
+    deleted_at: u64,
+}
+
+impl From<Tombstone> for StoredTombstone {
+    fn from(tombstone: Tombstone) -> Self {
+        Self {
+            pokemon: tombstone.pokemon.into(),
+            deleted_at: tombstone.deleted_at,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    seq: u64,
+    pokemons: Vec<StoredPokemon>,
+    #[serde(default)]
+    trash: Vec<StoredTombstone>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum Record {
+    Insert {
+        seq: u64,
+        pokemon: StoredPokemon,
+    },
+    // A whole bulk insert in one line, so that a torn write drops all of it.
+    InsertMany {
+        seq: u64,
+        pokemons: Vec<StoredPokemon>,
+    },
+    Update {
+        seq: u64,
+        pokemon: StoredPokemon,
+    },
+    // Deletes written before the trash existed have no timestamp and drop the Pokémon for good.
+    Delete {
+        seq: u64,
+        number: u16,
+        form: Option<String>,
+        #[serde(default)]
+        deleted_at: Option<u64>,
+    },
+    Restore {
+        seq: u64,
+        number: u16,
+        form: Option<String>,
+    },
+    Purge {
+        seq: u64,
+        before: u64,
+    },
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+impl FileRepository {
+    pub fn open(dir: impl AsRef<Path>) -> io::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        // Two writers would both hand out the next sequence number and lose records on replay.
+        let lock = File::create(dir.join(LOCK_FILE))?;
+        match lock.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::WouldBlock,
+                    "the data directory is in use by another process",
+                ))
+            }
+            Err(TryLockError::Error(e)) => return Err(e),
+        }
+
+        let mut pokemons = Collection::default();
+        let mut seq = match fs::read(dir.join(SNAPSHOT_FILE)) {
+            Ok(bytes) => {
+                let snapshot: Snapshot = serde_json::from_slice(&bytes)
+                    .map_err(|_| invalid_data("corrupted snapshot"))?;
+                for pokemon in snapshot.pokemons {
+                    let pokemon = Pokemon::try_from(pokemon)
+                        .map_err(|_| invalid_data("invalid pokemon in snapshot"))?;
+                    pokemons
+                        .check_insert(&pokemon)
+                        .map_err(|_| invalid_data("duplicate pokemon or name in snapshot"))?;
+                    pokemons.insert(pokemon);
+                }
+                for tombstone in snapshot.trash {
+                    let pokemon = Pokemon::try_from(tombstone.pokemon)
+                        .map_err(|_| invalid_data("invalid pokemon in snapshot"))?;
+                    pokemons.trash.insert(
+                        (pokemon.number.clone(), pokemon.form.clone()),
+                        Tombstone {
+                            pokemon,
+                            deleted_at: tombstone.deleted_at,
+                        },
+                    );
+                }
+                snapshot.seq
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => 0,
+            Err(e) => return Err(e),
+        };
+
+        let mut log = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(dir.join(LOG_FILE))?;
+        let mut bytes = vec![];
+        log.read_to_end(&mut bytes)?;
+
+        // Only the final record can be torn by a crash; anything unreadable before it is corruption.
+        let mut records = vec![];
+        let mut log_len = 0;
+        let mut lines = bytes.split_inclusive(|b| *b == b'\n').peekable();
+        while let Some(line) = lines.next() {
+            let last = lines.peek().is_none();
+            match serde_json::from_slice::<Record>(line) {
+                Ok(record) if line.ends_with(b"\n") => records.push(record),
+                _ if last => break,
+                _ => return Err(invalid_data("corrupted log record")),
+            }
+            log_len += line.len() as u64;
+        }
+        if log_len < bytes.len() as u64 {
+            log.set_len(log_len)?;
+            log.sync_data()?;
+        }
+
+        let mut pending = 0;
+        for record in records {
+            match record {
+                Record::Insert { seq: s, pokemon } | Record::Update { seq: s, pokemon }
+                    if s > seq =>
+                {
+                    let pokemon = Pokemon::try_from(pokemon)
+                        .map_err(|_| invalid_data("invalid pokemon in log"))?;
+                    pokemons
+                        .upsert(pokemon)
+                        .map_err(|_| invalid_data("name taken by another number in log"))?;
+                    seq = s;
+                }
+                Record::InsertMany {
+                    seq: s,
+                    pokemons: batch,
+                } if s > seq => {
+                    for pokemon in batch {
+                        let pokemon = Pokemon::try_from(pokemon)
+                            .map_err(|_| invalid_data("invalid pokemon in log"))?;
+                        pokemons
+                            .upsert(pokemon)
+                            .map_err(|_| invalid_data("name taken by another number in log"))?;
+                    }
+                    seq = s;
+                }
+                Record::Delete {
+                    seq: s,
+                    number,
+                    form,
+                    deleted_at,
+                } if s > seq => {
+                    let position = match PokemonForm::try_from(form) {
+                        Ok(form) => pokemons.position(&PokemonNumber::from_stored(number), &form),
+                        _ => return Err(invalid_data("invalid key in log")),
+                    };
+                    match (position, deleted_at) {
+                        (Some(idx), Some(deleted_at)) => {
+                            pokemons.discard(idx, deleted_at);
+                        }
+                        (Some(idx), None) => {
+                            pokemons.remove(idx);
+                        }
+                        (None, _) => {}
+                    }
+                    seq = s;
+                }
+                Record::Restore {
+                    seq: s,
+                    number,
+                    form,
+                } if s > seq => {
+                    let restored = match PokemonForm::try_from(form) {
+                        Ok(form) => {
+                            pokemons.check_restore(&PokemonNumber::from_stored(number), &form)
+                        }
+                        _ => return Err(invalid_data("invalid key in log")),
+                    };
+                    if let Ok(pokemon) = restored {
+                        pokemons.restore(pokemon);
+                    }
+                    seq = s;
+                }
+                Record::Purge { seq: s, before } if s > seq => {
+                    pokemons.purge(before);
+                    seq = s;
+                }
+                _ => continue,
+            }
+            pending += 1;
+        }
+
+        Ok(Self {
+            store: Mutex::new(FileStore {
+                _lock: lock,
+                dir,
+                log,
+                log_len,
+                pokemons,
+                seq,
+                pending,
+                compact_every: DEFAULT_COMPACT_EVERY,
+            }),
+        })
+    }
+
+    // The ability catalogue in the same data directory, covered by this repository's lock.
+    pub fn abilities(&self) -> io::Result<ability::FileRepository> {
+        match self.store.lock() {
+            Ok(store) => ability::FileRepository::open(&store.dir),
+            _ => Err(io::Error::other("the repository lock is poisoned")),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn compact_every(self, records: usize) -> Self {
+        if let Ok(mut store) = self.store.lock() {
+            store.compact_every = records.max(1);
+        }
+        self
+    }
+}
+
+impl FileStore {
+    fn append(&mut self, record: &Record) -> io::Result<()> {
+        let mut line = serde_json::to_vec(record).map_err(io::Error::from)?;
+        line.push(b'\n');
+        let res = self.log.write_all(&line).and_then(|_| self.log.sync_data());
+        match res {
+            Ok(()) => {
+                self.log_len += line.len() as u64;
+                self.pending += 1;
+                Ok(())
+            }
+            Err(e) => {
+                // Drop whatever part of the record made it to disk so later appends stay readable.
+                let _ = self.log.set_len(self.log_len);
+                Err(e)
+            }
+        }
+    }
+
+    fn compact_if_needed(&mut self) {
+        if self.pending >= self.compact_every {
+            // The log already holds every change, so a failed compaction is retried next time.
+            let _ = self.compact();
+        }
+    }
+
+    fn compact(&mut self) -> io::Result<()> {
+        let snapshot = Snapshot {
+            seq: self.seq,
+            pokemons: self
+                .pokemons
+                .all()
+                .into_iter()
+                .map(StoredPokemon::from)
+                .collect(),
+            trash: self
+                .pokemons
+                .trashed()
+                .into_iter()
+                .map(StoredTombstone::from)
+                .collect(),
+        };
+        let tmp = self.dir.join(format!("{}.tmp", SNAPSHOT_FILE));
+        let mut file = File::create(&tmp)?;
+        file.write_all(&serde_json::to_vec(&snapshot).map_err(io::Error::from)?)?;
+        file.sync_all()?;
+        fs::rename(&tmp, self.dir.join(SNAPSHOT_FILE))?;
+        File::open(&self.dir)?.sync_all()?;
+
+        self.log.set_len(0)?;
+        self.log.sync_data()?;
+        self.log_len = 0;
+        self.pending = 0;
+        Ok(())
+    }
+}
+
+impl Repository for FileRepository {
+    fn insert(
+        &self,
+        number: PokemonNumber,
+        form: PokemonForm,
+        name: PokemonName,
+        types: PokemonTypes,
+        stats: Option<BaseStats>,
+        abilities: PokemonAbilities,
+    ) -> Result<Pokemon, InsertError> {
+        let mut store = match self.store.lock() {
+            Ok(lock) => lock,
+            _ => return Err(InsertError::Unknown),
+        };
+
+        let pokemon = store
+            .pokemons
+            .reborn(Pokemon::new(number, form, name, types, stats, abilities));
+        store.pokemons.check_insert(&pokemon)?;
+        let record = Record::Insert {
+            seq: store.seq + 1,
+            pokemon: pokemon.clone().into(),
+        };
+        if store.append(&record).is_err() {
+            return Err(InsertError::Unknown);
+        }
+        store.seq += 1;
+        store.pokemons.insert(pokemon.clone());
+        store.compact_if_needed();
+
+        Ok(pokemon)
+    }
+
+    fn insert_many(
+        &self,
+        pokemons: Vec<Pokemon>,
+        mode: BulkMode,
+    ) -> Result<Vec<Result<Pokemon, InsertError>>, InsertManyError> {
+        let mut store = match self.store.lock() {
+            Ok(lock) => lock,
+            _ => return Err(InsertManyError::Unknown),
+        };
+
+        // Checking against the collection itself catches conflicts inside the batch too;
+        // the inserts are undone if the record cannot be written.
+        let len = store.pokemons.pokemons.len();
+        let results = store.pokemons.insert_many(pokemons, mode)?;
+        // A batch that stored nothing leaves no record behind.
+        if results.iter().all(Result::is_err) {
+            return Ok(results);
+        }
+        let record = Record::InsertMany {
+            seq: store.seq + 1,
+            pokemons: results
+                .iter()
+                .flatten()
+                .cloned()
+                .map(StoredPokemon::from)
+                .collect(),
+        };
+        if store.append(&record).is_err() {
+            store.pokemons.truncate(len);
+            return Err(InsertManyError::Unknown);
+        }
+        store.seq += 1;
+        store.compact_if_needed();
+
+        Ok(results)
+    }
+
+    fn fetch_all(&self) -> Result<Vec<Pokemon>, FetchAllError> {
+        match self.store.lock() {
+            Ok(store) => Ok(store.pokemons.all()),
+            _ => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn fetch(&self, number: PokemonNumber, form: &PokemonForm) -> Result<Pokemon, FetchError> {
+        let store = match self.store.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchError::Unknown),
+        };
+
+        store
+            .pokemons
+            .get(&number, form)
+            .ok_or(FetchError::NotFound)
+    }
+
+    fn fetch_by_name(&self, name: &PokemonName) -> Result<Pokemon, FetchError> {
+        let store = match self.store.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchError::Unknown),
+        };
+
+        store.pokemons.get_by_name(name).ok_or(FetchError::NotFound)
+    }
+
+    fn fetch_by_trigrams(&self, name: &PokemonName) -> Result<Vec<Pokemon>, FetchAllError> {
+        match self.store.lock() {
+            Ok(store) => Ok(store.pokemons.get_by_trigrams(name)),
+            _ => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn fetch_forms(&self, number: PokemonNumber) -> Result<Vec<Pokemon>, FetchError> {
+        let store = match self.store.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchError::Unknown),
+        };
+
+        match store.pokemons.forms(&number) {
+            forms if forms.is_empty() => Err(FetchError::NotFound),
+            forms => Ok(forms),
+        }
+    }
+
+    fn update(&self, pokemon: Pokemon, version: Option<u64>) -> Result<Pokemon, UpdateError> {
+        let mut store = match self.store.lock() {
+            Ok(lock) => lock,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        let (idx, version) = store.pokemons.check_update(&pokemon, version)?;
+        let pokemon = Pokemon { version, ..pokemon };
+        let record = Record::Update {
+            seq: store.seq + 1,
+            pokemon: pokemon.clone().into(),
+        };
+        if store.append(&record).is_err() {
+            return Err(UpdateError::Unknown);
+        }
+        store.seq += 1;
+        store.pokemons.replace(idx, pokemon.clone());
+        store.compact_if_needed();
+
+        Ok(pokemon)
+    }
+
+    fn delete(
+        &self,
+        number: PokemonNumber,
+        form: &PokemonForm,
+        version: Option<u64>,
+        deleted_at: u64,
+    ) -> Result<Pokemon, DeleteError> {
+        let mut store = match self.store.lock() {
+            Ok(lock) => lock,
+            _ => return Err(DeleteError::Unknown),
+        };
+
+        let idx = store.pokemons.check_delete(&number, form, version)?;
+        let record = Record::Delete {
+            seq: store.seq + 1,
+            number: number.into(),
+            form: form.clone().into(),
+            deleted_at: Some(deleted_at),
+        };
+        if store.append(&record).is_err() {
+            return Err(DeleteError::Unknown);
+        }
+        store.seq += 1;
+        let pokemon = store.pokemons.discard(idx, deleted_at);
+        store.compact_if_needed();
+
+        Ok(pokemon)
+    }
+
+    fn fetch_trash(&self) -> Result<Vec<Tombstone>, FetchAllError> {
+        match self.store.lock() {
+            Ok(store) => Ok(store.pokemons.trashed()),
+            _ => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn restore(&self, number: PokemonNumber, form: &PokemonForm) -> Result<Pokemon, RestoreError> {
+        let mut store = match self.store.lock() {
+            Ok(lock) => lock,
+            _ => return Err(RestoreError::Unknown),
+        };
+
+        let pokemon = store.pokemons.check_restore(&number, form)?;
+        let record = Record::Restore {
+            seq: store.seq + 1,
+            number: number.into(),
+            form: form.clone().into(),
+        };
+        if store.append(&record).is_err() {
+            return Err(RestoreError::Unknown);
+        }
+        store.seq += 1;
+        store.pokemons.restore(pokemon.clone());
+        store.compact_if_needed();
+
+        Ok(pokemon)
+    }
+
+    fn purge_trash(&self, deleted_before: u64) -> Result<usize, PurgeError> {
+        let mut store = match self.store.lock() {
+            Ok(lock) => lock,
+            _ => return Err(PurgeError::Unknown),
+        };
+
+        // Nothing to purge is the common case on every sweep; keep it out of the log.
+        if store.pokemons.purge_count(deleted_before) == 0 {
+            return Ok(0);
+        }
+        let record = Record::Purge {
+            seq: store.seq + 1,
+            before: deleted_before,
+        };
+        if store.append(&record).is_err() {
+            return Err(PurgeError::Unknown);
+        }
+        store.seq += 1;
+        let purged = store.pokemons.purge(deleted_before);
+        store.compact_if_needed();
+
+        Ok(purged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn insert_pikachu(repo: &SqliteRepository, form: PokemonForm) -> Result<Pokemon, InsertError> {
+        repo.insert(
+            PokemonNumber::pikachu(),
+            form,
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            Some(BaseStats::pikachu()),
+            PokemonAbilities::pikachu(),
+        )
+    }
+
+    fn pokemon(number: u16, name: &str) -> Pokemon {
+        Pokemon::new(
+            PokemonNumber::try_from(number).unwrap(),
+            PokemonForm::default(),
+            PokemonName::try_from(String::from(name)).unwrap(),
+            PokemonTypes::pikachu(),
+            None,
+            PokemonAbilities::default(),
+        )
+    }
+
+    #[test]
+    fn it_should_insert_many_all_or_nothing_in_sqlite() {
+        let repo = SqliteRepository::open(":memory:").unwrap();
+        let batch = vec![pokemon(25, "Pikachu"), pokemon(26, "PIKACHU")];
+
+        match repo.insert_many(batch, BulkMode::AllOrNothing) {
+            Err(InsertManyError::Rejected(1, InsertError::Conflict)) => {}
+            _ => unreachable!(),
+        }
+        match repo.fetch_all() {
+            Ok(pokemons) => assert!(pokemons.is_empty()),
+            _ => unreachable!(),
+        }
+
+        let batch = vec![
+            pokemon(25, "Pikachu"),
+            pokemon(26, "PIKACHU"),
+            pokemon(26, "Raichu"),
+        ];
+        match repo.insert_many(batch, BulkMode::ContinueOnError) {
+            Ok(results) => {
+                assert!(results[0].is_ok());
+                assert!(matches!(results[1], Err(InsertError::Conflict)));
+                assert!(results[2].is_ok());
+            }
+            _ => unreachable!(),
+        }
+        match repo.fetch_all() {
+            Ok(pokemons) => assert_eq!(pokemons.len(), 2),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_round_trip_a_pokemon_through_sqlite() {
+        let repo = SqliteRepository::open(":memory:").unwrap();
+        insert_pikachu(&repo, PokemonForm::default())
+            .ok()
+            .expect("pokemon to be inserted");
+
+        match repo.fetch(PokemonNumber::pikachu(), &PokemonForm::default()) {
+            Ok(pokemon) => {
+                assert_eq!(
+                    String::from(pokemon.name),
+                    String::from(PokemonName::pikachu())
+                );
+                assert_eq!(
+                    Vec::<String>::from(pokemon.types),
+                    Vec::<String>::from(PokemonTypes::pikachu())
+                );
+                assert_eq!(pokemon.stats, Some(BaseStats::pikachu()));
+                assert_eq!(
+                    <(Vec<String>, Option<String>)>::from(pokemon.abilities),
+                    <(Vec<String>, Option<String>)>::from(PokemonAbilities::pikachu())
+                );
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_map_a_duplicate_key_to_a_conflict() {
+        let repo = SqliteRepository::open(":memory:").unwrap();
+        insert_pikachu(&repo, PokemonForm::default())
+            .ok()
+            .expect("pokemon to be inserted");
+        insert_pikachu(
+            &repo,
+            PokemonForm::try_from(String::from("partner")).unwrap(),
+        )
+        .ok()
+        .expect("form to be inserted");
+
+        match insert_pikachu(&repo, PokemonForm::default()) {
+            Err(InsertError::Conflict) => {}
+            _ => unreachable!(),
+        }
+        match repo.fetch_forms(PokemonNumber::pikachu()) {
+            Ok(forms) => assert_eq!(forms.len(), 2),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_enforce_case_insensitive_names_in_sqlite() {
+        let repo = SqliteRepository::open(":memory:").unwrap();
+        insert_pikachu(&repo, PokemonForm::default())
+            .ok()
+            .expect("pokemon to be inserted");
+        insert_pikachu(
+            &repo,
+            PokemonForm::try_from(String::from("partner")).unwrap(),
+        )
+        .ok()
+        .expect("form to be inserted");
+
+        match repo.insert(
+            PokemonNumber::charmander(),
+            PokemonForm::default(),
+            PokemonName::try_from(String::from("PIKACHU")).unwrap(),
+            PokemonTypes::charmander(),
+            None,
+            PokemonAbilities::default(),
+        ) {
+            Err(InsertError::Conflict) => {}
+            _ => unreachable!(),
+        }
+        match repo.fetch_by_name(&PokemonName::try_from(String::from("pikachu")).unwrap()) {
+            Ok(pokemon) => assert!(pokemon.form.is_default()),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_keep_the_trigram_index_in_sync_in_sqlite() {
+        let repo = SqliteRepository::open(":memory:").unwrap();
+        insert_pikachu(&repo, PokemonForm::default())
+            .ok()
+            .expect("pokemon to be inserted");
+        let typo = PokemonName::try_from(String::from("pikchu")).unwrap();
+
+        match repo.fetch_by_trigrams(&typo) {
+            Ok(pokemons) => assert_eq!(pokemons.len(), 1),
+            _ => unreachable!(),
+        }
+        repo.delete(
+            PokemonNumber::pikachu(),
+            &PokemonForm::default(),
+            None,
+            now(),
+        )
+        .ok()
+        .expect("pokemon to be deleted");
+        match repo.fetch_by_trigrams(&typo) {
+            Ok(pokemons) => assert!(pokemons.is_empty()),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_update_a_pokemon_in_sqlite() {
+        let repo = SqliteRepository::open(":memory:").unwrap();
+        let mut pokemon = insert_pikachu(&repo, PokemonForm::default())
+            .ok()
+            .expect("pokemon to be inserted");
+        pokemon.stats = None;
+
+        match repo.update(pokemon.clone(), None) {
+            Ok(_) => {}
+            _ => unreachable!(),
+        }
+        match repo.fetch(PokemonNumber::pikachu(), &PokemonForm::default()) {
+            Ok(pokemon) => assert_eq!(pokemon.stats, None),
+            _ => unreachable!(),
+        }
+        pokemon.form = PokemonForm::try_from(String::from("partner")).unwrap();
+        match repo.update(pokemon, None) {
+            Err(UpdateError::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_bump_the_version_and_refuse_stale_changes_in_sqlite() {
+        let repo = SqliteRepository::open(":memory:").unwrap();
+        let pokemon = insert_pikachu(&repo, PokemonForm::default())
+            .ok()
+            .expect("pokemon to be inserted");
+        assert_eq!(pokemon.version, Pokemon::FIRST_VERSION);
+
+        match repo.update(pokemon.clone(), Some(1)) {
+            Ok(pokemon) => assert_eq!(pokemon.version, 2),
+            _ => unreachable!(),
+        }
+        match repo.update(pokemon, Some(1)) {
+            Err(UpdateError::VersionMismatch) => {}
+            _ => unreachable!(),
+        }
+        match repo.delete(
+            PokemonNumber::pikachu(),
+            &PokemonForm::default(),
+            Some(1),
+            now(),
+        ) {
+            Err(DeleteError::VersionMismatch) => {}
+            _ => unreachable!(),
+        }
+        match repo.fetch(PokemonNumber::pikachu(), &PokemonForm::default()) {
+            Ok(pokemon) => assert_eq!(pokemon.version, 2),
+            _ => unreachable!(),
+        }
+        match repo.delete(
+            PokemonNumber::pikachu(),
+            &PokemonForm::default(),
+            Some(2),
+            now(),
+        ) {
+            Ok(_) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_filter_sort_and_paginate_in_sqlite() {
+        let repo = SqliteRepository::open(":memory:").unwrap();
+        for (n, name, types) in [
+            (4, "Charmander", vec!["Fire"]),
+            (6, "Charizard", vec!["Fire", "Flying"]),
+            (16, "Pidgey", vec!["Normal", "Flying"]),
+            (25, "Pikachu", vec!["Electric"]),
+        ] {
+            repo.insert(
+                PokemonNumber::try_from(n).unwrap(),
+                PokemonForm::default(),
+                PokemonName::try_from(String::from(name)).unwrap(),
+                PokemonTypes::try_from(types.into_iter().map(String::from).collect::<Vec<_>>())
+                    .unwrap(),
+                None,
+                PokemonAbilities::default(),
+            )
+            .ok()
+            .expect("pokemon to be inserted");
+        }
+        let numbers = |query: Query| match repo.query(&query) {
+            Ok(page) => (
+                page.pokemons
+                    .into_iter()
+                    .map(|p| u16::from(p.number))
+                    .collect::<Vec<_>>(),
+                page.total,
+            ),
+            _ => unreachable!(),
+        };
+
+        assert_eq!(
+            numbers(Query {
+                pokemon_type: Some(PokemonType::Flying),
+                ..Query::default()
+            }),
+            (vec![6, 16], 2)
+        );
+        assert_eq!(
+            numbers(Query {
+                name: Some(NameFilter::Prefix(String::from("CHAR"))),
+                numbers: Some(5..=10),
+                ..Query::default()
+            }),
+            (vec![6], 1)
+        );
+        assert_eq!(
+            numbers(Query {
+                name: Some(NameFilter::Contains(String::from("%"))),
+                ..Query::default()
+            }),
+            (vec![], 0)
+        );
+        assert_eq!(
+            numbers(Query {
+                sort: SortKey::Name,
+                descending: true,
+                offset: 1,
+                limit: Some(2),
+                ..Query::default()
+            }),
+            (vec![16, 4], 4)
+        );
+    }
+
+    #[test]
+    fn it_should_fold_only_ascii_case_in_every_backend() {
+        let sqlite = SqliteRepository::open(":memory:").unwrap();
+        let memory = InMemoryRepository::new();
+        let repos: [&dyn Repository; 2] = [&sqlite, &memory];
+        for repo in repos {
+            repo.insert(
+                PokemonNumber::try_from(239).unwrap(),
+                PokemonForm::default(),
+                PokemonName::try_from(String::from("Élekid")).unwrap(),
+                PokemonTypes::pikachu(),
+                None,
+                PokemonAbilities::default(),
+            )
+            .ok()
+            .expect("pokemon to be inserted");
+            let total = |part: &str| {
+                repo.query(&Query {
+                    name: Some(NameFilter::Contains(String::from(part))),
+                    ..Query::default()
+                })
+                .ok()
+                .map(|page| page.total)
+            };
+
+            assert_eq!(total("ÉLEKID"), Some(1));
+            assert_eq!(total("élekid"), Some(0));
+        }
+    }
+
+    #[test]
+    fn it_should_continue_versions_past_the_tombstone_in_every_backend() {
+        let dir = TempDir::new("reborn");
+        let sqlite = SqliteRepository::open(":memory:").unwrap();
+        let memory = InMemoryRepository::new();
+        let file = FileRepository::open(&dir.0).unwrap();
+        let repos: [&dyn Repository; 3] = [&sqlite, &memory, &file];
+        for repo in repos {
+            let pikachu = || {
+                Pokemon::new(
+                    PokemonNumber::pikachu(),
+                    PokemonForm::default(),
+                    PokemonName::pikachu(),
+                    PokemonTypes::pikachu(),
+                    Some(BaseStats::pikachu()),
+                    PokemonAbilities::pikachu(),
+                )
+            };
+            let delete = || {
+                repo.delete(
+                    PokemonNumber::pikachu(),
+                    &PokemonForm::default(),
+                    None,
+                    now(),
+                )
+                .ok()
+                .expect("pokemon to be deleted")
+            };
+            let p = pikachu();
+            repo.insert(p.number, p.form, p.name, p.types, p.stats, p.abilities)
+                .ok()
+                .expect("pokemon to be inserted");
+            repo.update(pikachu(), None)
+                .ok()
+                .expect("pokemon to be updated");
+            delete();
+
+            let p = pikachu();
+            match repo.insert(p.number, p.form, p.name, p.types, p.stats, p.abilities) {
+                Ok(pokemon) => assert_eq!(pokemon.version, 3),
+                _ => unreachable!(),
+            }
+            delete();
+
+            match repo.insert_many(vec![pikachu()], BulkMode::AllOrNothing) {
+                Ok(results) => match &results[..] {
+                    [Ok(pokemon)] => assert_eq!(pokemon.version, 4),
+                    _ => unreachable!(),
+                },
+                _ => unreachable!(),
+            }
+            match repo.fetch(PokemonNumber::pikachu(), &PokemonForm::default()) {
+                Ok(pokemon) => assert_eq!(pokemon.version, 4),
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_delete_a_pokemon() {
+        let repo = SqliteRepository::open(":memory:").unwrap();
+        insert_pikachu(&repo, PokemonForm::default())
+            .ok()
+            .expect("pokemon to be inserted");
+
+        match repo.delete(
+            PokemonNumber::pikachu(),
+            &PokemonForm::default(),
+            None,
+            now(),
+        ) {
+            Ok(pokemon) => assert_eq!(pokemon.number, PokemonNumber::pikachu()),
+            _ => unreachable!(),
+        }
+        match repo.fetch(PokemonNumber::pikachu(), &PokemonForm::default()) {
+            Err(FetchError::NotFound) => {}
+            _ => unreachable!(),
+        }
+        match repo.fetch_all() {
+            Ok(pokemons) => assert!(pokemons.is_empty()),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_restore_a_pokemon_from_the_trash_in_sqlite() {
+        let repo = SqliteRepository::open(":memory:").unwrap();
+        insert_pikachu(&repo, PokemonForm::default())
+            .ok()
+            .expect("pokemon to be inserted");
+        repo.delete(
+            PokemonNumber::pikachu(),
+            &PokemonForm::default(),
+            None,
+            now(),
+        )
+        .ok()
+        .expect("pokemon to be deleted");
+
+        match repo.fetch_trash() {
+            Ok(trash) => {
+                assert_eq!(trash.len(), 1);
+                assert_eq!(trash[0].pokemon.number, PokemonNumber::pikachu());
+                assert!(trash[0].deleted_at > 0);
+            }
+            _ => unreachable!(),
+        }
+        match repo.restore(PokemonNumber::pikachu(), &PokemonForm::default()) {
+            Ok(pokemon) => assert_eq!(pokemon.version, 2),
+            _ => unreachable!(),
+        }
+        match repo.fetch(PokemonNumber::pikachu(), &PokemonForm::default()) {
+            Ok(pokemon) => assert_eq!(pokemon.version, 2),
+            _ => unreachable!(),
+        }
+        assert_eq!(repo.fetch_trash().ok().map(|trash| trash.len()), Some(0));
+        match repo.restore(PokemonNumber::pikachu(), &PokemonForm::default()) {
+            Err(RestoreError::NotFound) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_not_restore_over_a_recreated_pokemon_in_sqlite() {
+        let repo = SqliteRepository::open(":memory:").unwrap();
+        insert_pikachu(&repo, PokemonForm::default())
+            .ok()
+            .expect("pokemon to be inserted");
+        repo.delete(
+            PokemonNumber::pikachu(),
+            &PokemonForm::default(),
+            None,
+            now(),
+        )
+        .ok()
+        .expect("pokemon to be deleted");
+        insert_pikachu(&repo, PokemonForm::default())
+            .ok()
+            .expect("pokemon to be inserted");
+
+        match repo.restore(PokemonNumber::pikachu(), &PokemonForm::default()) {
+            Err(RestoreError::Conflict) => {}
+            _ => unreachable!(),
+        }
+        // The tombstone stays so that the restore can be retried.
+        assert_eq!(repo.fetch_trash().ok().map(|trash| trash.len()), Some(1));
+    }
+
+    #[test]
+    fn it_should_purge_tombstones_older_than_the_cutoff_in_sqlite() {
+        let repo = SqliteRepository::open(":memory:").unwrap();
+        insert_pikachu(&repo, PokemonForm::default())
+            .ok()
+            .expect("pokemon to be inserted");
+        repo.delete(
+            PokemonNumber::pikachu(),
+            &PokemonForm::default(),
+            None,
+            now(),
+        )
+        .ok()
+        .expect("pokemon to be deleted");
+
+        assert_eq!(repo.purge_trash(0).ok(), Some(0));
+        assert_eq!(repo.purge_trash(now() + 1).ok(), Some(1));
+        assert_eq!(repo.fetch_trash().ok().map(|trash| trash.len()), Some(0));
+    }
+
+    #[test]
+    fn it_should_keep_data_and_schema_version_across_reopens() {
+        let path = std::env::temp_dir().join(format!("pokedex-{}.db", std::process::id()));
+        let path = path.to_str().unwrap();
+        {
+            let repo = SqliteRepository::open(path).unwrap();
+            insert_pikachu(&repo, PokemonForm::default())
+                .ok()
+                .expect("pokemon to be inserted");
+        }
+
+        let repo = SqliteRepository::open(path).unwrap();
+        let version: usize = repo
+            .connection
+            .lock()
+            .unwrap()
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(version, MIGRATIONS.len());
+        match repo.fetch_all() {
+            Ok(pokemons) => assert_eq!(pokemons.len(), 1),
+            _ => unreachable!(),
+        }
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn it_should_refuse_a_name_taken_through_another_connection() {
+        let path = std::env::temp_dir().join(format!("pokedex-names-{}.db", std::process::id()));
+        let path = path.to_str().unwrap();
+        let repo = SqliteRepository::open(path).unwrap();
+        insert_pikachu(&repo, PokemonForm::default())
+            .ok()
+            .expect("pokemon to be inserted");
+
+        let other = Connection::open(path).unwrap();
+        let insert = "INSERT INTO pokemons (number, form, name, types, abilities)
+            VALUES (?1, ?2, ?3, '[\"Electric\"]', '[]')";
+        assert!(other.execute(insert, params![26, "", "PIKACHU"]).is_err());
+        assert!(other
+            .execute(insert, params![25, "gmax", "Pikachu"])
+            .is_ok());
+        assert!(other
+            .execute("UPDATE pokemons SET number = 26 WHERE form = 'gmax'", [])
+            .is_err());
+        drop(repo);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn it_should_refuse_to_upgrade_a_database_whose_numbers_share_a_name() {
+        let path = std::env::temp_dir().join(format!("pokedex-shared-{}.db", std::process::id()));
+        let path = path.to_str().unwrap();
+        drop(SqliteRepository::open(path).unwrap());
+        let connection = Connection::open(path).unwrap();
+        connection
+            .execute_batch(&format!(
+                "DROP TRIGGER pokemons_name_insert;
+                DROP TRIGGER pokemons_name_update;
+                PRAGMA user_version = {};
+                INSERT INTO pokemons (number, form, name, types, abilities)
+                VALUES (25, '', 'Pikachu', '[]', '[]'), (26, '', 'pikachu', '[]', '[]');",
+                MIGRATIONS.len() - 1
+            ))
+            .unwrap();
+        drop(connection);
+
+        assert!(SqliteRepository::open(path).is_err());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn it_should_refuse_a_database_from_a_newer_schema() {
+        let path = std::env::temp_dir().join(format!("pokedex-newer-{}.db", std::process::id()));
+        let path = path.to_str().unwrap();
+        drop(SqliteRepository::open(path).unwrap());
+        let connection = Connection::open(path).unwrap();
+        connection
+            .pragma_update(None, "user_version", MIGRATIONS.len() + 1)
+            .unwrap();
+        drop(connection);
+
+        assert!(SqliteRepository::open(path).is_err());
+        let connection = Connection::open(path).unwrap();
+        let version: usize = connection
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(version, MIGRATIONS.len() + 1);
+        drop(connection);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("pokedex-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            Self(dir)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn insert_file_pokemon(repo: &FileRepository, number: u16, name: &str) {
+        repo.insert(
+            PokemonNumber::try_from(number).unwrap(),
+            PokemonForm::default(),
+            PokemonName::try_from(String::from(name)).unwrap(),
+            PokemonTypes::pikachu(),
+            Some(BaseStats::pikachu()),
+            PokemonAbilities::pikachu(),
+        )
+        .ok()
+        .expect("pokemon to be inserted");
+    }
+
+    fn numbers(repo: &FileRepository) -> Vec<u16> {
+        match repo.fetch_all() {
+            Ok(pokemons) => pokemons.into_iter().map(|p| u16::from(p.number)).collect(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_replay_a_bulk_insert_as_a_single_record() {
+        let dir = TempDir::new("bulk");
+        {
+            let repo = FileRepository::open(&dir.0).unwrap();
+            let batch = vec![pokemon(25, "Pikachu"), pokemon(25, "Pikachu")];
+            match repo.insert_many(batch, BulkMode::AllOrNothing) {
+                Err(InsertManyError::Rejected(1, InsertError::Conflict)) => {}
+                _ => unreachable!(),
+            }
+            let batch = vec![pokemon(25, "Pikachu"), pokemon(26, "Raichu")];
+            repo.insert_many(batch, BulkMode::AllOrNothing)
+                .ok()
+                .expect("pokemons to be inserted");
+        }
+
+        let log = fs::read_to_string(dir.0.join(LOG_FILE)).unwrap();
+        assert_eq!(log.lines().count(), 1);
+        let repo = FileRepository::open(&dir.0).unwrap();
+        assert_eq!(numbers(&repo), vec![25, 26]);
+    }
+
+    #[test]
+    fn it_should_read_back_numbers_outside_the_configured_range() {
+        // Stands in for data written before the operator lowered `max_number`.
+        let beyond = Pokemon {
+            number: PokemonNumber::from_stored(PokemonNumber::DEFAULT_MAX + 1),
+            ..pokemon(25, "Missingno")
+        };
+        let dir = TempDir::new("beyond-range");
+        {
+            let repo = FileRepository::open(&dir.0).unwrap();
+            repo.insert_many(vec![beyond.clone()], BulkMode::AllOrNothing)
+                .ok()
+                .expect("pokemon to be inserted");
+        }
+        let file = FileRepository::open(&dir.0).unwrap();
+        assert_eq!(numbers(&file), vec![PokemonNumber::DEFAULT_MAX + 1]);
+
+        let sqlite = SqliteRepository::open(":memory:").unwrap();
+        sqlite
+            .insert_many(vec![beyond], BulkMode::AllOrNothing)
+            .ok()
+            .expect("pokemon to be inserted");
+        match sqlite.fetch_all() {
+            Ok(pokemons) => assert_eq!(pokemons.len(), 1),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_not_log_a_batch_that_stores_nothing() {
+        let dir = TempDir::new("empty-batch");
+        let repo = FileRepository::open(&dir.0).unwrap();
+        insert_file_pokemon(&repo, 25, "Pikachu");
+
+        match repo.insert_many(vec![], BulkMode::AllOrNothing) {
+            Ok(results) => assert!(results.is_empty()),
+            _ => unreachable!(),
+        }
+        let batch = vec![pokemon(25, "Pikachu")];
+        match repo.insert_many(batch, BulkMode::ContinueOnError) {
+            Ok(results) => assert!(matches!(results[..], [Err(InsertError::Conflict)])),
+            _ => unreachable!(),
+        }
+
+        let log = fs::read_to_string(dir.0.join(LOG_FILE)).unwrap();
+        assert_eq!(log.lines().count(), 1);
+    }
+
+    #[test]
+    fn it_should_replay_the_log_after_a_restart() {
+        let dir = TempDir::new("replay");
+        {
+            let repo = FileRepository::open(&dir.0).unwrap();
+            insert_file_pokemon(&repo, 25, "Pikachu");
+            insert_file_pokemon(&repo, 4, "Charmander");
+            insert_file_pokemon(&repo, 7, "Squirtle");
+            repo.delete(
+                PokemonNumber::charmander(),
+                &PokemonForm::default(),
+                None,
+                now(),
+            )
+            .ok()
+            .expect("pokemon to be deleted");
+            let mut pikachu = repo
+                .fetch(PokemonNumber::pikachu(), &PokemonForm::default())
+                .ok()
+                .expect("pokemon to be fetched");
+            pikachu.stats = None;
+            repo.update(pikachu, None)
+                .ok()
+                .expect("pokemon to be updated");
+        }
+
+        let repo = FileRepository::open(&dir.0).unwrap();
+        assert_eq!(numbers(&repo), vec![7, 25]);
+        match repo.fetch(PokemonNumber::pikachu(), &PokemonForm::default()) {
+            Ok(pokemon) => {
+                assert_eq!(pokemon.stats, None);
+                assert_eq!(pokemon.version, 2);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_keep_the_trash_across_restarts() {
+        let dir = TempDir::new("trash");
+        {
+            let repo = FileRepository::open(&dir.0).unwrap();
+            insert_file_pokemon(&repo, 25, "Pikachu");
+            insert_file_pokemon(&repo, 4, "Charmander");
+            insert_file_pokemon(&repo, 7, "Squirtle");
+            repo.delete(
+                PokemonNumber::pikachu(),
+                &PokemonForm::default(),
+                None,
+                now(),
+            )
+            .ok()
+            .expect("pokemon to be deleted");
+            repo.delete(
+                PokemonNumber::charmander(),
+                &PokemonForm::default(),
+                None,
+                now(),
+            )
+            .ok()
+            .expect("pokemon to be deleted");
+            repo.store.lock().ok().unwrap().compact().unwrap();
+            repo.restore(PokemonNumber::pikachu(), &PokemonForm::default())
+                .ok()
+                .expect("pokemon to be restored");
+            repo.delete(
+                PokemonNumber::try_from(7).unwrap(),
+                &PokemonForm::default(),
+                None,
+                now(),
+            )
+            .ok()
+            .expect("pokemon to be deleted");
+        }
+
+        let repo = FileRepository::open(&dir.0).unwrap();
+        assert_eq!(numbers(&repo), vec![25]);
+        match repo.fetch_trash() {
+            Ok(trash) => assert_eq!(
+                trash
+                    .into_iter()
+                    .map(|t| u16::from(t.pokemon.number))
+                    .collect::<Vec<_>>(),
+                vec![4, 7]
+            ),
+            _ => unreachable!(),
+        }
+        assert_eq!(repo.purge_trash(0).ok(), Some(0));
+        assert_eq!(repo.purge_trash(now() + 1).ok(), Some(2));
+        drop(repo);
+
+        let repo = FileRepository::open(&dir.0).unwrap();
+        assert_eq!(repo.fetch_trash().ok().map(|trash| trash.len()), Some(0));
+    }
+
+    #[test]
+    fn it_should_compact_the_log_into_a_snapshot() {
+        let dir = TempDir::new("compact");
+        {
+            let repo = FileRepository::open(&dir.0).unwrap().compact_every(2);
+            insert_file_pokemon(&repo, 1, "Bulbasaur");
+            insert_file_pokemon(&repo, 4, "Charmander");
+            insert_file_pokemon(&repo, 7, "Squirtle");
+        }
+        assert!(dir.0.join(SNAPSHOT_FILE).exists());
+        assert_eq!(
+            fs::read_to_string(dir.0.join(LOG_FILE))
+                .unwrap()
+                .lines()
+                .count(),
+            1
+        );
+
+        let repo = FileRepository::open(&dir.0).unwrap();
+        assert_eq!(numbers(&repo), vec![1, 4, 7]);
+    }
+
+    #[test]
+    fn it_should_truncate_a_torn_last_record() {
+        let dir = TempDir::new("torn");
+        {
+            let repo = FileRepository::open(&dir.0).unwrap();
+            insert_file_pokemon(&repo, 25, "Pikachu");
+        }
+        let log = dir.0.join(LOG_FILE);
+        let good_len = fs::metadata(&log).unwrap().len();
+        OpenOptions::new()
+            .append(true)
+            .open(&log)
+            .unwrap()
+            .write_all(br#"{"op":"insert","seq":2,"pokemon":{"numb"#)
+            .unwrap();
+
+        {
+            let repo = FileRepository::open(&dir.0).unwrap();
+            assert_eq!(numbers(&repo), vec![25]);
+            assert_eq!(fs::metadata(&log).unwrap().len(), good_len);
+            insert_file_pokemon(&repo, 4, "Charmander");
+        }
+
+        let repo = FileRepository::open(&dir.0).unwrap();
+        assert_eq!(numbers(&repo), vec![4, 25]);
+    }
+
+    #[test]
+    fn it_should_refuse_a_data_directory_another_repository_holds() {
+        let dir = TempDir::new("lock");
+        let repo = FileRepository::open(&dir.0).unwrap();
+
+        match FileRepository::open(&dir.0) {
+            Err(e) => assert_eq!(e.kind(), io::ErrorKind::WouldBlock),
+            _ => unreachable!(),
+        }
+        drop(repo);
+        assert!(FileRepository::open(&dir.0).is_ok());
+    }
+
+    #[test]
+    fn it_should_refuse_a_snapshot_whose_numbers_share_a_name() {
+        let dir = TempDir::new("shared-names");
+        fs::create_dir_all(&dir.0).unwrap();
+        let pokemon = |number: u16, name: &str| {
+            serde_json::json!({
+                "number": number,
+                "form": null,
+                "name": name,
+                "types": ["Electric"],
+                "stats": null,
+                "abilities": [],
+                "hidden_ability": null,
+            })
+        };
+        let snapshot = serde_json::json!({
+            "seq": 2,
+            "pokemons": [pokemon(25, "Pikachu"), pokemon(26, "PIKACHU")],
+        });
+        fs::write(dir.0.join(SNAPSHOT_FILE), snapshot.to_string()).unwrap();
+
+        assert!(FileRepository::open(&dir.0).is_err());
+    }
+
+    #[test]
+    fn it_should_refuse_a_log_corrupted_before_its_last_record() {
+        let dir = TempDir::new("corrupt");
+        {
+            let repo = FileRepository::open(&dir.0).unwrap();
+            insert_file_pokemon(&repo, 25, "Pikachu");
+        }
+        let log = dir.0.join(LOG_FILE);
+        let contents = fs::read_to_string(&log).unwrap();
+        fs::write(&log, format!("garbage\n{}", contents)).unwrap();
+
+        assert!(FileRepository::open(&dir.0).is_err());
+    }
+
+    #[test]
+    fn it_should_not_replay_records_already_in_the_snapshot() {
+        let dir = TempDir::new("stale-log");
+        let log = dir.0.join(LOG_FILE);
+        let stale = {
+            let repo = FileRepository::open(&dir.0).unwrap();
+            insert_file_pokemon(&repo, 25, "Pikachu");
+            insert_file_pokemon(&repo, 4, "Charmander");
+            let stale = fs::read(&log).unwrap();
+            repo.store.lock().ok().unwrap().compact().unwrap();
+            repo.delete(
+                PokemonNumber::charmander(),
+                &PokemonForm::default(),
+                None,
+                now(),
+            )
+            .ok()
+            .expect("pokemon to be deleted");
+            stale
+        };
+        // A crash between writing the snapshot and truncating the log leaves old records behind.
+        let current = fs::read(&log).unwrap();
+        fs::write(&log, [stale, current].concat()).unwrap();
+
+        let repo = FileRepository::open(&dir.0).unwrap();
+        assert_eq!(numbers(&repo), vec![25]);
     }
 }